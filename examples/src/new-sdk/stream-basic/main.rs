@@ -1,5 +1,5 @@
 use crate::shared::stream::PrintEventConsumer;
-use iggy::client::{Client, StreamClient};
+use iggy::client::Client;
 use iggy_examples::shared;
 use sdk::builder::*;
 use std::str::FromStr;
@@ -37,7 +37,7 @@ async fn main() -> Result<(), IggyError> {
 
     println!("Stop the message stream and shutdown iggy client");
     sender.send(()).expect("Failed to send shutdown signal");
-    client.delete_stream(stream_config.stream_id()).await?;
+    IggyStream::delete_stream(&client, &stream_config).await?;
     client.shutdown().await?;
 
     Ok(())
@@ -1,4 +1,4 @@
-use iggy::client::{Client, StreamClient};
+use iggy::client::Client;
 use iggy::error::IggyError;
 use iggy::messages::send_messages::Message;
 use sdk::builder::{IggyProducerConfig, IggyStreamProducer};
@@ -21,7 +21,7 @@ async fn main() -> Result<(), IggyError> {
     // wait a bit for all messages to arrive.
     tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
     println!("Stop the message stream and shutdown iggy client");
-    client.delete_stream(config.stream_id()).await?;
+    IggyStreamProducer::delete_stream(&client, &config).await?;
     client.shutdown().await?;
     Ok(())
 }
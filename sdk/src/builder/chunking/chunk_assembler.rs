@@ -0,0 +1,265 @@
+use crate::builder::{
+    MessageHeadersExt, CHUNK_COUNT_HEADER, CHUNK_INDEX_HEADER, TRANSFER_CHECKSUM_HEADER,
+    TRANSFER_ID_HEADER,
+};
+use bytes::{Bytes, BytesMut};
+use iggy::error::IggyError;
+use iggy::models::header::HeaderKey;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::byte_size::IggyByteSize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::error;
+
+/// The default max number of chunks of a single transfer.
+pub const DEFAULT_MAX_CHUNKS: u32 = 4096;
+
+/// The default max size of all buffered chunks in bytes, 256 MiB.
+pub const DEFAULT_MAX_PENDING_BYTES: usize = 256 * 1024 * 1024;
+
+/// A transfer that did not receive all chunks within the timeout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IncompleteTransfer {
+    pub transfer_id: u128,
+    pub partition_id: u32,
+    pub first_offset: u64,
+    pub received_chunks: u32,
+    pub chunk_count: u32,
+}
+
+#[derive(Debug)]
+struct PartialTransfer {
+    partition_id: u32,
+    first_offset: u64,
+    chunk_count: u32,
+    checksum: u32,
+    chunks: Vec<Option<Bytes>>,
+    received_chunks: u32,
+    started_at: Instant,
+}
+
+/// Reassembles the chunks written by the `ChunkingEventProducer` into a single message.
+///
+/// Messages without chunk headers pass through unchanged. The reassembled message has the
+/// transfer ID as its ID, the offset of the last received chunk, and the headers of that
+/// chunk without the chunk headers.
+///
+/// Transfers with more than `max_chunks` chunks are rejected, and so is a chunk that would
+/// raise the size of all buffered chunks above `max_pending_bytes`, which drops its transfer.
+///
+/// The assembler only holds chunks in memory. A consumer that commits the offsets of
+/// buffered chunks loses the transfer if it restarts before the last chunk arrives.
+#[derive(Debug)]
+pub struct ChunkAssembler {
+    timeout: Duration,
+    max_chunks: u32,
+    max_pending_bytes: usize,
+    pending_bytes: usize,
+    transfers: HashMap<u128, PartialTransfer>,
+}
+
+impl ChunkAssembler {
+    /// Creates a new `ChunkAssembler` with the default limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the remaining chunks of a transfer.
+    ///
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            max_chunks: DEFAULT_MAX_CHUNKS,
+            max_pending_bytes: DEFAULT_MAX_PENDING_BYTES,
+            pending_bytes: 0,
+            transfers: HashMap::new(),
+        }
+    }
+
+    /// Returns this assembler with the given limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_chunks` - The max number of chunks of a single transfer.
+    /// * `max_pending_bytes` - The max size of all buffered chunks in bytes.
+    ///
+    pub fn with_limits(self, max_chunks: u32, max_pending_bytes: usize) -> Self {
+        Self {
+            max_chunks,
+            max_pending_bytes,
+            ..self
+        }
+    }
+
+    /// Returns the number of transfers that are waiting for chunks.
+    pub fn pending_transfers(&self) -> usize {
+        self.transfers.len()
+    }
+
+    /// Returns the size of all buffered chunks in bytes.
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_bytes
+    }
+
+    /// Adds a message and returns the complete message once all chunks are received.
+    ///
+    /// Returns `None` while chunks of the transfer are missing.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotDeserializeResource` - If the chunk headers are invalid, a limit is
+    ///   exceeded, or the checksum of the reassembled payload does not match. The transfer
+    ///   is dropped.
+    ///
+    pub fn push(
+        &mut self,
+        partition_id: u32,
+        message: PolledMessage,
+    ) -> Result<Option<PolledMessage>, IggyError> {
+        let Some(transfer_id) = message.header(TRANSFER_ID_HEADER) else {
+            return Ok(Some(message));
+        };
+
+        let (transfer_id, index, chunk_count, checksum) = match (
+            transfer_id.as_uint128(),
+            chunk_header(&message, CHUNK_INDEX_HEADER),
+            chunk_header(&message, CHUNK_COUNT_HEADER),
+            chunk_header(&message, TRANSFER_CHECKSUM_HEADER),
+        ) {
+            (Ok(transfer_id), Some(index), Some(count), Some(checksum)) if index < count => {
+                (transfer_id, index, count, checksum)
+            }
+            _ => {
+                error!("Invalid chunk headers at offset: {}", message.offset);
+                return Err(IggyError::CannotDeserializeResource);
+            }
+        };
+
+        if chunk_count > self.max_chunks {
+            error!(
+                "Transfer {transfer_id} has {chunk_count} chunks, more than the limit of {}",
+                self.max_chunks
+            );
+            self.remove_transfer(transfer_id);
+            return Err(IggyError::CannotDeserializeResource);
+        }
+
+        if self.pending_bytes + message.payload.len() > self.max_pending_bytes {
+            error!(
+                "Chunk at offset: {} exceeds the limit of {} pending bytes",
+                message.offset, self.max_pending_bytes
+            );
+            self.remove_transfer(transfer_id);
+            return Err(IggyError::CannotDeserializeResource);
+        }
+
+        let transfer = self
+            .transfers
+            .entry(transfer_id)
+            .or_insert_with(|| PartialTransfer {
+                partition_id,
+                first_offset: message.offset,
+                chunk_count,
+                checksum,
+                chunks: vec![None; chunk_count as usize],
+                received_chunks: 0,
+                started_at: Instant::now(),
+            });
+
+        if transfer.chunk_count != chunk_count || transfer.checksum != checksum {
+            error!(
+                "Chunk at offset: {} does not match transfer {transfer_id}",
+                message.offset
+            );
+            self.remove_transfer(transfer_id);
+            return Err(IggyError::CannotDeserializeResource);
+        }
+
+        let slot = &mut transfer.chunks[index as usize];
+        if slot.is_none() {
+            *slot = Some(message.payload.clone());
+            transfer.received_chunks += 1;
+            self.pending_bytes += message.payload.len();
+        }
+
+        if transfer.received_chunks < transfer.chunk_count {
+            return Ok(None);
+        }
+
+        let Some(transfer) = self.remove_transfer(transfer_id) else {
+            return Ok(None);
+        };
+        let mut payload = BytesMut::new();
+        for chunk in transfer.chunks.into_iter().flatten() {
+            payload.extend_from_slice(&chunk);
+        }
+
+        if crc32fast::hash(&payload) != transfer.checksum {
+            error!("Checksum mismatch for transfer {transfer_id}");
+            return Err(IggyError::CannotDeserializeResource);
+        }
+
+        let mut headers = message.headers;
+        if let Some(headers) = headers.as_mut() {
+            for key in [
+                TRANSFER_ID_HEADER,
+                CHUNK_INDEX_HEADER,
+                CHUNK_COUNT_HEADER,
+                TRANSFER_CHECKSUM_HEADER,
+            ] {
+                headers.remove(&HeaderKey::new(key)?);
+            }
+        }
+
+        Ok(Some(PolledMessage {
+            offset: message.offset,
+            state: message.state,
+            timestamp: message.timestamp,
+            id: transfer_id,
+            checksum: message.checksum,
+            headers,
+            length: IggyByteSize::from(payload.len() as u64),
+            payload: payload.freeze(),
+        }))
+    }
+
+    /// Drops the transfers that did not complete within the timeout and returns them.
+    pub fn evict_expired(&mut self) -> Vec<IncompleteTransfer> {
+        let now = Instant::now();
+        let expired = self
+            .transfers
+            .iter()
+            .filter(|(_, transfer)| now.duration_since(transfer.started_at) >= self.timeout)
+            .map(|(transfer_id, _)| *transfer_id)
+            .collect::<Vec<_>>();
+
+        expired
+            .into_iter()
+            .filter_map(|transfer_id| {
+                let transfer = self.remove_transfer(transfer_id)?;
+                Some(IncompleteTransfer {
+                    transfer_id,
+                    partition_id: transfer.partition_id,
+                    first_offset: transfer.first_offset,
+                    received_chunks: transfer.received_chunks,
+                    chunk_count: transfer.chunk_count,
+                })
+            })
+            .collect()
+    }
+
+    fn remove_transfer(&mut self, transfer_id: u128) -> Option<PartialTransfer> {
+        let transfer = self.transfers.remove(&transfer_id)?;
+        let buffered = transfer
+            .chunks
+            .iter()
+            .flatten()
+            .map(Bytes::len)
+            .sum::<usize>();
+        self.pending_bytes -= buffered;
+        Some(transfer)
+    }
+}
+
+fn chunk_header(message: &PolledMessage, key: &str) -> Option<u32> {
+    message.header(key)?.as_uint32().ok()
+}
@@ -0,0 +1,151 @@
+use crate::builder::{
+    EventProducer, MessageHeaders, CHUNK_COUNT_HEADER, CHUNK_INDEX_HEADER,
+    TRANSFER_CHECKSUM_HEADER, TRANSFER_ID_HEADER,
+};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::header::HeaderValue;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+/// `EventProducer` that splits payloads larger than `max_chunk_size` into numbered chunks.
+///
+/// Every chunk carries the headers of the original message plus a shared `transfer-id`,
+/// its `chunk-index`, the `chunk-count` and a CRC32 `transfer-checksum` of the complete payload.
+/// The transfer ID is the ID of the original message, or a new UUIDv7 if it has none, and
+/// becomes the ID of the reassembled message. The ID of each chunk is derived from the transfer
+/// ID and the chunk index, so a resent chunk keeps its ID and message deduplication recognises
+/// it. The consume loop reassembles the chunks before the `EventConsumer` sees the message.
+///
+/// The chunks of a message are sent as one batch. Make sure they land on the same partition,
+/// e.g. with a partition key or a `batch_size` of at least the chunk count. To combine chunking
+/// with compression, wrap the `ChunkingEventProducer` in the `CompressingEventProducer`, so the
+/// complete payload is compressed before it is split.
+#[derive(Debug)]
+pub struct ChunkingEventProducer<P> {
+    producer: P,
+    max_chunk_size: usize,
+}
+
+impl<P> ChunkingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `ChunkingEventProducer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    /// * `max_chunk_size` - The max payload size of a single message in bytes.
+    ///
+    pub fn new(producer: P, max_chunk_size: usize) -> Self {
+        Self {
+            producer,
+            max_chunk_size: max_chunk_size.max(1),
+        }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the max payload size of a single message in bytes.
+    pub fn max_chunk_size(&self) -> usize {
+        self.max_chunk_size
+    }
+
+    /// Splits the message into chunks, or returns it unchanged if it fits into one message.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the chunk headers cannot be created.
+    ///
+    pub fn split(&self, message: Message) -> Result<Vec<Message>, IggyError> {
+        if message.payload.len() <= self.max_chunk_size {
+            return Ok(vec![message]);
+        }
+
+        let transfer_id = match message.id {
+            0 => Uuid::now_v7().as_u128(),
+            id => id,
+        };
+        let checksum = crc32fast::hash(&message.payload);
+        let chunk_count = message.payload.len().div_ceil(self.max_chunk_size);
+        let Ok(chunk_count) = u32::try_from(chunk_count) else {
+            error!(
+                "Payload of {} bytes needs too many chunks",
+                message.payload.len()
+            );
+            return Err(IggyError::TooBigMessagePayload);
+        };
+        debug!("Splitting transfer {transfer_id} into {chunk_count} chunks");
+
+        let mut chunks = Vec::with_capacity(chunk_count as usize);
+        for (index, start) in (0..message.payload.len())
+            .step_by(self.max_chunk_size)
+            .enumerate()
+        {
+            let end = (start + self.max_chunk_size).min(message.payload.len());
+            let mut headers = MessageHeaders::new();
+            if let Some(existing) = &message.headers {
+                headers = headers.extend(existing);
+            }
+            let headers = headers
+                .insert(TRANSFER_ID_HEADER, HeaderValue::from_uint128(transfer_id)?)
+                .insert(CHUNK_INDEX_HEADER, HeaderValue::from_uint32(index as u32)?)
+                .insert(CHUNK_COUNT_HEADER, HeaderValue::from_uint32(chunk_count)?)
+                .insert(
+                    TRANSFER_CHECKSUM_HEADER,
+                    HeaderValue::from_uint32(checksum)?,
+                )
+                .build()?;
+
+            chunks.push(Message::new(
+                Some(chunk_id(transfer_id, index as u32)),
+                message.payload.slice(start..end),
+                Some(headers),
+            ));
+        }
+
+        Ok(chunks)
+    }
+}
+
+impl<P> EventProducer for ChunkingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        let mut chunks = self.split(message)?;
+        if chunks.len() == 1 {
+            return self.producer.send_one_event(chunks.remove(0)).await;
+        }
+        self.producer.send_event_batch(chunks).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        let mut batch = Vec::with_capacity(messages.len());
+        for message in messages {
+            batch.extend(self.split(message)?);
+        }
+        self.producer.send_event_batch(batch).await
+    }
+}
+
+/// Derives the ID of a chunk from the transfer ID and the chunk index with a 128-bit FNV-1a
+/// hash, so the ID is the same every time the message is split.
+fn chunk_id(transfer_id: u128, index: u32) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    let id = transfer_id
+        .to_le_bytes()
+        .iter()
+        .chain(index.to_le_bytes().iter())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ *byte as u128).wrapping_mul(PRIME)
+        });
+    // The ID 0 makes the server generate a new ID.
+    id.max(1)
+}
@@ -0,0 +1,7 @@
+mod chunk_assembler;
+mod chunking_event_producer;
+
+pub use chunk_assembler::{
+    ChunkAssembler, IncompleteTransfer, DEFAULT_MAX_CHUNKS, DEFAULT_MAX_PENDING_BYTES,
+};
+pub use chunking_event_producer::ChunkingEventProducer;
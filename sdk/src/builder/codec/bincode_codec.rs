@@ -0,0 +1,29 @@
+use crate::builder::EventCodec;
+use iggy::error::IggyError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::error;
+
+/// Codec that serializes values with bincode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BincodeCodec {}
+
+impl EventCodec for BincodeCodec {
+    fn content_type(&self) -> &'static str {
+        "application/x-bincode"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, IggyError> {
+        bincode::serialize(value).map_err(|err| {
+            error!("Failed to serialize value to bincode: {err}");
+            IggyError::CannotSerializeResource
+        })
+    }
+
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, IggyError> {
+        bincode::deserialize(payload).map_err(|err| {
+            error!("Failed to deserialize value from bincode: {err}");
+            IggyError::CannotDeserializeResource
+        })
+    }
+}
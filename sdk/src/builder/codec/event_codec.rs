@@ -0,0 +1,25 @@
+use iggy::error::IggyError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Serializes typed values into message payloads and back.
+pub trait EventCodec: Send + Sync {
+    /// Returns the content type written into the `content-type` header of each message.
+    fn content_type(&self) -> &'static str;
+
+    /// Serializes the value into a message payload.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotSerializeResource` - If the value cannot be serialized.
+    ///
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, IggyError>;
+
+    /// Deserializes a message payload into a value.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotDeserializeResource` - If the payload cannot be deserialized.
+    ///
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, IggyError>;
+}
@@ -0,0 +1,29 @@
+use crate::builder::EventCodec;
+use iggy::error::IggyError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::error;
+
+/// Codec that serializes values as JSON.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonCodec {}
+
+impl EventCodec for JsonCodec {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, IggyError> {
+        serde_json::to_vec(value).map_err(|err| {
+            error!("Failed to serialize value to JSON: {err}");
+            IggyError::CannotSerializeResource
+        })
+    }
+
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, IggyError> {
+        serde_json::from_slice(payload).map_err(|err| {
+            error!("Failed to deserialize value from JSON: {err}");
+            IggyError::CannotDeserializeResource
+        })
+    }
+}
@@ -0,0 +1,9 @@
+mod bincode_codec;
+mod event_codec;
+mod json_codec;
+mod msgpack_codec;
+
+pub use bincode_codec::BincodeCodec;
+pub use event_codec::EventCodec;
+pub use json_codec::JsonCodec;
+pub use msgpack_codec::MessagePackCodec;
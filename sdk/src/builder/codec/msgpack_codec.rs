@@ -0,0 +1,32 @@
+use crate::builder::EventCodec;
+use iggy::error::IggyError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::error;
+
+/// Codec that serializes values as MessagePack.
+///
+/// Structs are encoded as maps with field names, so fields can be added
+/// without breaking existing consumers.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MessagePackCodec {}
+
+impl EventCodec for MessagePackCodec {
+    fn content_type(&self) -> &'static str {
+        "application/msgpack"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, IggyError> {
+        rmp_serde::to_vec_named(value).map_err(|err| {
+            error!("Failed to serialize value to MessagePack: {err}");
+            IggyError::CannotSerializeResource
+        })
+    }
+
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, IggyError> {
+        rmp_serde::from_slice(payload).map_err(|err| {
+            error!("Failed to deserialize value from MessagePack: {err}");
+            IggyError::CannotDeserializeResource
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use crate::builder::{CompressionStats, EventProducer, MessageHeaders, PayloadCompression};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::sync::Arc;
+
+/// `EventProducer` that compresses message payloads before sending them.
+///
+/// Compressed messages carry a `content-encoding` header with the algorithm name.
+/// Payloads smaller than `min_size_bytes`, and payloads that do not get smaller,
+/// are sent unchanged without the header. The consume loop decompresses the payloads
+/// before the `EventConsumer` sees them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let producer = IggyStreamProducer::with_compression(&client, &config, PayloadCompression::zstd(), 1024).await?;
+/// producer.send_one_event(message).await?;
+/// info!("Compression ratio: {:.2}", producer.stats().ratio());
+/// ```
+#[derive(Debug)]
+pub struct CompressingEventProducer<P> {
+    producer: P,
+    compression: PayloadCompression,
+    min_size_bytes: usize,
+    stats: Arc<CompressionStats>,
+}
+
+impl<P> CompressingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `CompressingEventProducer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    /// * `compression` - The compression algorithm.
+    /// * `min_size_bytes` - Payloads smaller than this are not compressed.
+    ///
+    pub fn new(producer: P, compression: PayloadCompression, min_size_bytes: usize) -> Self {
+        Self {
+            producer,
+            compression,
+            min_size_bytes,
+            stats: Arc::new(CompressionStats::default()),
+        }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the compression algorithm.
+    pub fn compression(&self) -> PayloadCompression {
+        self.compression
+    }
+
+    /// Returns the compression stats, shared with all clones of the returned `Arc`.
+    pub fn stats(&self) -> Arc<CompressionStats> {
+        self.stats.clone()
+    }
+
+    fn compress_message(&self, message: Message) -> Result<Message, IggyError> {
+        let uncompressed = message.payload.len();
+        if uncompressed < self.min_size_bytes {
+            self.stats.record(uncompressed, uncompressed, false);
+            return Ok(message);
+        }
+
+        let compressed = self.compression.compress(&message.payload)?;
+        if compressed.len() >= uncompressed {
+            self.stats.record(uncompressed, uncompressed, false);
+            return Ok(message);
+        }
+
+        self.stats.record(uncompressed, compressed.len(), true);
+        let mut headers = MessageHeaders::new();
+        if let Some(existing) = &message.headers {
+            headers = headers.extend(existing);
+        }
+        let headers = headers.content_encoding(self.compression.name()).build()?;
+        Ok(Message::new(
+            Some(message.id),
+            compressed.into(),
+            Some(headers),
+        ))
+    }
+}
+
+impl<P> EventProducer for CompressingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        let message = self.compress_message(message)?;
+        self.producer.send_one_event(message).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        let messages = messages
+            .into_iter()
+            .map(|message| self.compress_message(message))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.producer.send_event_batch(messages).await
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters of the payload sizes before and after compression.
+#[derive(Debug, Default)]
+pub struct CompressionStats {
+    messages: AtomicU64,
+    compressed_messages: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
+}
+
+impl CompressionStats {
+    pub(crate) fn record(&self, uncompressed: usize, sent: usize, compressed: bool) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        if compressed {
+            self.compressed_messages.fetch_add(1, Ordering::Relaxed);
+        }
+        self.uncompressed_bytes
+            .fetch_add(uncompressed as u64, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(sent as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the number of messages seen.
+    pub fn messages(&self) -> u64 {
+        self.messages.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages sent compressed.
+    pub fn compressed_messages(&self) -> u64 {
+        self.compressed_messages.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total payload size before compression.
+    pub fn uncompressed_bytes(&self) -> u64 {
+        self.uncompressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total payload size after compression, including payloads sent uncompressed.
+    pub fn compressed_bytes(&self) -> u64 {
+        self.compressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the ratio of uncompressed to compressed bytes, e.g. `4.0` if the payloads
+    /// shrank to a quarter. Returns `1.0` if no message was seen yet.
+    pub fn ratio(&self) -> f64 {
+        let compressed = self.compressed_bytes();
+        if compressed == 0 {
+            return 1.0;
+        }
+        self.uncompressed_bytes() as f64 / compressed as f64
+    }
+}
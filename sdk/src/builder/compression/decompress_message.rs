@@ -0,0 +1,55 @@
+use crate::builder::{
+    MessageHeadersExt, PayloadCompression, CONTENT_ENCODING_HEADER, DEFAULT_MAX_DECOMPRESSED_SIZE,
+};
+use iggy::error::IggyError;
+use iggy::models::header::HeaderKey;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::byte_size::IggyByteSize;
+use tracing::error;
+
+/// Decompresses the payload of a message that carries a `content-encoding` header and
+/// removes the header. Messages without the header are returned unchanged.
+///
+/// The decompressed payload may be at most `DEFAULT_MAX_DECOMPRESSED_SIZE` bytes.
+///
+/// # Errors
+///
+/// * `IggyError::CannotDeserializeResource` - If the encoding is unknown or the payload
+///   cannot be decompressed.
+///
+pub fn decompress_polled_message(message: PolledMessage) -> Result<PolledMessage, IggyError> {
+    decompress_polled_message_with_limit(message, DEFAULT_MAX_DECOMPRESSED_SIZE)
+}
+
+/// Decompresses the payload of a message like `decompress_polled_message`, with a custom
+/// max size of the decompressed payload.
+///
+/// # Errors
+///
+/// * `IggyError::CannotDeserializeResource` - If the encoding is unknown or the payload
+///   cannot be decompressed within `max_decompressed_size` bytes.
+///
+pub fn decompress_polled_message_with_limit(
+    mut message: PolledMessage,
+    max_decompressed_size: usize,
+) -> Result<PolledMessage, IggyError> {
+    let Some(encoding) = message.header_str(CONTENT_ENCODING_HEADER) else {
+        return Ok(message);
+    };
+
+    let Some(compression) = PayloadCompression::from_name(encoding) else {
+        error!(
+            "Unknown content encoding: {encoding} at offset: {}",
+            message.offset
+        );
+        return Err(IggyError::CannotDeserializeResource);
+    };
+
+    let payload = compression.decompress(&message.payload, max_decompressed_size)?;
+    if let Some(headers) = message.headers.as_mut() {
+        headers.remove(&HeaderKey::new(CONTENT_ENCODING_HEADER)?);
+    }
+    message.length = IggyByteSize::from(payload.len() as u64);
+    message.payload = payload.into();
+    Ok(message)
+}
@@ -0,0 +1,9 @@
+mod compressing_event_producer;
+mod compression_stats;
+mod decompress_message;
+mod payload_compression;
+
+pub use compressing_event_producer::CompressingEventProducer;
+pub use compression_stats::CompressionStats;
+pub use decompress_message::{decompress_polled_message, decompress_polled_message_with_limit};
+pub use payload_compression::{PayloadCompression, DEFAULT_MAX_DECOMPRESSED_SIZE};
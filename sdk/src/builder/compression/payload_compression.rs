@@ -0,0 +1,138 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use iggy::error::IggyError;
+use std::io::{Read, Write};
+use tracing::error;
+
+/// The default max size of a decompressed payload, 64 MiB.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Algorithm used to compress message payloads on the client.
+///
+/// The name of the algorithm is written into the `content-encoding` header, so the consumer
+/// can decompress the payload without further configuration.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PayloadCompression {
+    /// Zstandard with the given level (1-22).
+    Zstd { level: i32 },
+    /// LZ4 block format with the uncompressed size prepended.
+    Lz4,
+    /// Gzip with the given level (0-9).
+    Gzip { level: u32 },
+}
+
+impl PayloadCompression {
+    /// Zstandard with the default level 3.
+    pub fn zstd() -> Self {
+        PayloadCompression::Zstd { level: 3 }
+    }
+
+    /// LZ4.
+    pub fn lz4() -> Self {
+        PayloadCompression::Lz4
+    }
+
+    /// Gzip with the default level 6.
+    pub fn gzip() -> Self {
+        PayloadCompression::Gzip { level: 6 }
+    }
+
+    /// Returns the name written into the `content-encoding` header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PayloadCompression::Zstd { .. } => "zstd",
+            PayloadCompression::Lz4 => "lz4",
+            PayloadCompression::Gzip { .. } => "gzip",
+        }
+    }
+
+    /// Returns the algorithm for the given `content-encoding` header value, with the default level.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "zstd" => Some(Self::zstd()),
+            "lz4" => Some(Self::lz4()),
+            "gzip" => Some(Self::gzip()),
+            _ => None,
+        }
+    }
+
+    /// Compresses the payload.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotSerializeResource` - If the payload cannot be compressed.
+    ///
+    pub fn compress(&self, payload: &[u8]) -> Result<Vec<u8>, IggyError> {
+        let res = match self {
+            PayloadCompression::Zstd { level } => zstd::bulk::compress(payload, *level),
+            PayloadCompression::Lz4 => Ok(lz4_flex::compress_prepend_size(payload)),
+            PayloadCompression::Gzip { level } => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(*level));
+                encoder.write_all(payload).and_then(|_| encoder.finish())
+            }
+        };
+
+        res.map_err(|err| {
+            error!("Failed to compress payload with {}: {err}", self.name());
+            IggyError::CannotSerializeResource
+        })
+    }
+
+    /// Decompresses the payload.
+    ///
+    /// Stops as soon as the output exceeds `max_decompressed_size`, so a small malicious
+    /// payload cannot expand into an unbounded allocation.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotDeserializeResource` - If the payload is not valid for the algorithm
+    ///   or decompresses to more than `max_decompressed_size` bytes.
+    ///
+    pub fn decompress(
+        &self,
+        payload: &[u8],
+        max_decompressed_size: usize,
+    ) -> Result<Vec<u8>, IggyError> {
+        let limit = max_decompressed_size as u64 + 1;
+        let res = match self {
+            PayloadCompression::Zstd { .. } => zstd::stream::read::Decoder::new(payload)
+                .and_then(|decoder| read_limited(decoder.take(limit))),
+            // The size prefix is checked before the output buffer is allocated.
+            PayloadCompression::Lz4 => match payload.get(..4) {
+                Some(size)
+                    if u32::from_le_bytes([size[0], size[1], size[2], size[3]]) as u64 >= limit =>
+                {
+                    return Err(self.size_exceeded(max_decompressed_size));
+                }
+                _ => lz4_flex::decompress_size_prepended(payload)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+            },
+            PayloadCompression::Gzip { .. } => read_limited(GzDecoder::new(payload).take(limit)),
+        };
+
+        match res {
+            Ok(decompressed) if decompressed.len() > max_decompressed_size => {
+                Err(self.size_exceeded(max_decompressed_size))
+            }
+            Ok(decompressed) => Ok(decompressed),
+            Err(err) => {
+                error!("Failed to decompress payload with {}: {err}", self.name());
+                Err(IggyError::CannotDeserializeResource)
+            }
+        }
+    }
+
+    fn size_exceeded(&self, max_decompressed_size: usize) -> IggyError {
+        error!(
+            "Payload decompressed with {} exceeds the limit of {max_decompressed_size} bytes",
+            self.name()
+        );
+        IggyError::CannotDeserializeResource
+    }
+}
+
+fn read_limited(mut reader: impl Read) -> std::io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    reader.read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
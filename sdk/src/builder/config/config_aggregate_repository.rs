@@ -0,0 +1,78 @@
+use crate::builder::IggyConsumerConfig;
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+
+#[derive(Builder, Debug, Clone)]
+pub struct AggregateRepositoryConfig {
+    topic_config: IggyConsumerConfig,
+    snapshot_interval: u64,
+}
+
+impl Default for AggregateRepositoryConfig {
+    fn default() -> Self {
+        Self {
+            topic_config: IggyConsumerConfig::default(),
+            snapshot_interval: 100,
+        }
+    }
+}
+
+impl AggregateRepositoryConfig {
+    /// Creates a new `AggregateRepositoryConfig` from the given arguments.
+    ///
+    /// The repository reads the event topic with its own standalone consumer and never
+    /// stores offsets on the server, so only the stream, topic, consumer name and batch size
+    /// of the topic config are used.
+    ///
+    /// # Args
+    ///
+    /// * `topic_config` - The consumer configuration of the event topic.
+    /// * `snapshot_interval` - The number of events after which a loaded aggregate is
+    ///   snapshotted again. Snapshots are only stored if the repository has a `SnapshotStore`.
+    ///
+    /// Returns:
+    /// A new `AggregateRepositoryConfig`.
+    ///
+    pub fn new(topic_config: IggyConsumerConfig, snapshot_interval: u64) -> Self {
+        Self {
+            topic_config,
+            snapshot_interval,
+        }
+    }
+
+    /// Creates a new `AggregateRepositoryConfig` for the given stream and topic with the
+    /// default snapshot interval.
+    ///
+    /// # Args
+    ///
+    /// * `stream` - The stream name of the event topic.
+    /// * `topic` - The event topic name.
+    /// * `batch_size` - The max number of events to poll in a batch while loading.
+    ///
+    /// Returns:
+    /// A new `AggregateRepositoryConfig`.
+    ///
+    pub fn from_stream_topic(stream: &str, topic: &str, batch_size: u32) -> Self {
+        Self {
+            topic_config: IggyConsumerConfig::from_stream_topic(
+                stream,
+                topic,
+                batch_size,
+                IggyDuration::from_str("5ms").unwrap(),
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+// Getters.
+impl AggregateRepositoryConfig {
+    pub fn topic_config(&self) -> &IggyConsumerConfig {
+        &self.topic_config
+    }
+
+    pub fn snapshot_interval(&self) -> u64 {
+        self.snapshot_interval
+    }
+}
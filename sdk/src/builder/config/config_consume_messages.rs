@@ -0,0 +1,117 @@
+use crate::builder::{
+    ConsumeFailure, DEFAULT_MAX_CHUNKS, DEFAULT_MAX_DECOMPRESSED_SIZE, DEFAULT_MAX_PENDING_BYTES,
+};
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Builder, Debug, Clone)]
+pub struct ConsumeMessagesConfig {
+    handler_timeout: Option<IggyDuration>,
+    handler_retries: u32,
+    retry_interval: IggyDuration,
+    failure_sender: Option<UnboundedSender<ConsumeFailure>>,
+    #[builder(default = IggyDuration::from_str("60s").unwrap())]
+    chunk_timeout: IggyDuration,
+    #[builder(default = DEFAULT_MAX_CHUNKS)]
+    max_chunks: u32,
+    #[builder(default = DEFAULT_MAX_PENDING_BYTES)]
+    max_pending_bytes: usize,
+    #[builder(default = DEFAULT_MAX_DECOMPRESSED_SIZE)]
+    max_decompressed_size: usize,
+}
+
+impl Default for ConsumeMessagesConfig {
+    fn default() -> Self {
+        Self {
+            handler_timeout: None,
+            handler_retries: 0,
+            retry_interval: IggyDuration::from_str("100ms").unwrap(),
+            failure_sender: None,
+            chunk_timeout: IggyDuration::from_str("60s").unwrap(),
+            max_chunks: DEFAULT_MAX_CHUNKS,
+            max_pending_bytes: DEFAULT_MAX_PENDING_BYTES,
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+        }
+    }
+}
+
+impl ConsumeMessagesConfig {
+    /// Creates a new `ConsumeMessagesConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `handler_timeout` - The max duration a single `EventConsumer::consume` call may take.
+    /// * `handler_retries` - The number of retries after a failed or timed out `consume` call.
+    /// * `retry_interval` - The interval between retries.
+    /// * `failure_sender` - The optional channel that receives messages that failed all attempts.
+    ///
+    /// Returns:
+    /// A new `ConsumeMessagesConfig`.
+    ///
+    pub fn new(
+        handler_timeout: Option<IggyDuration>,
+        handler_retries: u32,
+        retry_interval: IggyDuration,
+        failure_sender: Option<UnboundedSender<ConsumeFailure>>,
+    ) -> Self {
+        Self {
+            handler_timeout,
+            handler_retries,
+            retry_interval,
+            failure_sender,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `ConsumeMessagesConfig` that only sets a handler timeout.
+    ///
+    /// # Args
+    ///
+    /// * `handler_timeout` - The max duration a single `EventConsumer::consume` call may take.
+    ///
+    /// Returns:
+    /// A new `ConsumeMessagesConfig`.
+    ///
+    pub fn with_handler_timeout(handler_timeout: IggyDuration) -> Self {
+        Self {
+            handler_timeout: Some(handler_timeout),
+            ..Default::default()
+        }
+    }
+}
+
+impl ConsumeMessagesConfig {
+    pub fn handler_timeout(&self) -> Option<IggyDuration> {
+        self.handler_timeout
+    }
+
+    pub fn handler_retries(&self) -> u32 {
+        self.handler_retries
+    }
+
+    pub fn retry_interval(&self) -> IggyDuration {
+        self.retry_interval
+    }
+
+    pub fn failure_sender(&self) -> Option<&UnboundedSender<ConsumeFailure>> {
+        self.failure_sender.as_ref()
+    }
+
+    pub fn chunk_timeout(&self) -> IggyDuration {
+        self.chunk_timeout
+    }
+
+    pub fn max_chunks(&self) -> u32 {
+        self.max_chunks
+    }
+
+    pub fn max_pending_bytes(&self) -> usize {
+        self.max_pending_bytes
+    }
+
+    pub fn max_decompressed_size(&self) -> usize {
+        self.max_decompressed_size
+    }
+}
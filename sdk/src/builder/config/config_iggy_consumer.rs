@@ -1,7 +1,9 @@
 use crate::builder::config::shared_config;
+use crate::builder::OffsetResetPolicy;
 use bon::Builder;
 use iggy::clients::consumer::{AutoCommit, AutoCommitWhen};
-use iggy::consumer::ConsumerKind;
+use iggy::consumer::{Consumer, ConsumerKind};
+use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::utils::duration::IggyDuration;
@@ -22,6 +24,7 @@ pub struct IggyConsumerConfig {
     polling_strategy: PollingStrategy,
     partitions_count: u32,
     replication_factor: Option<u8>,
+    offset_reset: Option<OffsetResetPolicy>,
 }
 
 impl Default for IggyConsumerConfig {
@@ -42,6 +45,7 @@ impl Default for IggyConsumerConfig {
             polling_strategy: PollingStrategy::last(),
             partitions_count: 1,
             replication_factor: None,
+            offset_reset: None,
         }
     }
 }
@@ -62,10 +66,12 @@ impl IggyConsumerConfig {
     /// * `polling_interval` - The interval between polling for new messages.
     /// * `polling_strategy` - The polling strategy to use.
     /// * `partition` - The number of partitions to create.
+    /// * `replication_factor` - The replication factor to use.
     ///
     /// Returns:
     /// A new `IggyConsumerConfig`.
     ///
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         stream_id: Identifier,
         stream_name: String,
@@ -93,6 +99,7 @@ impl IggyConsumerConfig {
             polling_strategy,
             partitions_count,
             replication_factor,
+            offset_reset: None,
         }
     }
 
@@ -130,6 +137,7 @@ impl IggyConsumerConfig {
             polling_strategy: PollingStrategy::next(),
             partitions_count: 1,
             replication_factor: None,
+            offset_reset: None,
         }
     }
 }
@@ -182,4 +190,53 @@ impl IggyConsumerConfig {
     pub fn replication_factor(&self) -> Option<u8> {
         self.replication_factor
     }
+
+    pub fn offset_reset(&self) -> Option<OffsetResetPolicy> {
+        self.offset_reset
+    }
+
+    /// Returns this config with the given reset policy for partitions without a stored offset.
+    ///
+    /// With an offset reset policy, the built consumer always resumes from its stored offset
+    /// and ignores the configured polling strategy.
+    pub fn with_offset_reset(self, offset_reset: OffsetResetPolicy) -> Self {
+        Self {
+            offset_reset: Some(offset_reset),
+            ..self
+        }
+    }
+
+    /// Returns this config with the given polling strategy and without an offset reset policy,
+    /// so the built consumer starts at the given position, e.g. `PollingStrategy::offset(0)`
+    /// after a seek to the first message of a single partition.
+    pub fn with_polling_strategy(self, polling_strategy: PollingStrategy) -> Self {
+        Self {
+            polling_strategy,
+            offset_reset: None,
+            ..self
+        }
+    }
+
+    /// Returns a copy of this config with the given auto-commit setting.
+    pub(crate) fn with_auto_commit(&self, auto_commit: AutoCommit) -> Self {
+        Self {
+            auto_commit,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the iggy `Consumer` identified by the consumer name and kind,
+    /// as used by the server to store offsets.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the consumer name is not a valid identifier.
+    ///
+    pub fn consumer(&self) -> Result<Consumer, IggyError> {
+        let id = self.consumer_name.as_str().try_into()?;
+        Ok(match self.consumer_kind {
+            ConsumerKind::Consumer => Consumer::new(id),
+            ConsumerKind::ConsumerGroup => Consumer::group(id),
+        })
+    }
 }
@@ -1,11 +1,14 @@
 use crate::builder::config::shared_config;
+use crate::builder::PartitionKeyExtractor;
 use bon::Builder;
 use iggy::identifier::Identifier;
 use iggy::messages::send_messages::Partitioning;
 use iggy::utils::duration::IggyDuration;
+use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
+use std::sync::Arc;
 
-#[derive(Builder, Debug, Clone)]
+#[derive(Builder, Clone)]
 #[builder(on(String, into))]
 pub struct IggyProducerConfig {
     stream_id: Identifier,
@@ -17,6 +20,24 @@ pub struct IggyProducerConfig {
     partitioning: Partitioning,
     partitions_count: u32,
     replication_factor: Option<u8>,
+    partition_key: Option<Arc<dyn PartitionKeyExtractor>>,
+}
+
+impl Debug for IggyProducerConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IggyProducerConfig")
+            .field("stream_id", &self.stream_id)
+            .field("stream_name", &self.stream_name)
+            .field("topic_id", &self.topic_id)
+            .field("topic_name", &self.topic_name)
+            .field("batch_size", &self.batch_size)
+            .field("send_interval", &self.send_interval)
+            .field("partitioning", &self.partitioning)
+            .field("partitions_count", &self.partitions_count)
+            .field("replication_factor", &self.replication_factor)
+            .field("partition_key", &self.partition_key.is_some())
+            .finish()
+    }
 }
 
 impl Default for IggyProducerConfig {
@@ -34,6 +55,7 @@ impl Default for IggyProducerConfig {
             partitioning: Partitioning::balanced(),
             partitions_count: 1,
             replication_factor: None,
+            partition_key: None,
         }
     }
 }
@@ -56,6 +78,7 @@ impl IggyProducerConfig {
     /// Returns:
     /// A new `IggyProducerConfig`.
     ///
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         stream_id: Identifier,
         stream_name: String,
@@ -77,6 +100,7 @@ impl IggyProducerConfig {
             partitioning,
             partitions_count,
             replication_factor,
+            partition_key: None,
         }
     }
 
@@ -112,6 +136,7 @@ impl IggyProducerConfig {
             partitioning: Partitioning::balanced(),
             partitions_count: 1,
             replication_factor: None,
+            partition_key: None,
         }
     }
 }
@@ -152,4 +177,19 @@ impl IggyProducerConfig {
     pub fn replication_factor(&self) -> Option<u8> {
         self.replication_factor
     }
+
+    pub fn partition_key(&self) -> Option<&Arc<dyn PartitionKeyExtractor>> {
+        self.partition_key.as_ref()
+    }
+
+    /// Returns this config with the given partition key extractor.
+    ///
+    /// The extractor takes precedence over `partitioning`, so messages with the same key
+    /// go to the same partition.
+    pub fn with_partition_key(self, partition_key: Arc<dyn PartitionKeyExtractor>) -> Self {
+        Self {
+            partition_key: Some(partition_key),
+            ..self
+        }
+    }
 }
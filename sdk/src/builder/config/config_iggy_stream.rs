@@ -58,18 +58,18 @@ impl IggyStreamConfig {
     }
 
     pub fn stream_id(&self) -> &Identifier {
-        &self.producer_config.stream_id()
+        self.producer_config.stream_id()
     }
 
     pub fn stream_name(&self) -> &str {
-        &self.producer_config.stream_name()
+        self.producer_config.stream_name()
     }
 
     pub fn topic_id(&self) -> &Identifier {
-        &self.producer_config.topic_id()
+        self.producer_config.topic_id()
     }
 
     pub fn topic_name(&self) -> &str {
-        &self.producer_config.topic_name()
+        self.producer_config.topic_name()
     }
 }
@@ -0,0 +1,83 @@
+use crate::builder::IggyConsumerConfig;
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+
+#[derive(Builder, Debug, Clone)]
+pub struct MaterializedTableConfig {
+    source_config: IggyConsumerConfig,
+    changes_capacity: usize,
+}
+
+impl Default for MaterializedTableConfig {
+    fn default() -> Self {
+        Self {
+            source_config: IggyConsumerConfig::default(),
+            changes_capacity: 1024,
+        }
+    }
+}
+
+impl MaterializedTableConfig {
+    /// Creates a new `MaterializedTableConfig` from the given arguments.
+    ///
+    /// The table polls every partition of the source topic with its own standalone consumer
+    /// and never stores offsets on the server, so the consumer kind, polling strategy and
+    /// auto-commit setting of the source config are ignored.
+    ///
+    /// # Args
+    ///
+    /// * `source_config` - The consumer configuration of the source topic.
+    /// * `changes_capacity` - The number of changes buffered for each subscriber.
+    ///   Slower subscribers miss the oldest changes.
+    ///
+    /// Returns:
+    /// A new `MaterializedTableConfig`.
+    ///
+    pub fn new(source_config: IggyConsumerConfig, changes_capacity: usize) -> Self {
+        Self {
+            source_config,
+            changes_capacity,
+        }
+    }
+
+    /// Creates a new `MaterializedTableConfig` for the given stream and topic with the
+    /// default change capacity.
+    ///
+    /// # Args
+    ///
+    /// * `stream` - The stream name of the source topic.
+    /// * `topic` - The source topic name.
+    /// * `batch_size` - The max number of messages to poll in a batch.
+    /// * `polling_interval` - The interval between polling for new messages once caught up.
+    ///
+    /// Returns:
+    /// A new `MaterializedTableConfig`.
+    ///
+    pub fn from_stream_topic(
+        stream: &str,
+        topic: &str,
+        batch_size: u32,
+        polling_interval: IggyDuration,
+    ) -> Self {
+        Self {
+            source_config: IggyConsumerConfig::from_stream_topic(
+                stream,
+                topic,
+                batch_size,
+                polling_interval,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+// Getters.
+impl MaterializedTableConfig {
+    pub fn source_config(&self) -> &IggyConsumerConfig {
+        &self.source_config
+    }
+
+    pub fn changes_capacity(&self) -> usize {
+        self.changes_capacity
+    }
+}
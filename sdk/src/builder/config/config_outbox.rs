@@ -0,0 +1,69 @@
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+
+#[derive(Builder, Debug, Clone)]
+#[builder(on(String, into))]
+pub struct OutboxConfig {
+    table_name: String,
+    batch_size: u32,
+    poll_interval: IggyDuration,
+    delete_sent: bool,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            table_name: "iggy_outbox".to_string(),
+            batch_size: 100,
+            poll_interval: IggyDuration::from_str("100ms").unwrap(),
+            delete_sent: false,
+        }
+    }
+}
+
+impl OutboxConfig {
+    /// Creates a new `OutboxConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `table_name` - The name of the outbox table. Only ASCII letters, digits and `_` are allowed.
+    /// * `batch_size` - The max number of rows the relay reads per poll.
+    /// * `poll_interval` - The interval between two polls of the relay.
+    /// * `delete_sent` - Delete rows once they are sent instead of marking them as sent.
+    ///
+    /// Returns:
+    /// A new `OutboxConfig`.
+    ///
+    pub fn new(
+        table_name: String,
+        batch_size: u32,
+        poll_interval: IggyDuration,
+        delete_sent: bool,
+    ) -> Self {
+        Self {
+            table_name,
+            batch_size,
+            poll_interval,
+            delete_sent,
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    pub fn poll_interval(&self) -> IggyDuration {
+        self.poll_interval
+    }
+
+    pub fn delete_sent(&self) -> bool {
+        self.delete_sent
+    }
+}
@@ -0,0 +1,89 @@
+use crate::builder::SpoolOverflowPolicy;
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Builder, Debug, Clone)]
+pub struct ProducerSpoolConfig {
+    #[builder(into)]
+    path: PathBuf,
+    max_size_bytes: u64,
+    overflow_policy: SpoolOverflowPolicy,
+    replay_interval: IggyDuration,
+}
+
+impl Default for ProducerSpoolConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("iggy_producer.spool"),
+            max_size_bytes: 64 * 1024 * 1024,
+            overflow_policy: SpoolOverflowPolicy::default(),
+            replay_interval: IggyDuration::from_str("1s").unwrap(),
+        }
+    }
+}
+
+impl ProducerSpoolConfig {
+    /// Creates a new `ProducerSpoolConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `path` - The path of the spool file. A second file with the `.cursor` extension
+    ///   stores the replay position, and a file with the `.dead` extension the rejected messages.
+    /// * `max_size_bytes` - The max size of the unsent messages in the spool.
+    /// * `overflow_policy` - What happens to new messages when the spool is full.
+    /// * `replay_interval` - The interval at which the replay task retries sending spooled messages.
+    ///
+    /// Returns:
+    /// A new `ProducerSpoolConfig`.
+    ///
+    pub fn new(
+        path: impl Into<PathBuf>,
+        max_size_bytes: u64,
+        overflow_policy: SpoolOverflowPolicy,
+        replay_interval: IggyDuration,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            max_size_bytes,
+            overflow_policy,
+            replay_interval,
+        }
+    }
+
+    /// Creates a new `ProducerSpoolConfig` for the given path with the default size limit,
+    /// overflow policy, and replay interval.
+    ///
+    /// # Args
+    ///
+    /// * `path` - The path of the spool file.
+    ///
+    /// Returns:
+    /// A new `ProducerSpoolConfig`.
+    ///
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl ProducerSpoolConfig {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_bytes
+    }
+
+    pub fn overflow_policy(&self) -> SpoolOverflowPolicy {
+        self.overflow_policy
+    }
+
+    pub fn replay_interval(&self) -> IggyDuration {
+        self.replay_interval
+    }
+}
@@ -0,0 +1,66 @@
+use bon::Builder;
+use std::path::{Path, PathBuf};
+
+#[derive(Builder, Debug, Clone)]
+pub struct StateStoreConfig {
+    #[builder(into)]
+    path: PathBuf,
+    #[builder(into)]
+    store_name: String,
+}
+
+impl Default for StateStoreConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("iggy_state.db"),
+            store_name: "iggy_state".to_string(),
+        }
+    }
+}
+
+impl StateStoreConfig {
+    /// Creates a new `StateStoreConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `path` - The path of the SQLite database file.
+    /// * `store_name` - The name of the store. Used as the prefix of its tables, so several
+    ///   stores can share one database. Only ASCII letters, digits and `_` are allowed.
+    ///
+    /// Returns:
+    /// A new `StateStoreConfig`.
+    ///
+    pub fn new(path: impl Into<PathBuf>, store_name: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            store_name: store_name.into(),
+        }
+    }
+
+    /// Creates a new `StateStoreConfig` for the given path with the default store name.
+    ///
+    /// # Args
+    ///
+    /// * `path` - The path of the SQLite database file.
+    ///
+    /// Returns:
+    /// A new `StateStoreConfig`.
+    ///
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+}
+
+// Getters.
+impl StateStoreConfig {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn store_name(&self) -> &str {
+        &self.store_name
+    }
+}
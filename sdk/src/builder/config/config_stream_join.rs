@@ -0,0 +1,74 @@
+use crate::builder::JoinKind;
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+
+#[derive(Builder, Debug, Clone)]
+pub struct StreamJoinConfig {
+    window: IggyDuration,
+    kind: JoinKind,
+    idle_timeout: Option<IggyDuration>,
+}
+
+impl Default for StreamJoinConfig {
+    /// Creates a default `StreamJoinConfig` for an inner join within one minute.
+    fn default() -> Self {
+        Self {
+            window: IggyDuration::from_str("1m").unwrap(),
+            kind: JoinKind::Inner,
+            idle_timeout: None,
+        }
+    }
+}
+
+impl StreamJoinConfig {
+    /// Creates a new `StreamJoinConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `window` - The max distance in event time between a left and a right message.
+    /// * `kind` - The join kind.
+    ///
+    /// Returns:
+    /// A new `StreamJoinConfig`.
+    ///
+    pub fn new(window: IggyDuration, kind: JoinKind) -> Self {
+        Self {
+            window,
+            kind,
+            idle_timeout: None,
+        }
+    }
+
+    /// Creates a new `StreamJoinConfig` for an inner join.
+    pub fn inner(window: IggyDuration) -> Self {
+        Self::new(window, JoinKind::Inner)
+    }
+
+    /// Creates a new `StreamJoinConfig` for a left-outer join.
+    pub fn left_outer(window: IggyDuration) -> Self {
+        Self::new(window, JoinKind::LeftOuter)
+    }
+
+    /// Lets a side that received no message for the given processing time stop holding
+    /// back the join watermark, so the buffers of the other side still expire.
+    pub fn with_idle_timeout(mut self, idle_timeout: IggyDuration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+}
+
+// Getters.
+impl StreamJoinConfig {
+    pub fn window(&self) -> IggyDuration {
+        self.window
+    }
+
+    pub fn kind(&self) -> JoinKind {
+        self.kind
+    }
+
+    pub fn idle_timeout(&self) -> Option<IggyDuration> {
+        self.idle_timeout
+    }
+}
@@ -0,0 +1,104 @@
+use crate::builder::{IggyConsumerConfig, IggyProducerConfig};
+use bon::Builder;
+use iggy::clients::consumer::AutoCommit;
+use iggy::utils::duration::IggyDuration;
+
+#[derive(Builder, Debug, Clone)]
+pub struct IggyStreamProcessorConfig {
+    input_config: IggyConsumerConfig,
+    output_config: IggyProducerConfig,
+}
+
+impl Default for IggyStreamProcessorConfig {
+    /// Creates a default `IggyStreamProcessorConfig`.
+    fn default() -> Self {
+        Self {
+            input_config: IggyConsumerConfig::default(),
+            output_config: IggyProducerConfig::default(),
+        }
+    }
+}
+
+impl IggyStreamProcessorConfig {
+    /// Creates a new `IggyStreamProcessorConfig` from the given arguments.
+    ///
+    /// The processor commits the input offsets itself, so the auto-commit setting of the
+    /// input config is ignored.
+    ///
+    /// # Args
+    ///
+    /// * `input_config` - The consumer configuration of the input topic.
+    /// * `output_config` - The producer configuration of the output topic.
+    ///
+    /// Returns:
+    /// A new `IggyStreamProcessorConfig`.
+    ///
+    pub fn new(input_config: IggyConsumerConfig, output_config: IggyProducerConfig) -> Self {
+        Self {
+            input_config,
+            output_config,
+        }
+    }
+
+    /// Creates a new `IggyStreamProcessorConfig` that reads the input topic and writes
+    /// the output topic.
+    ///
+    /// # Args
+    ///
+    /// * `input_stream` - The stream name of the input topic.
+    /// * `input_topic` - The input topic name.
+    /// * `output_stream` - The stream name of the output topic.
+    /// * `output_topic` - The output topic name.
+    /// * `batch_size` - The max number of messages to poll and send in a batch.
+    /// * `send_interval` - The interval between sending batches of output messages.
+    /// * `polling_interval` - The interval between polling for new input messages.
+    ///
+    /// Returns:
+    /// A new `IggyStreamProcessorConfig`.
+    ///
+    pub fn from_stream_topics(
+        input_stream: &str,
+        input_topic: &str,
+        output_stream: &str,
+        output_topic: &str,
+        batch_size: u32,
+        send_interval: IggyDuration,
+        polling_interval: IggyDuration,
+    ) -> Self {
+        let input_config = IggyConsumerConfig::from_stream_topic(
+            input_stream,
+            input_topic,
+            batch_size,
+            polling_interval,
+        );
+
+        let output_config = IggyProducerConfig::from_stream_topic(
+            output_stream,
+            output_topic,
+            batch_size,
+            send_interval,
+        );
+
+        Self {
+            input_config,
+            output_config,
+        }
+    }
+}
+
+// Getters.
+impl IggyStreamProcessorConfig {
+    pub fn input_config(&self) -> &IggyConsumerConfig {
+        &self.input_config
+    }
+
+    pub fn output_config(&self) -> &IggyProducerConfig {
+        &self.output_config
+    }
+
+    /// Returns the input config with auto-commit disabled, as used to build the
+    /// consumer of the processor.
+    pub(crate) fn input_config_without_auto_commit(&self) -> IggyConsumerConfig {
+        self.input_config.with_auto_commit(AutoCommit::Disabled)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::builder::WindowKind;
+use bon::Builder;
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+
+#[derive(Builder, Debug, Clone)]
+pub struct WindowedAggregationConfig {
+    window: WindowKind,
+    allowed_lateness: IggyDuration,
+}
+
+impl Default for WindowedAggregationConfig {
+    /// Creates a default `WindowedAggregationConfig` with one minute tumbling windows
+    /// and no allowed lateness.
+    fn default() -> Self {
+        Self {
+            window: WindowKind::Tumbling {
+                size: IggyDuration::from_str("1m").unwrap(),
+            },
+            allowed_lateness: IggyDuration::from_str("0").unwrap(),
+        }
+    }
+}
+
+impl WindowedAggregationConfig {
+    /// Creates a new `WindowedAggregationConfig` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `window` - The kind and size of the windows.
+    /// * `allowed_lateness` - How long a window stays open after the watermark passed its end.
+    ///
+    /// Returns:
+    /// A new `WindowedAggregationConfig`.
+    ///
+    pub fn new(window: WindowKind, allowed_lateness: IggyDuration) -> Self {
+        Self {
+            window,
+            allowed_lateness,
+        }
+    }
+
+    /// Creates a new `WindowedAggregationConfig` with tumbling windows of the given size.
+    pub fn tumbling(size: IggyDuration, allowed_lateness: IggyDuration) -> Self {
+        Self::new(WindowKind::Tumbling { size }, allowed_lateness)
+    }
+
+    /// Creates a new `WindowedAggregationConfig` with hopping windows of the given size
+    /// that start every `advance`.
+    pub fn hopping(
+        size: IggyDuration,
+        advance: IggyDuration,
+        allowed_lateness: IggyDuration,
+    ) -> Self {
+        Self::new(WindowKind::Hopping { size, advance }, allowed_lateness)
+    }
+
+    /// Creates a new `WindowedAggregationConfig` with session windows that close after
+    /// the given gap without messages.
+    pub fn session(gap: IggyDuration, allowed_lateness: IggyDuration) -> Self {
+        Self::new(WindowKind::Session { gap }, allowed_lateness)
+    }
+
+    /// Checks that all window durations are positive and that hopping windows
+    /// do not leave gaps.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidConfiguration` - If the window is invalid.
+    ///
+    pub fn validate(&self) -> Result<(), IggyError> {
+        let valid = match self.window {
+            WindowKind::Tumbling { size } => size.as_micros() > 0,
+            WindowKind::Hopping { size, advance } => {
+                advance.as_micros() > 0 && advance.as_micros() <= size.as_micros()
+            }
+            WindowKind::Session { gap } => gap.as_micros() > 0,
+        };
+
+        match valid {
+            true => Ok(()),
+            false => Err(IggyError::InvalidConfiguration),
+        }
+    }
+}
+
+// Getters.
+impl WindowedAggregationConfig {
+    pub fn window(&self) -> WindowKind {
+        self.window
+    }
+
+    pub fn allowed_lateness(&self) -> IggyDuration {
+        self.allowed_lateness
+    }
+}
@@ -0,0 +1,10 @@
+/// Which messages a stream-stream join emits.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum JoinKind {
+    /// Emit a pair for every left and right message with the same key within the window.
+    #[default]
+    Inner,
+    /// Like `Inner`, and also emit every left message that found no match
+    /// once its window has passed.
+    LeftOuter,
+}
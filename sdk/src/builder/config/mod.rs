@@ -1,4 +1,18 @@
+pub mod config_aggregate_repository;
+pub mod config_consume_messages;
 pub mod config_iggy_consumer;
 pub mod config_iggy_producer;
 pub mod config_iggy_stream;
+pub mod config_materialized_table;
+pub mod config_outbox;
+pub mod config_producer_spool;
+pub mod config_state_store;
+pub mod config_stream_join;
+pub mod config_stream_processor;
+pub mod config_windowed_aggregation;
+pub mod join_kind;
+pub mod offset_reset_policy;
+pub mod producer_retry_policy;
 mod shared_config;
+pub mod spool_overflow_policy;
+pub mod window_kind;
@@ -0,0 +1,17 @@
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// Where a consumer starts reading a partition for which it has no stored offset.
+///
+/// Partitions with a stored offset always resume after that offset.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum OffsetResetPolicy {
+    /// Start at the first message of the partition.
+    #[default]
+    Earliest,
+    /// Skip all existing messages and only receive messages sent after the consumer started.
+    Latest,
+    /// Start at the first message with a timestamp at or after the given timestamp.
+    Timestamp(IggyTimestamp),
+    /// Fail to build the consumer.
+    Fail,
+}
@@ -0,0 +1,68 @@
+use bon::Builder;
+use iggy::utils::duration::IggyDuration;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How often and how long the producer retries a send that failed with a transient error.
+///
+/// The backoff starts at `initial_backoff` and doubles with every retry up to `max_backoff`.
+#[derive(Builder, Debug, Clone, Copy)]
+pub struct ProducerRetryPolicy {
+    max_retries: u32,
+    initial_backoff: IggyDuration,
+    max_backoff: IggyDuration,
+}
+
+impl Default for ProducerRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: IggyDuration::from_str("100ms").unwrap(),
+            max_backoff: IggyDuration::from_str("5s").unwrap(),
+        }
+    }
+}
+
+impl ProducerRetryPolicy {
+    /// Creates a new `ProducerRetryPolicy` from the given arguments.
+    ///
+    /// # Args
+    ///
+    /// * `max_retries` - The number of retries after the first failed attempt.
+    /// * `initial_backoff` - The wait time before the first retry.
+    /// * `max_backoff` - The upper bound of the wait time between retries.
+    ///
+    /// Returns:
+    /// A new `ProducerRetryPolicy`.
+    ///
+    pub fn new(max_retries: u32, initial_backoff: IggyDuration, max_backoff: IggyDuration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// Returns the wait time before the given retry, starting at 1 for the first retry.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .get_duration()
+            .saturating_mul(factor)
+            .min(self.max_backoff.get_duration())
+    }
+}
+
+impl ProducerRetryPolicy {
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn initial_backoff(&self) -> IggyDuration {
+        self.initial_backoff
+    }
+
+    pub fn max_backoff(&self) -> IggyDuration {
+        self.max_backoff
+    }
+}
@@ -0,0 +1,11 @@
+/// What the producer spool does with a new message when it has reached its max size.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum SpoolOverflowPolicy {
+    /// Reject the new message and return an error to the caller.
+    #[default]
+    Reject,
+    /// Drop the new message and keep the spooled messages.
+    DropNewest,
+    /// Drop the oldest spooled messages until the new message fits.
+    DropOldest,
+}
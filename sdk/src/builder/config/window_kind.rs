@@ -0,0 +1,18 @@
+use iggy::utils::duration::IggyDuration;
+
+/// How messages are grouped into windows by their event time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum WindowKind {
+    /// Fixed-size, non-overlapping windows aligned to the epoch.
+    /// Every message belongs to exactly one window.
+    Tumbling { size: IggyDuration },
+    /// Fixed-size windows that start every `advance` and overlap if `advance` is smaller
+    /// than `size`. A message belongs to every window that contains its event time.
+    Hopping {
+        size: IggyDuration,
+        advance: IggyDuration,
+    },
+    /// Windows per key that stay open while messages arrive within `gap` of each other.
+    /// A message that bridges two sessions merges them.
+    Session { gap: IggyDuration },
+}
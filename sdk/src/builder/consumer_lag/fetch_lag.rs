@@ -0,0 +1,50 @@
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{ConsumerLag, IggyConsumerConfig, PartitionLag};
+use iggy::client::ConsumerOffsetClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+
+/// Fetches the lag of the configured consumer for every partition of its topic.
+///
+/// # Arguments
+///
+/// * `client` - The `IggyClient` to use.
+/// * `config` - The `IggyConsumerConfig` of the consumer.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic does not exist or the metadata cannot be fetched.
+///
+pub(crate) async fn fetch_consumer_lag(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+) -> Result<ConsumerLag, IggyError> {
+    let stream_id = config.stream_id();
+    let topic_id = config.topic_id();
+
+    let topic = get_topic_details(client, config).await?;
+
+    let consumer = config.consumer()?;
+    let mut partitions = Vec::with_capacity(topic.partitions.len());
+    for partition in &topic.partitions {
+        let latest_offset = if partition.messages_count == 0 {
+            None
+        } else {
+            Some(partition.current_offset)
+        };
+
+        let committed_offset = client
+            .get_consumer_offset(&consumer, stream_id, topic_id, Some(partition.id))
+            .await?
+            .map(|info| info.stored_offset);
+
+        partitions.push(PartitionLag::new(
+            partition.id,
+            latest_offset,
+            committed_offset,
+        ));
+    }
+    partitions.sort_by_key(|lag| lag.partition_id);
+
+    Ok(ConsumerLag::new(partitions))
+}
@@ -0,0 +1,70 @@
+/// Lag of a consumer on a single partition.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct PartitionLag {
+    /// The partition ID.
+    pub partition_id: u32,
+    /// The offset of the latest message in the partition, `None` if the partition is empty.
+    pub latest_offset: Option<u64>,
+    /// The offset stored for the consumer, `None` if nothing was committed yet.
+    pub committed_offset: Option<u64>,
+    /// The number of messages the consumer has not yet committed.
+    pub lag: u64,
+}
+
+impl PartitionLag {
+    /// Creates a new `PartitionLag` and computes the lag from the given offsets.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition ID.
+    /// * `latest_offset` - The offset of the latest message in the partition.
+    /// * `committed_offset` - The offset stored for the consumer.
+    ///
+    pub fn new(
+        partition_id: u32,
+        latest_offset: Option<u64>,
+        committed_offset: Option<u64>,
+    ) -> Self {
+        let lag = match (latest_offset, committed_offset) {
+            (None, _) => 0,
+            (Some(latest), None) => latest + 1,
+            (Some(latest), Some(committed)) => latest.saturating_sub(committed),
+        };
+
+        Self {
+            partition_id,
+            latest_offset,
+            committed_offset,
+            lag,
+        }
+    }
+}
+
+/// Lag of a consumer across all partitions of its topic.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ConsumerLag {
+    partitions: Vec<PartitionLag>,
+}
+
+impl ConsumerLag {
+    pub fn new(partitions: Vec<PartitionLag>) -> Self {
+        Self { partitions }
+    }
+
+    /// Returns the lag of each partition, ordered by partition ID.
+    pub fn partitions(&self) -> &[PartitionLag] {
+        &self.partitions
+    }
+
+    /// Returns the lag of the given partition, if it exists.
+    pub fn partition(&self, partition_id: u32) -> Option<&PartitionLag> {
+        self.partitions
+            .iter()
+            .find(|lag| lag.partition_id == partition_id)
+    }
+
+    /// Returns the sum of the lag of all partitions.
+    pub fn total(&self) -> u64 {
+        self.partitions.iter().map(|lag| lag.lag).sum()
+    }
+}
@@ -0,0 +1,54 @@
+use crate::builder::consumer_lag::fetch_consumer_lag;
+use crate::builder::{ConsumerLag, IggyConsumerConfig};
+use iggy::clients::client::IggyClient;
+use iggy::utils::duration::IggyDuration;
+use std::sync::Arc;
+use tokio::sync::{oneshot, watch};
+use tracing::{error, info};
+
+/// Spawns a task that fetches the consumer lag at the given interval and publishes it
+/// on a watch channel until a shutdown signal is received or all receivers are dropped.
+///
+/// # Arguments
+///
+/// * `client` - The shared `IggyClient` to use.
+/// * `config` - The `IggyConsumerConfig` of the consumer.
+/// * `interval` - The interval between two lag updates.
+/// * `shutdown_rx` - Stops the reporter when a value is received.
+///
+pub(crate) fn spawn_lag_reporter(
+    client: Arc<IggyClient>,
+    config: IggyConsumerConfig,
+    interval: IggyDuration,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) -> watch::Receiver<ConsumerLag> {
+    let (sender, receiver) = watch::channel(ConsumerLag::default());
+
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(interval.get_duration());
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    info!("Received shutdown signal, stopping lag reporter");
+                    break;
+                }
+
+                _ = sender.closed() => {
+                    info!("All lag receivers dropped, stopping lag reporter");
+                    break;
+                }
+
+                _ = timer.tick() => {
+                    match fetch_consumer_lag(&client, &config).await {
+                        Ok(lag) => {
+                            sender.send_replace(lag);
+                        }
+                        Err(err) => error!("Failed to fetch consumer lag: {err}"),
+                    }
+                }
+            }
+        }
+    });
+
+    receiver
+}
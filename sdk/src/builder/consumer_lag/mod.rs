@@ -0,0 +1,7 @@
+mod fetch_lag;
+mod lag;
+mod lag_reporter;
+
+pub(crate) use fetch_lag::fetch_consumer_lag;
+pub use lag::{ConsumerLag, PartitionLag};
+pub(crate) use lag_reporter::spawn_lag_reporter;
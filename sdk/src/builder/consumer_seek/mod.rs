@@ -0,0 +1,9 @@
+mod replay_messages;
+mod replay_range;
+mod seek_consumer;
+mod seek_target;
+
+pub(crate) use replay_messages::replay_messages;
+pub use replay_range::{ReplayBound, ReplayRange};
+pub(crate) use seek_consumer::{lookup_consumer, seek_consumer};
+pub use seek_target::SeekTarget;
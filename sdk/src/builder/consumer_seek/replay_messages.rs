@@ -0,0 +1,99 @@
+use crate::builder::consumer_seek::seek_consumer::lookup_consumer;
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{decompress_polled_message, EventConsumer, IggyConsumerConfig, ReplayRange};
+use iggy::client::MessageClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollingStrategy;
+use tracing::{error, info};
+
+/// Replays the messages in the given range through the event processor and stops
+/// once the end of the range or the latest message is reached.
+///
+/// The replay polls with a separate standalone consumer and never stores offsets,
+/// so the position of the configured consumer is not changed.
+///
+/// # Arguments
+///
+/// * `client` - The `IggyClient` to use.
+/// * `config` - The `IggyConsumerConfig` of the consumer.
+/// * `range` - The range of messages to replay.
+/// * `partition_id` - The partition to replay, or `None` to replay all partitions of the topic.
+/// * `event_processor` - The `EventConsumer` that handles each replayed message.
+///
+/// Returns:
+/// The number of replayed messages.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic does not exist or the messages cannot be polled.
+///
+pub(crate) async fn replay_messages<P>(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+    range: ReplayRange,
+    partition_id: Option<u32>,
+    event_processor: &P,
+) -> Result<u64, IggyError>
+where
+    P: EventConsumer + Sync,
+{
+    let stream_id = config.stream_id();
+    let topic_id = config.topic_id();
+
+    let topic = get_topic_details(client, config).await?;
+
+    let consumer = lookup_consumer(config)?;
+    let batch_size = config.batch_size();
+    let mut replayed = 0;
+
+    for partition in &topic.partitions {
+        if partition_id.is_some_and(|id| id != partition.id) {
+            continue;
+        }
+
+        info!("Replay {range:?} in partition: {}", partition.id);
+        let mut strategy = range.start_strategy();
+        'partition: loop {
+            let polled = client
+                .poll_messages(
+                    stream_id,
+                    topic_id,
+                    Some(partition.id),
+                    &consumer,
+                    &strategy,
+                    batch_size,
+                    false,
+                )
+                .await?;
+
+            let Some(last_offset) = polled.messages.last().map(|message| message.offset) else {
+                break;
+            };
+
+            for message in polled.messages {
+                if range.is_past_end(&message) {
+                    break 'partition;
+                }
+
+                let message = match decompress_polled_message(message) {
+                    Ok(message) => message,
+                    Err(err) => {
+                        error!("Error while decompressing replayed message: {err}");
+                        continue;
+                    }
+                };
+
+                if let Err(err) = event_processor.consume(message).await {
+                    error!("Error while replaying message: {err}");
+                }
+                replayed += 1;
+            }
+
+            strategy = PollingStrategy::offset(last_offset + 1);
+        }
+    }
+
+    info!("Replayed {replayed} messages");
+    Ok(replayed)
+}
@@ -0,0 +1,72 @@
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// A position in a partition used as a bound of a `ReplayRange`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReplayBound {
+    /// The message at the given offset.
+    Offset(u64),
+    /// The first message with a timestamp at or after the given timestamp.
+    Timestamp(IggyTimestamp),
+}
+
+/// A range of messages to replay, from the inclusive `start` to the exclusive `end`.
+///
+/// Without an `end`, the replay stops once it reaches the latest message of the partition.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ReplayRange {
+    start: ReplayBound,
+    end: Option<ReplayBound>,
+}
+
+impl ReplayRange {
+    /// Creates a new `ReplayRange`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first position to replay, inclusive.
+    /// * `end` - The position to stop at, exclusive.
+    ///
+    pub fn new(start: ReplayBound, end: Option<ReplayBound>) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates a range that replays the offsets `start..end`.
+    pub fn offsets(start: u64, end: u64) -> Self {
+        Self::new(ReplayBound::Offset(start), Some(ReplayBound::Offset(end)))
+    }
+
+    /// Creates a range that replays the messages with timestamps in `start..end`.
+    pub fn timestamps(start: IggyTimestamp, end: IggyTimestamp) -> Self {
+        Self::new(
+            ReplayBound::Timestamp(start),
+            Some(ReplayBound::Timestamp(end)),
+        )
+    }
+
+    pub fn start(&self) -> ReplayBound {
+        self.start
+    }
+
+    pub fn end(&self) -> Option<ReplayBound> {
+        self.end
+    }
+
+    /// Returns the polling strategy that starts polling at the start of the range.
+    pub fn start_strategy(&self) -> PollingStrategy {
+        match self.start {
+            ReplayBound::Offset(offset) => PollingStrategy::offset(offset),
+            ReplayBound::Timestamp(timestamp) => PollingStrategy::timestamp(timestamp),
+        }
+    }
+
+    /// Returns true if the given message lies at or beyond the end of the range.
+    pub fn is_past_end(&self, message: &PolledMessage) -> bool {
+        match self.end {
+            None => false,
+            Some(ReplayBound::Offset(offset)) => message.offset >= offset,
+            Some(ReplayBound::Timestamp(timestamp)) => message.timestamp >= timestamp.as_micros(),
+        }
+    }
+}
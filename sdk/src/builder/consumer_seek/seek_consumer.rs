@@ -0,0 +1,131 @@
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{IggyConsumerConfig, SeekTarget};
+use iggy::client::{ConsumerOffsetClient, MessageClient};
+use iggy::clients::client::IggyClient;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollingStrategy;
+use tracing::info;
+
+/// Moves the stored offset of the configured consumer so that the next message it
+/// receives is the one at the seek target.
+///
+/// A seek to the first message deletes the stored offset, so the offset reset policy of
+/// the config applies again on the next build.
+///
+/// # Arguments
+///
+/// * `client` - The `IggyClient` to use.
+/// * `config` - The `IggyConsumerConfig` of the consumer to move.
+/// * `target` - The position to move to.
+/// * `partition_id` - The partition to seek, or `None` to seek all partitions of the topic.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic does not exist or the offsets cannot be stored.
+///
+pub(crate) async fn seek_consumer(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+    target: SeekTarget,
+    partition_id: Option<u32>,
+) -> Result<(), IggyError> {
+    let stream_id = config.stream_id();
+    let topic_id = config.topic_id();
+
+    let topic = get_topic_details(client, config).await?;
+
+    let consumer = config.consumer()?;
+    for partition in &topic.partitions {
+        if partition_id.is_some_and(|id| id != partition.id) {
+            continue;
+        }
+
+        let latest_offset = if partition.messages_count == 0 {
+            None
+        } else {
+            Some(partition.current_offset)
+        };
+
+        let next_offset = match target {
+            SeekTarget::Offset(offset) => Some(offset),
+            SeekTarget::First => Some(0),
+            SeekTarget::Last => latest_offset,
+            SeekTarget::Timestamp(timestamp) => {
+                find_offset(
+                    client,
+                    config,
+                    partition.id,
+                    PollingStrategy::timestamp(timestamp),
+                )
+                .await?
+            }
+        };
+
+        // Past the latest message, the consumer continues with new messages.
+        let next_offset = match (next_offset, latest_offset) {
+            (Some(next), Some(latest)) if next <= latest => Some(next),
+            (_, Some(latest)) => Some(latest + 1),
+            (_, None) => None,
+        };
+
+        info!(
+            "Seek consumer: {} to {target:?} in partition: {}",
+            config.consumer_name(),
+            partition.id
+        );
+        match next_offset {
+            // The stored offset is the last consumed one, so we store the offset before the target.
+            Some(offset) if offset > 0 => {
+                client
+                    .store_consumer_offset(
+                        &consumer,
+                        stream_id,
+                        topic_id,
+                        Some(partition.id),
+                        offset - 1,
+                    )
+                    .await?
+            }
+            // Without a stored offset, the consumer starts at the first message.
+            _ => {
+                client
+                    .delete_consumer_offset(&consumer, stream_id, topic_id, Some(partition.id))
+                    .await?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the offset of the first message returned by the given polling strategy
+/// without storing any offset.
+async fn find_offset(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+    partition_id: u32,
+    strategy: PollingStrategy,
+) -> Result<Option<u64>, IggyError> {
+    let consumer = lookup_consumer(config)?;
+    let polled = client
+        .poll_messages(
+            config.stream_id(),
+            config.topic_id(),
+            Some(partition_id),
+            &consumer,
+            &strategy,
+            1,
+            false,
+        )
+        .await?;
+
+    Ok(polled.messages.first().map(|message| message.offset))
+}
+
+/// A standalone consumer used for lookups and replays, so that the offsets
+/// of the configured consumer are never touched.
+pub(crate) fn lookup_consumer(config: &IggyConsumerConfig) -> Result<Consumer, IggyError> {
+    let name = format!("{}-lookup", config.consumer_name());
+    Ok(Consumer::new(name.as_str().try_into()?))
+}
@@ -0,0 +1,16 @@
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// The position a consumer is moved to by a seek.
+///
+/// After a seek, the next message delivered to the consumer is the one at the target position.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SeekTarget {
+    /// The message at the given offset.
+    Offset(u64),
+    /// The first message of the partition.
+    First,
+    /// The last message of the partition.
+    Last,
+    /// The first message with a timestamp at or after the given timestamp.
+    Timestamp(IggyTimestamp),
+}
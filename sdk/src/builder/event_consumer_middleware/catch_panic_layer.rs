@@ -0,0 +1,64 @@
+use crate::builder::{EventConsumer, EventConsumerError, EventConsumerLayer};
+use futures::FutureExt;
+use iggy::models::messages::PolledMessage;
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+
+/// Layer that converts a panic inside `consume` into an `EventConsumerError`.
+///
+/// Without this layer, a panicking handler takes down the task that runs the consume loop.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CatchPanicLayer {}
+
+impl CatchPanicLayer {
+    /// Creates a new `CatchPanicLayer`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<C> EventConsumerLayer<C> for CatchPanicLayer
+where
+    C: EventConsumer + Sync,
+{
+    type Consumer = CatchPanicEventConsumer<C>;
+
+    fn layer(&self, inner: C) -> Self::Consumer {
+        CatchPanicEventConsumer { inner }
+    }
+}
+
+/// `EventConsumer` produced by the `CatchPanicLayer`.
+#[derive(Debug, Clone)]
+pub struct CatchPanicEventConsumer<C> {
+    inner: C,
+}
+
+impl<C> EventConsumer for CatchPanicEventConsumer<C>
+where
+    C: EventConsumer + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let offset = message.offset;
+        match AssertUnwindSafe(self.inner.consume(message))
+            .catch_unwind()
+            .await
+        {
+            Ok(res) => res,
+            Err(panic) => Err(EventConsumerError::new(format!(
+                "Panic while consuming message at offset: {offset}: {}",
+                panic_message(&panic)
+            ))),
+        }
+    }
+}
+
+pub(crate) fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = panic.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown panic"
+    }
+}
@@ -0,0 +1,118 @@
+use crate::builder::{EventConsumer, EventConsumerError, EventConsumerLayer};
+use iggy::models::messages::PolledMessage;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Layer that skips messages whose ID was already consumed within a window.
+///
+/// The window holds at most `max_ids` IDs, each for at most `ttl`. An ID is only
+/// recorded after the inner consumer succeeded, so a failed message is not skipped
+/// when it is delivered again. Pairs with the stable message IDs of the
+/// `RetryingEventProducer`.
+#[derive(Debug, Clone)]
+pub struct DeduplicationLayer {
+    window: Arc<Mutex<DeduplicationWindow>>,
+}
+
+impl DeduplicationLayer {
+    /// Creates a new `DeduplicationLayer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_ids` - The max number of IDs kept in the window.
+    /// * `ttl` - How long an ID is kept in the window.
+    ///
+    pub fn new(max_ids: usize, ttl: Duration) -> Self {
+        Self {
+            window: Arc::new(Mutex::new(DeduplicationWindow::new(max_ids, ttl))),
+        }
+    }
+}
+
+impl<C> EventConsumerLayer<C> for DeduplicationLayer
+where
+    C: EventConsumer + Sync,
+{
+    type Consumer = DeduplicationEventConsumer<C>;
+
+    fn layer(&self, inner: C) -> Self::Consumer {
+        DeduplicationEventConsumer {
+            inner,
+            window: self.window.clone(),
+        }
+    }
+}
+
+/// `EventConsumer` produced by the `DeduplicationLayer`.
+#[derive(Debug, Clone)]
+pub struct DeduplicationEventConsumer<C> {
+    inner: C,
+    window: Arc<Mutex<DeduplicationWindow>>,
+}
+
+impl<C> EventConsumer for DeduplicationEventConsumer<C>
+where
+    C: EventConsumer + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let id = message.id;
+        if self.window.lock().unwrap().contains(id, Instant::now()) {
+            debug!(
+                "Skipping duplicate message with ID: {id} at offset: {}",
+                message.offset
+            );
+            return Ok(());
+        }
+
+        self.inner.consume(message).await?;
+        self.window.lock().unwrap().insert(id, Instant::now());
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DeduplicationWindow {
+    max_ids: usize,
+    ttl: Duration,
+    seen: HashMap<u128, Instant>,
+    order: VecDeque<(u128, Instant)>,
+}
+
+impl DeduplicationWindow {
+    fn new(max_ids: usize, ttl: Duration) -> Self {
+        Self {
+            max_ids,
+            ttl,
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn contains(&mut self, id: u128, now: Instant) -> bool {
+        self.evict(now);
+        self.seen.contains_key(&id)
+    }
+
+    fn insert(&mut self, id: u128, now: Instant) {
+        self.seen.insert(id, now);
+        self.order.push_back((id, now));
+        self.evict(now);
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while let Some((id, seen_at)) = self.order.front().copied() {
+            let expired = now.duration_since(seen_at) > self.ttl;
+            if !expired && self.seen.len() <= self.max_ids {
+                break;
+            }
+
+            self.order.pop_front();
+            // Only remove the ID if it was not recorded again later.
+            if self.seen.get(&id) == Some(&seen_at) {
+                self.seen.remove(&id);
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::builder::EventConsumer;
+
+/// Decorates an `EventConsumer` with cross-cutting behaviour.
+///
+/// A layer takes the inner consumer and returns a new consumer that wraps it,
+/// so that logging, timing, timeouts and similar concerns can be written once
+/// and stacked around any `EventConsumer` implementation.
+pub trait EventConsumerLayer<C>
+where
+    C: EventConsumer,
+{
+    /// The consumer produced by wrapping the inner consumer.
+    type Consumer: EventConsumer;
+
+    /// Wraps the given consumer with this layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The consumer to wrap.
+    ///
+    fn layer(&self, inner: C) -> Self::Consumer;
+}
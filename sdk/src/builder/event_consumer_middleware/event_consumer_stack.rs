@@ -0,0 +1,55 @@
+use crate::builder::{EventConsumer, EventConsumerLayer};
+
+/// Builder that stacks `EventConsumerLayer`s around an `EventConsumer`.
+///
+/// Layers are applied in the order they are added, so the last layer added
+/// becomes the outermost one and sees each message first.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let consumer = EventConsumerStack::new(MyConsumer {})
+///     .layer(CatchPanicLayer::new())
+///     .layer(TimeoutLayer::new(Duration::from_secs(5)))
+///     .layer(TracingLayer::new("orders"))
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventConsumerStack<C> {
+    inner: C,
+}
+
+impl<C> EventConsumerStack<C>
+where
+    C: EventConsumer,
+{
+    /// Creates a new stack around the given consumer.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The innermost consumer that handles the messages.
+    ///
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+
+    /// Wraps the current stack with the given layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to apply on top of the current stack.
+    ///
+    pub fn layer<L>(self, layer: L) -> EventConsumerStack<L::Consumer>
+    where
+        L: EventConsumerLayer<C>,
+    {
+        EventConsumerStack {
+            inner: layer.layer(self.inner),
+        }
+    }
+
+    /// Returns the fully layered consumer.
+    pub fn build(self) -> C {
+        self.inner
+    }
+}
@@ -0,0 +1,122 @@
+use crate::builder::{EventConsumer, EventConsumerError, EventConsumerLayer};
+use iggy::models::messages::PolledMessage;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Counters collected by the `MetricsLayer`.
+///
+/// The metrics are shared, so a clone can be kept by the caller and read
+/// while the consumer is running.
+#[derive(Debug, Default)]
+pub struct EventConsumerMetrics {
+    consumed: AtomicU64,
+    failed: AtomicU64,
+    total_duration_micros: AtomicU64,
+    max_duration_micros: AtomicU64,
+}
+
+impl EventConsumerMetrics {
+    /// Returns the number of messages consumed successfully.
+    pub fn consumed(&self) -> u64 {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages for which `consume` returned an error.
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total time spent in `consume`.
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_micros(self.total_duration_micros.load(Ordering::Relaxed))
+    }
+
+    /// Returns the longest single `consume` call.
+    pub fn max_duration(&self) -> Duration {
+        Duration::from_micros(self.max_duration_micros.load(Ordering::Relaxed))
+    }
+
+    /// Returns the average duration of a `consume` call, or zero if nothing was consumed yet.
+    pub fn average_duration(&self) -> Duration {
+        let calls = self.consumed() + self.failed();
+        if calls == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros(self.total_duration_micros.load(Ordering::Relaxed) / calls)
+    }
+
+    fn record(&self, elapsed: Duration, success: bool) {
+        let micros = elapsed.as_micros() as u64;
+        if success {
+            self.consumed.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_duration_micros
+            .fetch_add(micros, Ordering::Relaxed);
+        self.max_duration_micros
+            .fetch_max(micros, Ordering::Relaxed);
+    }
+}
+
+/// Layer that records call counts and durations into `EventConsumerMetrics`.
+#[derive(Debug, Default, Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<EventConsumerMetrics>,
+}
+
+impl MetricsLayer {
+    /// Creates a new `MetricsLayer` with fresh metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `MetricsLayer` that records into existing metrics.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics` - The shared metrics to record into.
+    ///
+    pub fn with_metrics(metrics: Arc<EventConsumerMetrics>) -> Self {
+        Self { metrics }
+    }
+
+    /// Returns the shared metrics recorded by this layer.
+    pub fn metrics(&self) -> Arc<EventConsumerMetrics> {
+        self.metrics.clone()
+    }
+}
+
+impl<C> EventConsumerLayer<C> for MetricsLayer
+where
+    C: EventConsumer + Sync,
+{
+    type Consumer = MetricsEventConsumer<C>;
+
+    fn layer(&self, inner: C) -> Self::Consumer {
+        MetricsEventConsumer {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+/// `EventConsumer` produced by the `MetricsLayer`.
+#[derive(Debug, Clone)]
+pub struct MetricsEventConsumer<C> {
+    inner: C,
+    metrics: Arc<EventConsumerMetrics>,
+}
+
+impl<C> EventConsumer for MetricsEventConsumer<C>
+where
+    C: EventConsumer + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let start = Instant::now();
+        let res = self.inner.consume(message).await;
+        self.metrics.record(start.elapsed(), res.is_ok());
+        res
+    }
+}
@@ -0,0 +1,16 @@
+mod catch_panic_layer;
+mod deduplication_layer;
+mod event_consumer_layer;
+mod event_consumer_stack;
+mod metrics_layer;
+mod timeout_layer;
+mod tracing_layer;
+
+pub(crate) use catch_panic_layer::panic_message;
+pub use catch_panic_layer::{CatchPanicEventConsumer, CatchPanicLayer};
+pub use deduplication_layer::{DeduplicationEventConsumer, DeduplicationLayer};
+pub use event_consumer_layer::EventConsumerLayer;
+pub use event_consumer_stack::EventConsumerStack;
+pub use metrics_layer::{EventConsumerMetrics, MetricsEventConsumer, MetricsLayer};
+pub use timeout_layer::{TimeoutEventConsumer, TimeoutLayer};
+pub use tracing_layer::{TracingEventConsumer, TracingLayer};
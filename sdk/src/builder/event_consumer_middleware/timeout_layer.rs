@@ -0,0 +1,60 @@
+use crate::builder::{EventConsumer, EventConsumerError, EventConsumerLayer};
+use iggy::models::messages::PolledMessage;
+use std::time::Duration;
+
+/// Layer that cancels a `consume` call once it exceeds the given duration.
+///
+/// A timed out call is dropped and reported as an `EventConsumerError`.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    /// Creates a new `TimeoutLayer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum duration a single `consume` call may take.
+    ///
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<C> EventConsumerLayer<C> for TimeoutLayer
+where
+    C: EventConsumer + Sync,
+{
+    type Consumer = TimeoutEventConsumer<C>;
+
+    fn layer(&self, inner: C) -> Self::Consumer {
+        TimeoutEventConsumer {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// `EventConsumer` produced by the `TimeoutLayer`.
+#[derive(Debug, Clone)]
+pub struct TimeoutEventConsumer<C> {
+    inner: C,
+    timeout: Duration,
+}
+
+impl<C> EventConsumer for TimeoutEventConsumer<C>
+where
+    C: EventConsumer + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let offset = message.offset;
+        match tokio::time::timeout(self.timeout, self.inner.consume(message)).await {
+            Ok(res) => res,
+            Err(_) => Err(EventConsumerError::new(format!(
+                "Timed out after {:?} while consuming message at offset: {offset}",
+                self.timeout
+            ))),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::builder::{EventConsumer, EventConsumerError, EventConsumerLayer};
+use iggy::models::messages::PolledMessage;
+use tracing::{error, info_span, Instrument};
+
+/// Layer that runs every `consume` call inside a tracing span.
+///
+/// The span carries the configured name together with the message offset and ID,
+/// and failed calls are logged inside that span.
+#[derive(Debug, Clone)]
+pub struct TracingLayer {
+    name: String,
+}
+
+impl TracingLayer {
+    /// Creates a new `TracingLayer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name recorded on each span, usually the name of the consumer.
+    ///
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<C> EventConsumerLayer<C> for TracingLayer
+where
+    C: EventConsumer + Sync,
+{
+    type Consumer = TracingEventConsumer<C>;
+
+    fn layer(&self, inner: C) -> Self::Consumer {
+        TracingEventConsumer {
+            inner,
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// `EventConsumer` produced by the `TracingLayer`.
+#[derive(Debug, Clone)]
+pub struct TracingEventConsumer<C> {
+    inner: C,
+    name: String,
+}
+
+impl<C> EventConsumer for TracingEventConsumer<C>
+where
+    C: EventConsumer + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let span = info_span!(
+            "consume",
+            consumer = %self.name,
+            offset = message.offset,
+            id = %message.id
+        );
+
+        async {
+            let res = self.inner.consume(message).await;
+            if let Err(err) = &res {
+                error!("Failed to consume message: {err}");
+            }
+            res
+        }
+        .instrument(span)
+        .await
+    }
+}
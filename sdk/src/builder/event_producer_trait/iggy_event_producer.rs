@@ -0,0 +1,26 @@
+use crate::builder::EventProducer;
+use iggy::clients::producer::IggyProducer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+
+impl EventProducer for IggyProducer {
+    /// Send a single iggy message with the producer's configured partitioning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message cannot be sent.
+    ///
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_one(message).await
+    }
+
+    /// Send a batch of iggy messages with the producer's configured partitioning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the messages cannot be sent.
+    ///
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        self.send(messages).await
+    }
+}
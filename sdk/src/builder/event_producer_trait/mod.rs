@@ -1,3 +1,4 @@
 mod event_producer;
+mod iggy_event_producer;
 
 pub use event_producer::EventProducer;
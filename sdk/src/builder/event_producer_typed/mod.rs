@@ -0,0 +1,3 @@
+mod typed_event_producer;
+
+pub use typed_event_producer::TypedEventProducer;
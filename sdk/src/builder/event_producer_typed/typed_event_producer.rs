@@ -0,0 +1,160 @@
+use crate::builder::{EventCodec, EventProducer, JsonCodec, MessageHeaders};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use serde::Serialize;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Producer that serializes typed values with an `EventCodec` before sending them
+/// through an `EventProducer`.
+///
+/// Every message carries a `content-type` header with the content type of the codec.
+/// With `with_partition_key`, every message also carries a `partition-key` header; combine it
+/// with `HeaderPartitionKey` in the `IggyProducerConfig` to send all values with the same key
+/// to the same partition.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let producer = IggyStreamProducer::new(&client, &config).await?;
+/// let orders = TypedEventProducer::<OrderCreated, _>::json(producer);
+/// orders.send(&order).await?;
+///
+/// let orders = TypedEventProducer::<OrderCreated, _>::json(producer)
+///     .with_partition_key(|order| order.order_id.to_string());
+/// ```
+pub struct TypedEventProducer<T, P, C = JsonCodec> {
+    producer: P,
+    codec: C,
+    partition_key: Option<PartitionKeyFn<T>>,
+    _value: PhantomData<fn(&T)>,
+}
+
+type PartitionKeyFn<T> = Box<dyn Fn(&T) -> String + Send + Sync>;
+
+impl<T, P: Debug, C: Debug> Debug for TypedEventProducer<T, P, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedEventProducer")
+            .field("producer", &self.producer)
+            .field("codec", &self.codec)
+            .field("partition_key", &self.partition_key.is_some())
+            .finish()
+    }
+}
+
+impl<T, P> TypedEventProducer<T, P, JsonCodec>
+where
+    T: Serialize + Sync,
+    P: EventProducer + Sync,
+{
+    /// Creates a new `TypedEventProducer` that serializes values as JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    ///
+    pub fn json(producer: P) -> Self {
+        Self::new(producer, JsonCodec::default())
+    }
+}
+
+impl<T, P, C> TypedEventProducer<T, P, C>
+where
+    T: Serialize + Sync,
+    P: EventProducer + Sync,
+    C: EventCodec,
+{
+    /// Creates a new `TypedEventProducer` with the given codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    /// * `codec` - The `EventCodec` used to serialize the values.
+    ///
+    pub fn new(producer: P, codec: C) -> Self {
+        Self {
+            producer,
+            codec,
+            partition_key: None,
+            _value: PhantomData,
+        }
+    }
+
+    /// Derives a partitioning key from each value and writes it into the `partition-key` header.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_key` - Returns the key of a value, e.g. the order ID.
+    ///
+    pub fn with_partition_key<F>(mut self, partition_key: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        self.partition_key = Some(Box::new(partition_key));
+        self
+    }
+
+    /// Serializes and sends a single value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be serialized or the message cannot be sent.
+    ///
+    pub async fn send(&self, value: &T) -> Result<(), IggyError> {
+        let message = self.to_message(value)?;
+        self.producer.send_one_event(message).await
+    }
+
+    /// Serializes and sends a batch of values.
+    ///
+    /// With a partition key, the values are split into one batch per key so that each batch
+    /// lands on the partition of its key. The order of values with the same key is preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any value cannot be serialized or the messages cannot be sent.
+    ///
+    pub async fn send_batch(&self, values: &[T]) -> Result<(), IggyError> {
+        let Some(partition_key) = &self.partition_key else {
+            let messages = values
+                .iter()
+                .map(|value| self.to_message(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            return self.producer.send_event_batch(messages).await;
+        };
+
+        let mut batches: Vec<(String, Vec<Message>)> = Vec::new();
+        for value in values {
+            let key = partition_key(value);
+            let message = self.to_message(value)?;
+            match batches.iter_mut().find(|(batch_key, _)| *batch_key == key) {
+                Some((_, batch)) => batch.push(message),
+                None => batches.push((key, vec![message])),
+            }
+        }
+
+        for (_, messages) in batches {
+            self.producer.send_event_batch(messages).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the codec used to serialize the values.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    fn to_message(&self, value: &T) -> Result<Message, IggyError> {
+        let payload = self.codec.encode(value)?;
+        let mut headers = MessageHeaders::new().content_type(self.codec.content_type());
+        if let Some(partition_key) = &self.partition_key {
+            headers = headers.partition_key(&partition_key(value));
+        }
+        headers.message(payload)
+    }
+}
@@ -0,0 +1,34 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The state of an event-sourced entity, rebuilt by applying its events in order.
+///
+/// The state itself is serialized with the codec of the `AggregateRepository` when a
+/// snapshot is stored.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Default, Serialize, Deserialize)]
+/// struct Account {
+///     balance: i64,
+/// }
+///
+/// impl Aggregate for Account {
+///     type Event = AccountEvent;
+///
+///     fn apply(&mut self, event: AccountEvent) {
+///         match event {
+///             AccountEvent::Deposited(amount) => self.balance += amount,
+///             AccountEvent::Withdrawn(amount) => self.balance -= amount,
+///         }
+///     }
+/// }
+/// ```
+pub trait Aggregate: Default + Serialize + DeserializeOwned + Send + Sync {
+    /// The type of the events of the aggregate.
+    type Event: Serialize + DeserializeOwned + Send + Sync;
+
+    /// Applies a single event to the state.
+    fn apply(&mut self, event: Self::Event);
+}
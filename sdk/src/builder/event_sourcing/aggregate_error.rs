@@ -0,0 +1,40 @@
+use iggy::error::IggyError;
+use std::error::Error;
+use std::fmt;
+
+/// The reason loading or appending to an aggregate failed.
+#[derive(Debug)]
+pub enum AggregateError {
+    /// The events could not be read, decoded or sent.
+    Iggy(IggyError),
+    /// The aggregate was changed since it was loaded.
+    Conflict {
+        aggregate_id: String,
+        expected_version: u64,
+        actual_version: u64,
+    },
+}
+
+impl Error for AggregateError {}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AggregateError::Iggy(err) => write!(f, "AggregateError: {err}"),
+            AggregateError::Conflict {
+                aggregate_id,
+                expected_version,
+                actual_version,
+            } => write!(
+                f,
+                "AggregateError: aggregate {aggregate_id} is at version {actual_version}, expected {expected_version}"
+            ),
+        }
+    }
+}
+
+impl From<IggyError> for AggregateError {
+    fn from(err: IggyError) -> Self {
+        AggregateError::Iggy(err)
+    }
+}
@@ -0,0 +1,380 @@
+use crate::builder::consumer_seek::lookup_consumer;
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{
+    assign_message_ids, decompress_polled_message, Aggregate, AggregateError,
+    AggregateRepositoryConfig, EventCodec, HeaderPartitionKey, JsonCodec, KeyPartitioner,
+    LoadedAggregate, MessageHeaders, MessageHeadersExt, SnapshotStore,
+};
+use iggy::client::MessageClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessage;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Loads and appends the events of event-sourced aggregates in a topic.
+///
+/// Every event carries the `aggregate-id` and `aggregate-version` headers, and the
+/// `aggregate-expected-version` and `aggregate-batch-id` headers of the append it belongs to.
+/// All events of an aggregate are sent to the partition derived from its ID, like
+/// `KeyPartitioner` with `HeaderPartitionKey` does. Loading replays that partition, optionally
+/// starting at the latest snapshot.
+///
+/// Appends check the expected version against the current version and are serialized
+/// within the repository. Iggy has no conditional append, so two processes can still
+/// race; in that case only the events of the first writer get applied when loading,
+/// because a batch is applied only if it was appended at the current version of the
+/// aggregate. The losing writer detects this by reading its events back and gets a conflict.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let accounts = AggregateRepository::<Account>::json(client, config)
+///     .await?
+///     .with_snapshots(SqliteSnapshotStore::open(&snapshot_config)?);
+///
+/// let account = accounts.load("account-42").await?;
+/// let version = accounts
+///     .append("account-42", account.version(), &[AccountEvent::Deposited(100)])
+///     .await?;
+/// ```
+pub struct AggregateRepository<A, C = JsonCodec> {
+    client: Arc<IggyClient>,
+    config: AggregateRepositoryConfig,
+    codec: C,
+    partitioner: KeyPartitioner,
+    snapshots: Option<Box<dyn SnapshotStore>>,
+    append_lock: Mutex<()>,
+    _aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A, C: Debug> Debug for AggregateRepository<A, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregateRepository")
+            .field("config", &self.config)
+            .field("codec", &self.codec)
+            .field("partitioner", &self.partitioner)
+            .field("snapshots", &self.snapshots.is_some())
+            .finish()
+    }
+}
+
+impl<A> AggregateRepository<A, JsonCodec>
+where
+    A: Aggregate,
+{
+    /// Creates a new `AggregateRepository` that encodes events and snapshots as JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The shared `IggyClient` to use.
+    /// * `config` - The event topic and snapshot interval.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the event topic does not exist.
+    ///
+    pub async fn json(
+        client: Arc<IggyClient>,
+        config: AggregateRepositoryConfig,
+    ) -> Result<Self, IggyError> {
+        Self::new(client, config, JsonCodec::default()).await
+    }
+}
+
+impl<A, C> AggregateRepository<A, C>
+where
+    A: Aggregate,
+    C: EventCodec,
+{
+    /// Creates a new `AggregateRepository` with the given codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The shared `IggyClient` to use.
+    /// * `config` - The event topic and snapshot interval.
+    /// * `codec` - The `EventCodec` used for events and snapshots.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the event topic does not exist.
+    ///
+    pub async fn new(
+        client: Arc<IggyClient>,
+        config: AggregateRepositoryConfig,
+        codec: C,
+    ) -> Result<Self, IggyError> {
+        let topic = get_topic_details(&client, config.topic_config()).await?;
+        let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), topic.partitions_count);
+
+        Ok(Self {
+            client,
+            config,
+            codec,
+            partitioner,
+            snapshots: None,
+            append_lock: Mutex::new(()),
+            _aggregate: PhantomData,
+        })
+    }
+
+    /// Stores snapshots in the given store, so that loading only replays newer events.
+    pub fn with_snapshots(mut self, snapshots: impl SnapshotStore + 'static) -> Self {
+        self.snapshots = Some(Box::new(snapshots));
+        self
+    }
+
+    /// Returns the client of the repository.
+    pub fn client(&self) -> &Arc<IggyClient> {
+        &self.client
+    }
+
+    /// Returns the repository configuration.
+    pub fn config(&self) -> &AggregateRepositoryConfig {
+        &self.config
+    }
+
+    /// Returns the codec used for events and snapshots.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns the partition that stores the events of the aggregate.
+    pub fn partition_for(&self, aggregate_id: &str) -> u32 {
+        self.partitioner.partition_for_key(aggregate_id.as_bytes())
+    }
+
+    /// Loads the aggregate by replaying its events after the latest snapshot.
+    ///
+    /// An aggregate without events is returned at version 0 with the default state.
+    /// If a snapshot store is set and at least `snapshot_interval` events were replayed,
+    /// a new snapshot is stored.
+    ///
+    /// # Errors
+    ///
+    /// * `AggregateError::Iggy` - If the events cannot be polled or decoded, or the
+    ///   snapshot cannot be read or written.
+    ///
+    pub async fn load(&self, aggregate_id: &str) -> Result<LoadedAggregate<A>, AggregateError> {
+        let mut aggregate = match &self.snapshots {
+            Some(snapshots) => match snapshots.load(aggregate_id)? {
+                Some(snapshot) => LoadedAggregate::from_snapshot(&snapshot, &self.codec)?,
+                None => LoadedAggregate::new(aggregate_id),
+            },
+            None => LoadedAggregate::new(aggregate_id),
+        };
+        let snapshot_version = aggregate.version();
+
+        let start_offset = aggregate.offset().map_or(0, |offset| offset + 1);
+        self.poll_events(aggregate_id, start_offset, |message| {
+            let message = decompress_polled_message(message)?;
+            aggregate.apply_message(&message, &self.codec)?;
+            Ok(())
+        })
+        .await?;
+
+        debug!(
+            "Loaded aggregate: {aggregate_id} at version: {}",
+            aggregate.version()
+        );
+        self.save_snapshot(&aggregate, snapshot_version)?;
+        Ok(aggregate)
+    }
+
+    /// Appends events to the aggregate if it is still at the expected version.
+    ///
+    /// The events are sent in one batch to the partition of the aggregate. Afterwards they
+    /// are read back to confirm that they were applied at the versions after the expected
+    /// version, and not lost to a writer in another process that appended first.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate_id` - The ID of the aggregate.
+    /// * `expected_version` - The version the events were decided on, usually the
+    ///   version of the `LoadedAggregate`.
+    /// * `events` - The new events.
+    ///
+    /// Returns:
+    /// The new version of the aggregate.
+    ///
+    /// # Errors
+    ///
+    /// * `AggregateError::Conflict` - If other events were appended since the expected version,
+    ///   before or while the events were sent.
+    /// * `AggregateError::Iggy` - If the events cannot be loaded, encoded, sent or read back.
+    ///
+    pub async fn append(
+        &self,
+        aggregate_id: &str,
+        expected_version: u64,
+        events: &[A::Event],
+    ) -> Result<u64, AggregateError> {
+        let _guard = self.append_lock.lock().await;
+
+        let current = self.load(aggregate_id).await?;
+        let actual_version = current.version();
+        if actual_version != expected_version {
+            return Err(AggregateError::Conflict {
+                aggregate_id: aggregate_id.to_string(),
+                expected_version,
+                actual_version,
+            });
+        }
+        if events.is_empty() {
+            return Ok(expected_version);
+        }
+
+        let batch_id = Uuid::now_v7().as_u128();
+        let mut messages = events
+            .iter()
+            .zip(expected_version + 1..)
+            .map(|(event, version)| {
+                self.to_message(aggregate_id, expected_version, batch_id, version, event)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assign_message_ids(&mut messages);
+        let message_ids: Vec<u128> = messages.iter().map(|message| message.id).collect();
+
+        let topic_config = self.config.topic_config();
+        self.client
+            .send_messages(
+                topic_config.stream_id(),
+                topic_config.topic_id(),
+                &Partitioning::partition_id(self.partition_for(aggregate_id)),
+                &mut messages,
+            )
+            .await?;
+
+        self.verify_append(current, &message_ids).await?;
+
+        let version = expected_version + events.len() as u64;
+        info!(
+            "Appended {} events to aggregate: {aggregate_id}, version: {version}",
+            events.len()
+        );
+        Ok(version)
+    }
+
+    /// Replays the events after the loaded aggregate like `load` does and checks that the
+    /// events applied after the expected version are the sent messages.
+    async fn verify_append(
+        &self,
+        mut aggregate: LoadedAggregate<A>,
+        message_ids: &[u128],
+    ) -> Result<(), AggregateError> {
+        let aggregate_id = aggregate.id().to_string();
+        let expected_version = aggregate.version();
+        let start_offset = aggregate.offset().map_or(0, |offset| offset + 1);
+        let mut applied_ids = Vec::with_capacity(message_ids.len());
+        self.poll_events(&aggregate_id, start_offset, |message| {
+            let id = message.id;
+            let message = decompress_polled_message(message)?;
+            if aggregate.apply_message(&message, &self.codec)? {
+                applied_ids.push(id);
+            }
+            Ok(())
+        })
+        .await?;
+
+        if !applied_ids.starts_with(message_ids) {
+            let actual_version = aggregate.version();
+            warn!(
+                "Events appended to aggregate: {aggregate_id} at version: {expected_version} were not applied, the aggregate is at version: {actual_version}"
+            );
+            return Err(AggregateError::Conflict {
+                aggregate_id,
+                expected_version,
+                actual_version,
+            });
+        }
+        Ok(())
+    }
+
+    /// Polls the partition of the aggregate from `start_offset` to the end and passes the
+    /// messages of the aggregate to `handle`.
+    async fn poll_events(
+        &self,
+        aggregate_id: &str,
+        start_offset: u64,
+        mut handle: impl FnMut(PolledMessage) -> Result<(), AggregateError>,
+    ) -> Result<(), AggregateError> {
+        let topic_config = self.config.topic_config();
+        let partition_id = self.partition_for(aggregate_id);
+        let consumer = lookup_consumer(topic_config)?;
+        let mut strategy = PollingStrategy::offset(start_offset);
+        loop {
+            let polled = self
+                .client
+                .poll_messages(
+                    topic_config.stream_id(),
+                    topic_config.topic_id(),
+                    Some(partition_id),
+                    &consumer,
+                    &strategy,
+                    topic_config.batch_size(),
+                    false,
+                )
+                .await?;
+
+            let Some(last_offset) = polled.messages.last().map(|message| message.offset) else {
+                return Ok(());
+            };
+
+            for message in polled.messages {
+                if message.aggregate_id() == Some(aggregate_id) {
+                    handle(message)?;
+                }
+            }
+
+            strategy = PollingStrategy::offset(last_offset + 1);
+        }
+    }
+
+    fn to_message(
+        &self,
+        aggregate_id: &str,
+        expected_version: u64,
+        batch_id: u128,
+        version: u64,
+        event: &A::Event,
+    ) -> Result<Message, IggyError> {
+        let payload = self.codec.encode(event)?;
+        MessageHeaders::new()
+            .content_type(self.codec.content_type())
+            .partition_key(aggregate_id)
+            .aggregate_id(aggregate_id)
+            .aggregate_version(version)
+            .aggregate_expected_version(expected_version)
+            .aggregate_batch_id(batch_id)
+            .message(payload)
+    }
+
+    fn save_snapshot(
+        &self,
+        aggregate: &LoadedAggregate<A>,
+        snapshot_version: u64,
+    ) -> Result<(), IggyError> {
+        let Some(snapshots) = &self.snapshots else {
+            return Ok(());
+        };
+        let interval = self.config.snapshot_interval();
+        if interval == 0 || aggregate.version() < snapshot_version + interval {
+            return Ok(());
+        }
+
+        if let Some(snapshot) = aggregate.snapshot(&self.codec)? {
+            debug!(
+                "Saving snapshot of aggregate: {} at version: {}",
+                snapshot.aggregate_id, snapshot.version
+            );
+            snapshots.save(&snapshot)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::builder::{AggregateSnapshot, SnapshotStore};
+use iggy::error::IggyError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// `SnapshotStore` that keeps the snapshots in memory.
+#[derive(Debug, Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: Mutex<HashMap<String, AggregateSnapshot>>,
+}
+
+impl InMemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no snapshot is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SnapshotStore for InMemorySnapshotStore {
+    fn load(&self, aggregate_id: &str) -> Result<Option<AggregateSnapshot>, IggyError> {
+        Ok(self.snapshots.lock().unwrap().get(aggregate_id).cloned())
+    }
+
+    fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), IggyError> {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(snapshot.aggregate_id.clone(), snapshot.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::builder::{Aggregate, AggregateSnapshot, EventCodec, MessageHeadersExt};
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use tracing::warn;
+
+/// An aggregate rebuilt from its events, with the version it was loaded at.
+///
+/// Pass the version to `AggregateRepository::append` as the expected version of the new events.
+#[derive(Debug)]
+pub struct LoadedAggregate<A> {
+    id: String,
+    state: A,
+    version: u64,
+    offset: Option<u64>,
+    batch_id: Option<u128>,
+}
+
+impl<A> LoadedAggregate<A>
+where
+    A: Aggregate,
+{
+    /// Creates a new aggregate at version 0 with the default state.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            state: A::default(),
+            version: 0,
+            offset: None,
+            batch_id: None,
+        }
+    }
+
+    /// Restores an aggregate from a snapshot.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the state cannot be decoded.
+    ///
+    pub fn from_snapshot(
+        snapshot: &AggregateSnapshot,
+        codec: &impl EventCodec,
+    ) -> Result<Self, IggyError> {
+        Ok(Self {
+            id: snapshot.aggregate_id.clone(),
+            state: codec.decode(&snapshot.state)?,
+            version: snapshot.version,
+            offset: Some(snapshot.offset),
+            batch_id: None,
+        })
+    }
+
+    /// Returns the ID of the aggregate.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> &A {
+        &self.state
+    }
+
+    /// Returns the version, i.e. the number of applied events.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns the offset of the last applied event.
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Returns the state.
+    pub fn into_state(self) -> A {
+        self.state
+    }
+
+    /// Applies the event in the message if it is the next event of this aggregate.
+    ///
+    /// Messages of other aggregates and messages at or before the last applied offset are
+    /// ignored. An event whose `aggregate-version` header is not the next version was
+    /// appended by a writer that lost a race and is ignored as well. Events appended in one
+    /// batch are applied together: the first event of a batch is applied only if the batch
+    /// was appended at the current version, and the other events only if the first one was.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The consumed message.
+    /// * `codec` - The `EventCodec` the event was encoded with.
+    ///
+    /// Returns:
+    /// `true` if the event was applied.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the event cannot be decoded.
+    ///
+    pub fn apply_message(
+        &mut self,
+        message: &PolledMessage,
+        codec: &impl EventCodec,
+    ) -> Result<bool, IggyError> {
+        if self.offset.is_some_and(|offset| message.offset <= offset)
+            || message.aggregate_id() != Some(self.id.as_str())
+        {
+            return Ok(false);
+        }
+
+        let next_version = self.version + 1;
+        if !self.is_next_event(message, next_version) {
+            warn!(
+                "Ignoring event at offset: {} of aggregate: {} at version: {}, appended by a writer that lost a race",
+                message.offset, self.id, self.version
+            );
+            return Ok(false);
+        }
+
+        let event = codec.decode(&message.payload)?;
+        self.state.apply(event);
+        self.version = next_version;
+        self.offset = Some(message.offset);
+        self.batch_id = message.aggregate_batch_id();
+        Ok(true)
+    }
+
+    fn is_next_event(&self, message: &PolledMessage, next_version: u64) -> bool {
+        if message
+            .aggregate_version()
+            .is_some_and(|version| version != next_version)
+        {
+            return false;
+        }
+
+        match (
+            message.aggregate_expected_version(),
+            message.aggregate_batch_id(),
+        ) {
+            // The first event of a batch, which must be appended at the current version.
+            (Some(expected_version), _) if expected_version + 1 == next_version => true,
+            // A later event of a batch, whose first event must have been applied.
+            (Some(_), batch_id) => batch_id.is_some() && batch_id == self.batch_id,
+            // Events without batch headers are checked by their version only.
+            (None, _) => true,
+        }
+    }
+
+    /// Returns a snapshot of the current state, or `None` if no event was applied yet.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the state cannot be encoded.
+    ///
+    pub fn snapshot(
+        &self,
+        codec: &impl EventCodec,
+    ) -> Result<Option<AggregateSnapshot>, IggyError> {
+        let Some(offset) = self.offset else {
+            return Ok(None);
+        };
+
+        Ok(Some(AggregateSnapshot {
+            aggregate_id: self.id.clone(),
+            version: self.version,
+            offset,
+            state: codec.encode(&self.state)?,
+        }))
+    }
+}
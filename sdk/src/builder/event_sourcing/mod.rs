@@ -0,0 +1,15 @@
+mod aggregate;
+mod aggregate_error;
+mod aggregate_repository;
+mod in_memory_snapshot_store;
+mod loaded_aggregate;
+mod snapshot_store;
+mod sqlite_snapshot_store;
+
+pub use aggregate::Aggregate;
+pub use aggregate_error::AggregateError;
+pub use aggregate_repository::AggregateRepository;
+pub use in_memory_snapshot_store::InMemorySnapshotStore;
+pub use loaded_aggregate::LoadedAggregate;
+pub use snapshot_store::{AggregateSnapshot, SnapshotStore};
+pub use sqlite_snapshot_store::SqliteSnapshotStore;
@@ -0,0 +1,32 @@
+use iggy::error::IggyError;
+
+/// The serialized state of an aggregate at a version.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct AggregateSnapshot {
+    /// The ID of the aggregate.
+    pub aggregate_id: String,
+    /// The version of the aggregate, i.e. the number of applied events.
+    pub version: u64,
+    /// The offset of the last applied event; loading continues after it.
+    pub offset: u64,
+    /// The state, serialized with the codec of the repository.
+    pub state: Vec<u8>,
+}
+
+/// Stores the latest snapshot of each aggregate, so that loading only replays
+/// the events after it.
+pub trait SnapshotStore: Send + Sync {
+    /// Returns the latest snapshot of the aggregate.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    fn load(&self, aggregate_id: &str) -> Result<Option<AggregateSnapshot>, IggyError>;
+
+    /// Replaces the snapshot of the aggregate.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be written.
+    fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), IggyError>;
+}
@@ -0,0 +1,129 @@
+use crate::builder::sqlite_tables::{create_tables, validate_table_name};
+use crate::builder::{AggregateSnapshot, SnapshotStore, StateStoreConfig};
+use iggy::error::IggyError;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+use tracing::error;
+
+/// `SnapshotStore` backed by a SQLite database on disk.
+///
+/// The snapshots are stored in the `<store_name>_snapshots` table.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let snapshots = SqliteSnapshotStore::open(&StateStoreConfig::new("accounts.db", "accounts"))?;
+/// let repository = AggregateRepository::json(client, config).await?.with_snapshots(snapshots);
+/// ```
+#[derive(Debug)]
+pub struct SqliteSnapshotStore {
+    connection: Mutex<Connection>,
+    table: String,
+}
+
+impl SqliteSnapshotStore {
+    /// Opens or creates the database and creates the snapshot table if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotReadFile` - If the database cannot be opened.
+    /// * `IggyError::CannotWriteToFile` - If the table cannot be created.
+    ///
+    pub fn open(config: &StateStoreConfig) -> Result<Self, IggyError> {
+        let connection = match Connection::open(config.path()) {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!(
+                    "Failed to open snapshot store {}: {err}",
+                    config.path().display()
+                );
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+        Self::with_connection(connection, config.store_name())
+    }
+
+    /// Creates the snapshot table in the given database if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotWriteToFile` - If the table cannot be created.
+    ///
+    pub fn with_connection(connection: Connection, store_name: &str) -> Result<Self, IggyError> {
+        validate_table_name("snapshot store", store_name)?;
+
+        let table = format!("{store_name}_snapshots");
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                aggregate_id TEXT PRIMARY KEY,
+                version INTEGER NOT NULL,
+                offset INTEGER NOT NULL,
+                state BLOB NOT NULL
+            );"
+        );
+        create_tables(&connection, "snapshot store", store_name, &sql)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+            table,
+        })
+    }
+}
+
+impl SnapshotStore for SqliteSnapshotStore {
+    fn load(&self, aggregate_id: &str) -> Result<Option<AggregateSnapshot>, IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!(
+            "SELECT version, offset, state FROM {} WHERE aggregate_id = ?1",
+            self.table
+        );
+        let snapshot = connection
+            .query_row(&sql, params![aggregate_id], |row| {
+                Ok(AggregateSnapshot {
+                    aggregate_id: aggregate_id.to_string(),
+                    version: row.get::<_, i64>(0)? as u64,
+                    offset: row.get::<_, i64>(1)? as u64,
+                    state: row.get(2)?,
+                })
+            })
+            .optional();
+
+        match snapshot {
+            Ok(snapshot) => Ok(snapshot),
+            Err(err) => {
+                error!("Failed to read snapshot of aggregate {aggregate_id}: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+
+    fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} (aggregate_id, version, offset, state) VALUES (?1, ?2, ?3, ?4)",
+            self.table
+        );
+        match connection.execute(
+            &sql,
+            params![
+                snapshot.aggregate_id,
+                snapshot.version as i64,
+                snapshot.offset as i64,
+                snapshot.state
+            ],
+        ) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    "Failed to write snapshot of aggregate {}: {err}",
+                    snapshot.aggregate_id
+                );
+                Err(IggyError::CannotWriteToFile)
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::builder::EventConsumerError;
+use std::fmt;
+
+/// The reason a message could not be consumed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConsumeFailureKind {
+    /// The `EventConsumer` returned an error.
+    Handler,
+    /// The `EventConsumer` did not finish within the configured handler timeout.
+    Timeout,
+    /// The `EventConsumer` panicked.
+    Panic,
+    /// The compressed payload could not be decompressed, so the `EventConsumer` was not called.
+    Decompression,
+    /// The chunks of a chunked message were invalid or did not all arrive within the chunk timeout.
+    Chunking,
+}
+
+impl fmt::Display for ConsumeFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConsumeFailureKind::Handler => write!(f, "handler error"),
+            ConsumeFailureKind::Timeout => write!(f, "handler timeout"),
+            ConsumeFailureKind::Panic => write!(f, "handler panic"),
+            ConsumeFailureKind::Decompression => write!(f, "decompression error"),
+            ConsumeFailureKind::Chunking => write!(f, "chunk reassembly error"),
+        }
+    }
+}
+
+/// A message that failed all consume attempts.
+#[derive(Debug, Clone)]
+pub struct ConsumeFailure {
+    pub partition_id: u32,
+    pub offset: u64,
+    pub kind: ConsumeFailureKind,
+    pub error: EventConsumerError,
+    pub attempts: u32,
+}
+
+impl fmt::Display for ConsumeFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at offset: {} in partition: {} after {} attempt(s): {}",
+            self.kind, self.offset, self.partition_id, self.attempts, self.error
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use crate::builder::event_consumer_middleware::panic_message;
+use crate::builder::{
+    ConsumeFailure, ConsumeFailureKind, ConsumeMessagesConfig, EventConsumerError,
+};
+use futures::FutureExt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use tracing::{error, warn};
+
+/// Runs a single attempt to handle a message until it succeeds or the retries of the config
+/// are used up. Every attempt is bounded by the handler timeout, and a panic counts as a
+/// failed attempt.
+///
+/// # Arguments
+///
+/// * `config` - The handler timeout, retries and retry interval.
+/// * `partition_id` - The partition of the message, used in the failure.
+/// * `offset` - The offset of the message, used in the failure.
+/// * `attempt` - Handles the message once.
+///
+/// # Errors
+///
+/// * `ConsumeFailure` - If all attempts failed, timed out or panicked.
+///
+pub(crate) async fn run_with_retries<T, F, Fut>(
+    config: &ConsumeMessagesConfig,
+    partition_id: u32,
+    offset: u64,
+    mut attempt: F,
+) -> Result<T, ConsumeFailure>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, EventConsumerError>>,
+{
+    let max_attempts = config.handler_retries() + 1;
+
+    let mut attempts = 1;
+    loop {
+        let (kind, error) = match run_attempt(config, offset, attempt()).await {
+            Ok(value) => return Ok(value),
+            Err(res) => res,
+        };
+
+        if attempts >= max_attempts {
+            return Err(ConsumeFailure {
+                partition_id,
+                offset,
+                kind,
+                error,
+                attempts,
+            });
+        }
+
+        warn!(
+            "Attempt {attempts}/{max_attempts} failed with {kind} at offset: {offset} in partition: {partition_id}: {error}"
+        );
+        attempts += 1;
+        tokio::time::sleep(config.retry_interval().get_duration()).await;
+    }
+}
+
+async fn run_attempt<T>(
+    config: &ConsumeMessagesConfig,
+    offset: u64,
+    attempt: impl Future<Output = Result<T, EventConsumerError>>,
+) -> Result<T, (ConsumeFailureKind, EventConsumerError)> {
+    let attempt = AssertUnwindSafe(attempt).catch_unwind();
+    let res = match config.handler_timeout() {
+        Some(timeout) => match tokio::time::timeout(timeout.get_duration(), attempt).await {
+            Ok(res) => res,
+            Err(_) => {
+                return Err((
+                    ConsumeFailureKind::Timeout,
+                    EventConsumerError::new(format!(
+                        "Handler timed out after {timeout} at offset: {offset}"
+                    )),
+                ))
+            }
+        },
+        None => attempt.await,
+    };
+
+    match res {
+        Ok(res) => res.map_err(|err| (ConsumeFailureKind::Handler, err)),
+        Err(panic) => Err((
+            ConsumeFailureKind::Panic,
+            EventConsumerError::new(format!(
+                "Panic while consuming message at offset: {offset}: {}",
+                panic_message(&panic)
+            )),
+        )),
+    }
+}
+
+/// Logs a failed message and sends it to the failure channel, if one is configured.
+pub(crate) fn report_failure(config: &ConsumeMessagesConfig, failure: ConsumeFailure) {
+    error!("Error while handling message: {failure}");
+    if let Some(sender) = config.failure_sender() {
+        if sender.send(failure).is_err() {
+            warn!("Failure receiver dropped, cannot report failed message");
+        }
+    }
+}
@@ -1,8 +1,14 @@
-use crate::builder::{EventConsumer, IggyConsumerMessageExt};
+use crate::builder::iggy_consumer_ext::consume_retry::{report_failure, run_with_retries};
+use crate::builder::{
+    decompress_polled_message_with_limit, is_fatal_client_error, ChunkAssembler, ConsumeFailure,
+    ConsumeFailureKind, ConsumeMessagesConfig, EventConsumer, EventConsumerError,
+    IggyConsumerMessageExt,
+};
 use async_trait::async_trait;
 use futures_util::StreamExt;
-use iggy::clients::consumer::IggyConsumer;
+use iggy::clients::consumer::{IggyConsumer, ReceivedMessage};
 use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
 use tokio::sync::oneshot;
 use tracing::{error, info};
 
@@ -11,11 +17,32 @@ impl IggyConsumerMessageExt for IggyConsumer {
     async fn consume_messages<P>(
         mut self,
         event_processor: &'static P,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError>
+    where
+        P: EventConsumer + Sync,
+    {
+        self.consume_messages_with_config(
+            event_processor,
+            ConsumeMessagesConfig::default(),
+            shutdown_rx,
+        )
+        .await
+    }
+
+    async fn consume_messages_with_config<P>(
+        mut self,
+        event_processor: &'static P,
+        config: ConsumeMessagesConfig,
         mut shutdown_rx: oneshot::Receiver<()>,
     ) -> Result<(), IggyError>
     where
         P: EventConsumer + Sync,
     {
+        let chunk_timeout = config.chunk_timeout().get_duration();
+        let mut chunks = ChunkAssembler::new(chunk_timeout)
+            .with_limits(config.max_chunks(), config.max_pending_bytes());
+        let mut eviction_timer = tokio::time::interval(chunk_timeout);
         loop {
             tokio::select! {
                 // Check first if we have received a shutdown signal
@@ -24,30 +51,22 @@ impl IggyConsumerMessageExt for IggyConsumer {
                     break;
                 }
 
+                _ = eviction_timer.tick() => {
+                    evict_expired_transfers(&mut chunks, &config);
+                }
+
                 message = self.next() => {
                     match message {
                         Some(Ok(received_message)) => {
-                            if let Err(err) = event_processor.consume(received_message.message).await {
-                                error!("Error while handling message: {err}");
-                            }
+                            handle_received_message(event_processor, &mut chunks, received_message, &config).await;
+                        }
+                        Some(Err(err)) if is_fatal_client_error(&err) => {
+                            error!("{err:?}: shutdown client: {err}");
+                            return Err(err);
                         }
                         Some(Err(err)) => {
-                            match err {
-                                IggyError::Disconnected |
-                                IggyError::CannotEstablishConnection |
-                                IggyError::StaleClient |
-                                IggyError::InvalidServerAddress |
-                                IggyError::InvalidClientAddress |
-                                IggyError::NotConnected |
-                                IggyError::ClientShutdown => {
-                                    error!("{err:?}: shutdown client: {err}");
-                                    return Err(err);
-                                }
-                                _ => {
-                                    error!("Error while handling message: {err}");
-                                    continue;
-                                }
-                            }
+                            error!("Error while handling message: {err}");
+                            continue;
                         }
                         None => break,
                     }
@@ -62,3 +81,136 @@ impl IggyConsumerMessageExt for IggyConsumer {
         Ok(())
     }
 }
+
+/// Reassembles chunked messages and runs the event processor on every complete message.
+/// Failed messages and rejected chunks are reported with `report_failure`.
+async fn handle_received_message<P>(
+    event_processor: &P,
+    chunks: &mut ChunkAssembler,
+    received_message: ReceivedMessage,
+    config: &ConsumeMessagesConfig,
+) where
+    P: EventConsumer + Sync,
+{
+    let partition_id = received_message.partition_id;
+    let offset = received_message.message.offset;
+    let res = match chunks.push(partition_id, received_message.message) {
+        Ok(Some(message)) => handle_message(event_processor, partition_id, message, config).await,
+        Ok(None) => Ok(()),
+        Err(err) => Err(ConsumeFailure {
+            partition_id,
+            offset,
+            kind: ConsumeFailureKind::Chunking,
+            error: EventConsumerError::new(err.to_string()),
+            attempts: 0,
+        }),
+    };
+    if let Err(failure) = res {
+        report_failure(config, failure);
+    }
+}
+
+/// Drops the transfers that did not complete within the chunk timeout and reports them
+/// with `report_failure`.
+fn evict_expired_transfers(chunks: &mut ChunkAssembler, config: &ConsumeMessagesConfig) {
+    for transfer in chunks.evict_expired() {
+        let error = EventConsumerError::new(format!(
+            "Transfer {} incomplete after {} of {} chunks",
+            transfer.transfer_id, transfer.received_chunks, transfer.chunk_count
+        ));
+        report_failure(
+            config,
+            ConsumeFailure {
+                partition_id: transfer.partition_id,
+                offset: transfer.first_offset,
+                kind: ConsumeFailureKind::Chunking,
+                error,
+                attempts: 0,
+            },
+        );
+    }
+}
+
+/// Runs the event processor on a single message like `consume_messages_with_config` does:
+/// compressed payloads are decompressed first, and the handler timeout and retries of the
+/// config are applied. A message that failed all attempts is reported to the failure channel
+/// of the config.
+///
+/// # Arguments
+///
+/// * `event_processor` - The `EventConsumer` that handles the message.
+/// * `partition_id` - The partition of the message.
+/// * `message` - The message to consume.
+/// * `config` - The handler timeout, retries and failure channel.
+///
+/// # Errors
+///
+/// * `ConsumeFailure` - If the message cannot be decompressed or all attempts failed.
+///
+pub async fn consume_message_with_config<P>(
+    event_processor: &P,
+    partition_id: u32,
+    message: PolledMessage,
+    config: &ConsumeMessagesConfig,
+) -> Result<(), ConsumeFailure>
+where
+    P: EventConsumer + Sync,
+{
+    let res = handle_message(event_processor, partition_id, message, config).await;
+    if let Err(failure) = &res {
+        report_failure(config, failure.clone());
+    }
+    res
+}
+
+/// Runs the event processor on a single message, applying the handler timeout and retries
+/// from the given config. Compressed payloads are decompressed first.
+///
+/// # Errors
+///
+/// * `ConsumeFailure` - If all attempts failed, timed out or panicked.
+///
+async fn handle_message<P>(
+    event_processor: &P,
+    partition_id: u32,
+    message: PolledMessage,
+    config: &ConsumeMessagesConfig,
+) -> Result<(), ConsumeFailure>
+where
+    P: EventConsumer + Sync,
+{
+    let offset = message.offset;
+    let message =
+        match decompress_polled_message_with_limit(message, config.max_decompressed_size()) {
+            Ok(message) => message,
+            Err(err) => {
+                return Err(ConsumeFailure {
+                    partition_id,
+                    offset,
+                    kind: ConsumeFailureKind::Decompression,
+                    error: EventConsumerError::new(err.to_string()),
+                    attempts: 0,
+                })
+            }
+        };
+
+    run_with_retries(config, partition_id, offset, || {
+        event_processor.consume(clone_polled_message(&message))
+    })
+    .await
+}
+
+/// `PolledMessage` does not implement `Clone`, so this copies the fields by hand.
+/// The payload is reference counted, so this does not copy the message body.
+pub(crate) fn clone_polled_message(message: &PolledMessage) -> PolledMessage {
+    PolledMessage {
+        offset: message.offset,
+        state: message.state,
+        timestamp: message.timestamp,
+        id: message.id,
+        checksum: message.checksum,
+        headers: message.headers.clone(),
+        length: message.length,
+        payload: message.payload.clone(),
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::builder::EventConsumer;
+use crate::builder::{ConsumeMessagesConfig, EventConsumer};
 use iggy::error::IggyError;
 use tokio::sync::oneshot;
 
@@ -13,4 +13,31 @@ pub trait IggyConsumerMessageExt {
     ) -> Result<(), IggyError>
     where
         P: EventConsumer + Sync;
+
+    /// Consume messages like `consume_messages`, but with a `ConsumeMessagesConfig`
+    /// that controls handler timeouts, retries and failure reporting.
+    ///
+    /// Chunked messages are reassembled before they are handled. The offsets of buffered
+    /// chunks are committed by the consumer's auto-commit like any other message, so a restart
+    /// in the middle of a transfer loses the transfer: its remaining chunks are reported as an
+    /// incomplete transfer once the chunk timeout expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_processor` - The `EventConsumer` that handles each message.
+    /// * `config` - The consume loop configuration.
+    /// * `shutdown_rx` - Stops the loop when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the client disconnects or shuts down.
+    ///
+    async fn consume_messages_with_config<P>(
+        mut self,
+        event_processor: &'static P,
+        config: ConsumeMessagesConfig,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError>
+    where
+        P: EventConsumer + Sync;
 }
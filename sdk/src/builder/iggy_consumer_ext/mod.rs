@@ -1,4 +1,10 @@
+mod consume_failure;
+mod consume_retry;
 mod iggy_consumer_message_ext;
 mod iggy_consumer_message_trait;
 
+pub use consume_failure::*;
+pub(crate) use consume_retry::{report_failure, run_with_retries};
+pub(crate) use iggy_consumer_message_ext::clone_polled_message;
+pub use iggy_consumer_message_ext::consume_message_with_config;
 pub use iggy_consumer_message_trait::*;
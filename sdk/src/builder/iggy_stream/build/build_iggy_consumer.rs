@@ -1,8 +1,10 @@
+use crate::builder::iggy_stream::build::build_offset_reset::apply_offset_reset_policy;
 use crate::builder::IggyConsumerConfig;
 use iggy::clients::client::IggyClient;
 use iggy::clients::consumer::IggyConsumer;
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollingStrategy;
 use tracing::{error, info};
 
 /// Builds an `IggyConsumer` from the given `IggyClient` and `IggyConsumerConfig`.
@@ -20,6 +22,8 @@ use tracing::{error, info};
 ///
 /// This function will create a new `IggyConsumer` with the given `IggyClient` and `IggyConsumerConfig`.
 /// The `IggyConsumerConfig` fields are used to configure the `IggyConsumer`.
+/// If the config has an offset reset policy, it is applied after the consumer
+/// has joined its consumer group.
 ///
 pub(crate) async fn build_iggy_consumer(
     client: &IggyClient,
@@ -33,7 +37,11 @@ pub(crate) async fn build_iggy_consumer(
     let consumer_name = config.consumer_name();
     let batch_size = config.batch_size();
     let polling_interval = config.polling_interval();
-    let polling_strategy = config.polling_strategy();
+    // With an offset reset policy, the consumer resumes from the offset stored by the policy.
+    let polling_strategy = match config.offset_reset() {
+        Some(_) => PollingStrategy::next(),
+        None => config.polling_strategy(),
+    };
     let partition = config.partitions_count();
     // let encryptor = config.encryptor().to_owned().unwrap();
 
@@ -59,5 +67,14 @@ pub(crate) async fn build_iggy_consumer(
         }
     }
 
+    info!("Apply offset reset policy");
+    match apply_offset_reset_policy(client, config).await {
+        Ok(_) => {}
+        Err(err) => {
+            error!("Failed to apply offset reset policy: {}", err);
+            return Err(err);
+        }
+    }
+
     Ok(consumer)
 }
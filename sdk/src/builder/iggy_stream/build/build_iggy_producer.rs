@@ -1,9 +1,11 @@
-use crate::builder::IggyProducerConfig;
+use crate::builder::{IggyProducerConfig, KeyPartitioner, KeyedEventProducer};
+use iggy::client::TopicClient;
 use iggy::clients::client::IggyClient;
 use iggy::clients::producer::IggyProducer;
 use iggy::error::IggyError;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
+use std::sync::Arc;
 use tracing::{error, info};
 
 /// Build a producer from the stream configuration.
@@ -21,11 +23,64 @@ use tracing::{error, info};
 ///
 /// This function will create a new `IggyProducer` with the given `IggyClient` and `IggyProducerConfig`.
 /// The `IggyProducerConfig` fields are used to configure the `IggyProducer`.
+/// If the config has a partition key extractor, a `KeyPartitioner` is installed so that
+/// messages with the same key are sent to the same partition. The partitioner maps the keys
+/// to the partitions of the existing topic, which may differ from the configured count.
 ///
 pub(crate) async fn build_iggy_producer(
     client: &IggyClient,
     config: &IggyProducerConfig,
 ) -> Result<IggyProducer, IggyError> {
+    let (producer, _) = build_producer(client, config, true).await?;
+    Ok(producer)
+}
+
+/// Build a producer like `build_iggy_producer` that sends every batch immediately,
+/// ignoring the `batch_size` and `send_interval` of the config.
+///
+/// Used below producers that collect their own batches.
+///
+/// # Errors
+///
+/// * `IggyError` - If the iggy producer cannot be build.
+///
+pub(crate) async fn build_unbatched_iggy_producer(
+    client: &IggyClient,
+    config: &IggyProducerConfig,
+) -> Result<IggyProducer, IggyError> {
+    let (producer, _) = build_producer(client, config, false).await?;
+    Ok(producer)
+}
+
+/// Build a producer like `build_iggy_producer` and wrap it in a `KeyedEventProducer`
+/// that splits every batch by the partitions of its keys.
+///
+/// # Errors
+///
+/// * `IggyError::InvalidConfiguration` - If the config has no partition key extractor.
+/// * `IggyError` - If the iggy producer cannot be build.
+///
+pub(crate) async fn build_keyed_iggy_producer(
+    client: &IggyClient,
+    config: &IggyProducerConfig,
+) -> Result<KeyedEventProducer<IggyProducer>, IggyError> {
+    if config.partition_key().is_none() {
+        error!("Key partitioning requires a partition key extractor in the producer config");
+        return Err(IggyError::InvalidConfiguration);
+    }
+
+    let (producer, partitioner) = build_producer(client, config, true).await?;
+    match partitioner {
+        Some(partitioner) => Ok(KeyedEventProducer::new(producer, partitioner)),
+        None => Err(IggyError::InvalidConfiguration),
+    }
+}
+
+async fn build_producer(
+    client: &IggyClient,
+    config: &IggyProducerConfig,
+    batching: bool,
+) -> Result<(IggyProducer, Option<Arc<KeyPartitioner>>), IggyError> {
     info!("Extract config fields.");
     let stream = config.stream_name();
     let topic = config.topic_name();
@@ -37,10 +92,13 @@ pub(crate) async fn build_iggy_producer(
     // let encryptor = config.encryptor().to_owned().unwrap();
 
     info!("Build iggy producer");
-    let mut producer = client
-        .producer(stream, topic)?
-        .batch_size(batch_size)
-        .send_interval(send_interval)
+    let builder = client.producer(stream, topic)?;
+    let builder = if batching {
+        builder.batch_size(batch_size).send_interval(send_interval)
+    } else {
+        builder.without_batch_size().without_send_interval()
+    };
+    let mut builder = builder
         .partitioning(partitioning)
         .create_stream_if_not_exists()
         .create_topic_if_not_exists(
@@ -48,8 +106,17 @@ pub(crate) async fn build_iggy_producer(
             replication_factor,
             IggyExpiry::ServerDefault,
             MaxTopicSize::ServerDefault,
-        )
-        .build();
+        );
+
+    let key_partitioner = config.partition_key().map(|extractor| {
+        info!("Install key partitioner");
+        Arc::new(KeyPartitioner::new(extractor.clone(), partitions_count))
+    });
+    if let Some(partitioner) = &key_partitioner {
+        builder = builder.partitioner(partitioner.clone());
+    }
+
+    let mut producer = builder.build();
 
     info!("Initialize iggy producer");
     match producer.init().await {
@@ -60,5 +127,15 @@ pub(crate) async fn build_iggy_producer(
         }
     };
 
-    Ok(producer)
+    if let Some(partitioner) = &key_partitioner {
+        info!("Read partitions count of topic: {topic}");
+        if let Some(details) = client
+            .get_topic(config.stream_id(), config.topic_id())
+            .await?
+        {
+            partitioner.set_partitions_count(details.partitions_count);
+        }
+    }
+
+    Ok((producer, key_partitioner))
 }
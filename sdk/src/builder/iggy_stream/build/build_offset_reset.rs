@@ -0,0 +1,85 @@
+use crate::builder::consumer_seek::seek_consumer;
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{IggyConsumerConfig, OffsetResetPolicy, SeekTarget};
+use iggy::client::ConsumerOffsetClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use tracing::{error, info};
+
+/// Applies the offset reset policy of the `IggyConsumerConfig` to every partition
+/// for which the consumer has no stored offset.
+///
+/// # Arguments
+///
+/// * `client` - The `IggyClient` to use.
+/// * `config` - The `IggyConsumerConfig` to use.
+///
+/// # Errors
+///
+/// * `IggyError` - If the offsets cannot be fetched or stored, or if the policy is
+///   `OffsetResetPolicy::Fail` and a partition has no stored offset.
+///
+pub(crate) async fn apply_offset_reset_policy(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+) -> Result<(), IggyError> {
+    let Some(policy) = config.offset_reset() else {
+        return Ok(());
+    };
+
+    let stream_id = config.stream_id();
+    let topic_id = config.topic_id();
+    let consumer = config.consumer()?;
+    let topic = get_topic_details(client, config).await?;
+
+    for partition in &topic.partitions {
+        let stored_offset = client
+            .get_consumer_offset(&consumer, stream_id, topic_id, Some(partition.id))
+            .await?;
+        if stored_offset.is_some() {
+            continue;
+        }
+
+        info!(
+            "No stored offset for consumer: {} in partition: {}, applying {policy:?}",
+            config.consumer_name(),
+            partition.id
+        );
+        match policy {
+            // Without a stored offset, polling the next message starts at the first one.
+            OffsetResetPolicy::Earliest => {}
+            OffsetResetPolicy::Latest => {
+                if partition.messages_count > 0 {
+                    client
+                        .store_consumer_offset(
+                            &consumer,
+                            stream_id,
+                            topic_id,
+                            Some(partition.id),
+                            partition.current_offset,
+                        )
+                        .await?;
+                }
+            }
+            OffsetResetPolicy::Timestamp(timestamp) => {
+                seek_consumer(
+                    client,
+                    config,
+                    SeekTarget::Timestamp(timestamp),
+                    Some(partition.id),
+                )
+                .await?;
+            }
+            OffsetResetPolicy::Fail => {
+                error!(
+                    "No stored offset for consumer: {} in partition: {}",
+                    config.consumer_name(),
+                    partition.id
+                );
+                return Err(IggyError::ConsumerOffsetNotFound(partition.id));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 pub(super) mod build_iggy_client;
 pub(super) mod build_iggy_consumer;
 pub(super) mod build_iggy_producer;
+pub(super) mod build_offset_reset;
 pub(super) mod build_stream_topic;
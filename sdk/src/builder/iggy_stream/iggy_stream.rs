@@ -1,12 +1,14 @@
 use crate::builder::iggy_stream::build::{
     build_iggy_client, build_iggy_consumer, build_iggy_producer,
 };
-use crate::builder::IggyStreamConfig;
+use crate::builder::teardown;
+use crate::builder::{IggyStreamConfig, IggyStreamConsumer, TeardownGuard};
 use iggy::client::SystemClient;
 use iggy::clients::client::IggyClient;
 use iggy::clients::consumer::IggyConsumer;
 use iggy::clients::producer::IggyProducer;
 use iggy::error::IggyError;
+use std::sync::Arc;
 use tracing::info;
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -15,6 +17,8 @@ pub struct IggyStream {}
 impl IggyStream {
     /// Build and connect iggy client, producer and consumer
     ///
+    /// The returned `IggyProducer` implements `EventProducer`.
+    ///
     /// # Arguments
     ///
     /// * `client` - reference to the iggy client
@@ -24,6 +28,7 @@ impl IggyStream {
     ///
     /// If the builds fails, an `IggyError` is returned.
     ///
+    #[allow(clippy::new_ret_no_self)]
     pub async fn new(
         client: &IggyClient,
         config: &IggyStreamConfig,
@@ -81,4 +86,52 @@ impl IggyStream {
         let (iggy_producer, iggy_consumer) = Self::new(&client, config).await?;
         Ok((client, iggy_producer, iggy_consumer))
     }
+
+    /// Deletes the stream of the config with all of its topics and messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_stream(
+        client: &IggyClient,
+        config: &IggyStreamConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_stream(client, config.stream_id()).await
+    }
+
+    /// Deletes the topic of the config with all of its messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_topic(
+        client: &IggyClient,
+        config: &IggyStreamConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Deletes all messages of the topic of the config and keeps the topic.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn purge_topic(
+        client: &IggyClient,
+        config: &IggyStreamConfig,
+    ) -> Result<(), IggyError> {
+        teardown::purge_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Deletes the consumer group of the config and its stored offsets.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_consumer_group(
+        client: &IggyClient,
+        config: &IggyStreamConfig,
+    ) -> Result<(), IggyError> {
+        IggyStreamConsumer::delete_consumer_group(client, config.consumer_config()).await
+    }
+
+    /// Returns a `TeardownGuard` that deletes the stream of the config when dropped.
+    pub fn teardown_guard(
+        client: Arc<IggyClient>,
+        config: &IggyStreamConfig,
+    ) -> Result<TeardownGuard, IggyError> {
+        TeardownGuard::new(client, config.stream_id().to_owned())
+    }
 }
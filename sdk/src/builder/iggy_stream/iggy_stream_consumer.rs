@@ -1,12 +1,21 @@
+use crate::builder::consumer_lag::{fetch_consumer_lag, spawn_lag_reporter};
+use crate::builder::consumer_seek::{replay_messages, seek_consumer};
 use crate::builder::iggy_stream::build::build_iggy_client::build_iggy_client;
 use crate::builder::iggy_stream::build::build_iggy_consumer;
 use crate::builder::iggy_stream::build::build_iggy_consumer::build_iggy_consumer;
 use crate::builder::iggy_stream::build::build_stream_topic::build_iggy_stream_topic_if_not_exists;
-use crate::builder::IggyConsumerConfig;
+use crate::builder::teardown;
+use crate::builder::{
+    ConsumerLag, EventConsumer, IggyConsumerConfig, ReplayRange, SeekTarget, TeardownGuard,
+};
 use iggy::client::SystemClient;
 use iggy::clients::client::IggyClient;
 use iggy::clients::consumer::IggyConsumer;
+use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use std::sync::Arc;
+use tokio::sync::{oneshot, watch};
 use tracing::info;
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -24,6 +33,7 @@ impl IggyStreamConsumer {
     ///
     /// If the builds fails, an `IggyError` is returned.
     ///
+    #[allow(clippy::new_ret_no_self)]
     pub async fn new(
         client: &IggyClient,
         config: &IggyConsumerConfig,
@@ -81,4 +91,167 @@ impl IggyStreamConsumer {
 
         Ok((client, iggy_consumer))
     }
+
+    /// Returns the lag of the configured consumer for each partition of its topic.
+    ///
+    /// The lag of a partition is the offset of its latest message minus the offset
+    /// committed by the consumer.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: the `IggyClient` used to fetch the topic and offset metadata.
+    /// * `config`: the `IggyConsumerConfig` of the consumer.
+    ///
+    /// # Errors
+    ///
+    /// If the topic or the consumer offsets cannot be fetched, an `IggyError` is returned.
+    ///
+    pub async fn lag(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<ConsumerLag, IggyError> {
+        fetch_consumer_lag(client, config).await
+    }
+
+    /// Starts a background task that publishes the consumer lag at the given interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: the shared `IggyClient` used to fetch the topic and offset metadata.
+    /// * `config`: the `IggyConsumerConfig` of the consumer.
+    /// * `interval`: the interval between two lag updates.
+    /// * `shutdown_rx`: stops the background task when a value is received.
+    ///
+    /// Returns:
+    /// A watch receiver that always holds the most recently fetched lag.
+    ///
+    pub fn lag_reporter(
+        client: Arc<IggyClient>,
+        config: &IggyConsumerConfig,
+        interval: IggyDuration,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> watch::Receiver<ConsumerLag> {
+        spawn_lag_reporter(client, config.to_owned(), interval, shutdown_rx)
+    }
+
+    /// Moves the configured consumer to the given position, so that the next message
+    /// it receives is the one at the seek target.
+    ///
+    /// The offsets are stored on the server, so the seek applies to every member of a
+    /// consumer group. Running consumers may still deliver messages they have already
+    /// polled, so stop the consumer before seeking and start it again afterwards.
+    ///
+    /// A seek to the first message deletes the stored offset, so the offset reset policy
+    /// applies again. To start there anyway, build the consumer from a config with
+    /// `with_polling_strategy`, e.g. `PollingStrategy::next()` for all partitions.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: the `IggyClient` used to store the offsets.
+    /// * `config`: the `IggyConsumerConfig` of the consumer to move.
+    /// * `target`: the position to move to.
+    /// * `partition_id`: the partition to seek, or `None` to seek all partitions.
+    ///
+    /// # Errors
+    ///
+    /// If the topic does not exist or the offsets cannot be stored, an `IggyError` is returned.
+    ///
+    pub async fn seek(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+        target: SeekTarget,
+        partition_id: Option<u32>,
+    ) -> Result<(), IggyError> {
+        seek_consumer(client, config, target, partition_id).await
+    }
+
+    /// Processes the messages in the given range with the event processor and stops
+    /// once the end of the range, or the latest message, is reached.
+    ///
+    /// The replay does not store any offsets, so the configured consumer keeps its position.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: the `IggyClient` used to poll the messages.
+    /// * `config`: the `IggyConsumerConfig` of the consumer.
+    /// * `range`: the range of messages to replay.
+    /// * `partition_id`: the partition to replay, or `None` to replay all partitions.
+    /// * `event_processor`: the `EventConsumer` that handles each message.
+    ///
+    /// Returns:
+    /// The number of replayed messages.
+    ///
+    /// # Errors
+    ///
+    /// If the topic does not exist or the messages cannot be polled, an `IggyError` is returned.
+    ///
+    pub async fn replay<P>(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+        range: ReplayRange,
+        partition_id: Option<u32>,
+        event_processor: &P,
+    ) -> Result<u64, IggyError>
+    where
+        P: EventConsumer + Sync,
+    {
+        replay_messages(client, config, range, partition_id, event_processor).await
+    }
+
+    /// Deletes the stream of the config with all of its topics and messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_stream(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_stream(client, config.stream_id()).await
+    }
+
+    /// Deletes the topic of the config with all of its messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_topic(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Deletes all messages of the topic of the config and keeps the topic.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn purge_topic(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::purge_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Deletes the consumer group of the config and its stored offsets.
+    ///
+    /// Does nothing for a standalone consumer. See `TeardownGuard` for the teardown errors.
+    pub async fn delete_consumer_group(
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<(), IggyError> {
+        if config.consumer_kind() != ConsumerKind::ConsumerGroup {
+            return Ok(());
+        }
+        teardown::delete_consumer_group(
+            client,
+            config.stream_id(),
+            config.topic_id(),
+            config.consumer_name(),
+        )
+        .await
+    }
+
+    /// Returns a `TeardownGuard` that deletes the stream of the config when dropped.
+    pub fn teardown_guard(
+        client: Arc<IggyClient>,
+        config: &IggyConsumerConfig,
+    ) -> Result<TeardownGuard, IggyError> {
+        TeardownGuard::new(client, config.stream_id().to_owned())
+    }
 }
@@ -1,9 +1,15 @@
 use crate::builder::iggy_stream::build::{build_iggy_client, build_iggy_producer};
-use crate::builder::IggyProducerConfig;
+use crate::builder::teardown;
+use crate::builder::{
+    ChunkingEventProducer, CompressingEventProducer, DeliveryReportProducer, IggyProducerConfig,
+    KeyedEventProducer, PayloadCompression, ProducerRetryPolicy, ProducerSpoolConfig,
+    RetryingEventProducer, SpooledEventProducer, TeardownGuard,
+};
 use iggy::client::SystemClient;
 use iggy::clients::client::IggyClient;
 use iggy::clients::producer::IggyProducer;
 use iggy::error::IggyError;
+use std::sync::Arc;
 use tracing::info;
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -13,6 +19,9 @@ impl IggyStreamProducer {
     /// Creates a new `IggyProducer` instance and its associated producer using the `client` and
     /// `config` parameters.
     ///
+    /// The returned `IggyProducer` implements `EventProducer`, so application code can be
+    /// written against the trait and tested with a test double.
+    ///
     /// # Arguments
     ///
     /// * `client`: The Iggy client to use to connect to the Iggy server.
@@ -22,6 +31,7 @@ impl IggyStreamProducer {
     ///
     /// If the client is not connected or the producer cannot be built, an `IggyError` is returned.
     ///
+    #[allow(clippy::new_ret_no_self)]
     pub async fn new(
         client: &IggyClient,
         config: &IggyProducerConfig,
@@ -68,4 +78,207 @@ impl IggyStreamProducer {
 
         Ok((client, iggy_producer))
     }
+
+    /// Creates a new `IggyProducer` like `new` and wraps it in a `SpooledEventProducer`
+    /// that writes messages to a local spool file while the server is unreachable.
+    ///
+    /// Use `SpooledEventProducer::spawn_replay_task` to replay the spool in the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer.
+    /// * `spool_config`: The configuration for the spool.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected, the producer cannot be built, or the spool cannot be
+    /// opened, an `IggyError` is returned.
+    ///
+    pub async fn with_spool(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+        spool_config: &ProducerSpoolConfig,
+    ) -> Result<SpooledEventProducer<IggyProducer>, IggyError> {
+        let iggy_producer = Self::new(client, config).await?;
+
+        info!("Open producer spool");
+        SpooledEventProducer::new(iggy_producer, spool_config)
+    }
+
+    /// Creates a new `IggyProducer` and wraps it in a `DeliveryReportProducer`
+    /// that batches messages with the `batch_size` and `send_interval` of the config and
+    /// reports the delivery of every message.
+    ///
+    /// The inner `IggyProducer` sends each batch immediately, so messages are batched only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected or the producer cannot be built, an `IggyError` is returned.
+    ///
+    pub async fn with_delivery_reports(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+    ) -> Result<DeliveryReportProducer, IggyError> {
+        info!("Check if client is connected");
+        if client.ping().await.is_err() {
+            return Err(IggyError::NotConnected);
+        }
+
+        info!("Build unbatched iggy producer");
+        let iggy_producer =
+            build_iggy_producer::build_unbatched_iggy_producer(client, config).await?;
+
+        info!("Start delivery report producer");
+        Ok(DeliveryReportProducer::new(
+            iggy_producer,
+            config.batch_size(),
+            config.send_interval(),
+        ))
+    }
+
+    /// Creates a new `IggyProducer` like `new` and wraps it in a `RetryingEventProducer`
+    /// that assigns stable message IDs and retries sends that failed with a transient error.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer.
+    /// * `policy`: The retry policy.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected or the producer cannot be built, an `IggyError` is returned.
+    ///
+    pub async fn with_retries(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+        policy: ProducerRetryPolicy,
+    ) -> Result<RetryingEventProducer<IggyProducer>, IggyError> {
+        let iggy_producer = Self::new(client, config).await?;
+
+        Ok(RetryingEventProducer::new(iggy_producer, policy))
+    }
+
+    /// Creates a new `IggyProducer` like `new` and wraps it in a `CompressingEventProducer`
+    /// that compresses payloads of at least `min_size_bytes` with the given algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer.
+    /// * `compression`: The compression algorithm.
+    /// * `min_size_bytes`: Payloads smaller than this are sent uncompressed.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected or the producer cannot be built, an `IggyError` is returned.
+    ///
+    pub async fn with_compression(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+        compression: PayloadCompression,
+        min_size_bytes: usize,
+    ) -> Result<CompressingEventProducer<IggyProducer>, IggyError> {
+        let iggy_producer = Self::new(client, config).await?;
+
+        Ok(CompressingEventProducer::new(
+            iggy_producer,
+            compression,
+            min_size_bytes,
+        ))
+    }
+
+    /// Creates a new `IggyProducer` like `new` and wraps it in a `ChunkingEventProducer`
+    /// that splits payloads larger than `max_chunk_size` into chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer.
+    /// * `max_chunk_size`: The max payload size of a single message in bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected or the producer cannot be built, an `IggyError` is returned.
+    ///
+    pub async fn with_chunking(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+        max_chunk_size: usize,
+    ) -> Result<ChunkingEventProducer<IggyProducer>, IggyError> {
+        let iggy_producer = Self::new(client, config).await?;
+
+        Ok(ChunkingEventProducer::new(iggy_producer, max_chunk_size))
+    }
+
+    /// Creates a new `IggyProducer` like `new` and wraps it in a `KeyedEventProducer`
+    /// that splits every batch into one batch per partition of its keys, so batches with
+    /// a different key per message can be sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The Iggy client to use to connect to the Iggy server.
+    /// * `config`: The configuration for the producer, with a partition key extractor.
+    ///
+    /// # Errors
+    ///
+    /// If the client is not connected, the config has no partition key extractor, or the
+    /// producer cannot be built, an `IggyError` is returned.
+    ///
+    pub async fn with_key_partitioning(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+    ) -> Result<KeyedEventProducer<IggyProducer>, IggyError> {
+        info!("Check if client is connected");
+        if client.ping().await.is_err() {
+            return Err(IggyError::NotConnected);
+        }
+
+        info!("Build keyed iggy producer");
+        build_iggy_producer::build_keyed_iggy_producer(client, config).await
+    }
+
+    /// Deletes the stream of the config with all of its topics and messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_stream(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_stream(client, config.stream_id()).await
+    }
+
+    /// Deletes the topic of the config with all of its messages.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn delete_topic(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::delete_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Deletes all messages of the topic of the config and keeps the topic.
+    ///
+    /// See `TeardownGuard` for the teardown errors.
+    pub async fn purge_topic(
+        client: &IggyClient,
+        config: &IggyProducerConfig,
+    ) -> Result<(), IggyError> {
+        teardown::purge_topic(client, config.stream_id(), config.topic_id()).await
+    }
+
+    /// Returns a `TeardownGuard` that deletes the stream of the config when dropped.
+    pub fn teardown_guard(
+        client: Arc<IggyClient>,
+        config: &IggyProducerConfig,
+    ) -> Result<TeardownGuard, IggyError> {
+        TeardownGuard::new(client, config.stream_id().to_owned())
+    }
 }
@@ -1,11 +1,26 @@
 mod build;
+#[allow(clippy::module_inception)]
 mod iggy_stream;
 mod iggy_stream_consumer;
 mod iggy_stream_producer;
 
+pub use crate::builder::config_aggregate_repository::AggregateRepositoryConfig;
+pub use crate::builder::config_consume_messages::ConsumeMessagesConfig;
 pub use crate::builder::config_iggy_consumer::IggyConsumerConfig;
 pub use crate::builder::config_iggy_producer::IggyProducerConfig;
 pub use crate::builder::config_iggy_stream::IggyStreamConfig;
+pub use crate::builder::config_materialized_table::MaterializedTableConfig;
+pub use crate::builder::config_outbox::OutboxConfig;
+pub use crate::builder::config_producer_spool::ProducerSpoolConfig;
+pub use crate::builder::config_state_store::StateStoreConfig;
+pub use crate::builder::config_stream_join::StreamJoinConfig;
+pub use crate::builder::config_stream_processor::IggyStreamProcessorConfig;
+pub use crate::builder::config_windowed_aggregation::WindowedAggregationConfig;
+pub use crate::builder::join_kind::JoinKind;
+pub use crate::builder::offset_reset_policy::OffsetResetPolicy;
+pub use crate::builder::producer_retry_policy::ProducerRetryPolicy;
+pub use crate::builder::spool_overflow_policy::SpoolOverflowPolicy;
+pub use crate::builder::window_kind::WindowKind;
 pub use iggy_stream::IggyStream;
 pub use iggy_stream_consumer::IggyStreamConsumer;
 pub use iggy_stream_producer::IggyStreamProducer;
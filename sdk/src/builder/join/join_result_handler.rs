@@ -0,0 +1,68 @@
+use crate::builder::{EventConsumerError, EventProducer, JoinedPair};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::fmt;
+
+/// Trait for handling the pairs of a stream-stream join.
+#[allow(dead_code)] // Clippy can't see that the trait is used
+#[trait_variant::make(JoinResultHandler: Send)]
+pub trait LocalJoinResultHandler {
+    /// Handle a joined pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `pair` - The key, the left message and the matching right message, if any
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the pair cannot be handled
+    async fn handle(&self, pair: JoinedPair) -> Result<(), EventConsumerError>;
+}
+
+/// Encodes every joined pair into a message and sends it to an `EventProducer`,
+/// e.g. the `IggyProducer` of an output topic.
+pub struct ProducerJoinHandler<P, F> {
+    producer: P,
+    encode: F,
+}
+
+impl<P: fmt::Debug, F> fmt::Debug for ProducerJoinHandler<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProducerJoinHandler")
+            .field("producer", &self.producer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, F> ProducerJoinHandler<P, F> {
+    /// Creates a new `ProducerJoinHandler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` of the output topic.
+    /// * `encode` - Encodes a joined pair into a message.
+    ///
+    pub fn new(producer: P, encode: F) -> Self {
+        Self { producer, encode }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+}
+
+impl<P, F> JoinResultHandler for ProducerJoinHandler<P, F>
+where
+    P: EventProducer + Sync,
+    F: Fn(&JoinedPair) -> Result<Message, IggyError> + Send + Sync,
+{
+    async fn handle(&self, pair: JoinedPair) -> Result<(), EventConsumerError> {
+        let message =
+            (self.encode)(&pair).map_err(|err| EventConsumerError::new(err.to_string()))?;
+        self.producer
+            .send_one_event(message)
+            .await
+            .map_err(|err| EventConsumerError::new(err.to_string()))
+    }
+}
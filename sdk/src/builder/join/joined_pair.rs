@@ -0,0 +1,30 @@
+use crate::builder::iggy_consumer_ext::clone_polled_message;
+use iggy::models::messages::PolledMessage;
+
+/// The input topic of a message in a stream-stream join.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum JoinSide {
+    Left,
+    Right,
+}
+
+/// A left message and the right message it was joined with.
+#[derive(Debug)]
+pub struct JoinedPair {
+    /// The join key of both messages.
+    pub key: String,
+    /// The message of the left topic.
+    pub left: PolledMessage,
+    /// The message of the right topic, `None` for an unmatched message of a left-outer join.
+    pub right: Option<PolledMessage>,
+}
+
+impl Clone for JoinedPair {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            left: clone_polled_message(&self.left),
+            right: self.right.as_ref().map(clone_polled_message),
+        }
+    }
+}
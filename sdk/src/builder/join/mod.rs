@@ -0,0 +1,9 @@
+mod join_result_handler;
+mod joined_pair;
+mod stream_join;
+mod stream_join_consumer;
+
+pub use join_result_handler::{JoinResultHandler, ProducerJoinHandler};
+pub use joined_pair::{JoinSide, JoinedPair};
+pub use stream_join::StreamJoin;
+pub use stream_join_consumer::{JoinSideConsumer, StreamJoinConsumer};
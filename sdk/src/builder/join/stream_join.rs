@@ -0,0 +1,319 @@
+use crate::builder::iggy_consumer_ext::clone_polled_message;
+use crate::builder::{
+    EventTimeExtractor, JoinKind, JoinSide, JoinedPair, StreamJoinConfig, WindowKeyExtractor,
+};
+use iggy::models::messages::PolledMessage;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+struct Buffered {
+    event_time: u64,
+    message: PolledMessage,
+    matched: bool,
+}
+
+type JoinBuffer = HashMap<String, Vec<Buffered>>;
+
+/// Joins the messages of two topics by key within a window of event time.
+///
+/// A left and a right message are joined when they have the same key and their event
+/// times are at most `window` apart. Both sides are buffered by key, and every new message
+/// is joined with all buffered messages of the other side, so a message can be part of
+/// several pairs.
+///
+/// Each side has its own watermark, the highest event time seen on that side. The join
+/// watermark is the lower of both, so a side that lags behind does not lose its matches;
+/// until both sides have seen a message, nothing expires. With an idle timeout, a side that
+/// received no message for that long in processing time stops holding back the join
+/// watermark, so an idle topic does not keep the buffers growing. Messages more than `window`
+/// behind the join watermark can no longer be joined; they are removed from the buffers, and
+/// in a left-outer join every removed left message without a match is emitted on its own.
+/// Messages that arrive already that far behind are counted as late: a late right message is
+/// dropped, and a late left message is dropped in an inner join and emitted on its own in a
+/// left-outer join.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut join = StreamJoin::new(StreamJoinConfig::left_outer(window), order_id, order_id, HeaderEventTime);
+/// let pairs = join.push(JoinSide::Left, &order_created);
+/// let pairs = join.push(JoinSide::Right, &order_confirmed);
+/// ```
+pub struct StreamJoin {
+    window: u64,
+    kind: JoinKind,
+    left_key: Box<dyn WindowKeyExtractor>,
+    right_key: Box<dyn WindowKeyExtractor>,
+    time_extractor: Box<dyn EventTimeExtractor>,
+    left: JoinBuffer,
+    right: JoinBuffer,
+    left_watermark: Option<u64>,
+    right_watermark: Option<u64>,
+    idle_timeout: Option<Duration>,
+    left_seen: Instant,
+    right_seen: Instant,
+    last_offsets: HashMap<(JoinSide, u32), u64>,
+    late_messages: u64,
+    skipped_messages: u64,
+}
+
+impl fmt::Debug for StreamJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamJoin")
+            .field("window", &self.window)
+            .field("kind", &self.kind)
+            .field("buffered", &self.buffered())
+            .field("left_watermark", &self.left_watermark)
+            .field("right_watermark", &self.right_watermark)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("late_messages", &self.late_messages)
+            .field("skipped_messages", &self.skipped_messages)
+            .finish()
+    }
+}
+
+impl StreamJoin {
+    /// Creates a new `StreamJoin`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The join window and kind.
+    /// * `left_key` - Derives the join key of a left message.
+    /// * `right_key` - Derives the join key of a right message.
+    /// * `time_extractor` - Derives the event time of a message of either side.
+    ///
+    pub fn new(
+        config: StreamJoinConfig,
+        left_key: impl WindowKeyExtractor + 'static,
+        right_key: impl WindowKeyExtractor + 'static,
+        time_extractor: impl EventTimeExtractor + 'static,
+    ) -> Self {
+        Self {
+            window: config.window().as_micros(),
+            kind: config.kind(),
+            left_key: Box::new(left_key),
+            right_key: Box::new(right_key),
+            time_extractor: Box::new(time_extractor),
+            left: HashMap::new(),
+            right: HashMap::new(),
+            left_watermark: None,
+            right_watermark: None,
+            idle_timeout: config.idle_timeout().map(|timeout| timeout.get_duration()),
+            left_seen: Instant::now(),
+            right_seen: Instant::now(),
+            last_offsets: HashMap::new(),
+            late_messages: 0,
+            skipped_messages: 0,
+        }
+    }
+
+    /// Returns the number of dropped late messages.
+    pub fn late_messages(&self) -> u64 {
+        self.late_messages
+    }
+
+    /// Returns the number of messages skipped for a missing key or event time.
+    pub fn skipped_messages(&self) -> u64 {
+        self.skipped_messages
+    }
+
+    /// Returns the idle timeout of a side, if any.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Returns the join watermark, the lower of the watermarks of both sides.
+    /// The watermark of an idle side is ignored, unless both sides are idle.
+    ///
+    /// Returns `None` until both sides have seen a message or one side is idle.
+    pub fn watermark(&self) -> Option<u64> {
+        let now = Instant::now();
+        let is_idle = |seen: Instant| {
+            self.idle_timeout
+                .is_some_and(|timeout| now.duration_since(seen) >= timeout)
+        };
+        match (is_idle(self.left_seen), is_idle(self.right_seen)) {
+            (false, false) => Some(self.left_watermark?.min(self.right_watermark?)),
+            (false, true) => self.left_watermark,
+            (true, false) => self.right_watermark,
+            (true, true) => self.left_watermark.max(self.right_watermark),
+        }
+    }
+
+    /// Returns the number of buffered messages of both sides.
+    pub fn buffered(&self) -> usize {
+        self.left
+            .values()
+            .chain(self.right.values())
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Joins a message with the buffered messages of the other side and buffers it.
+    ///
+    /// Returns:
+    /// The new pairs, followed by the unmatched left messages that expired,
+    /// if this is a left-outer join. A late left message of a left-outer join is returned
+    /// unmatched right away.
+    ///
+    pub fn push(&mut self, side: JoinSide, message: &PolledMessage) -> Vec<JoinedPair> {
+        match side {
+            JoinSide::Left => self.left_seen = Instant::now(),
+            JoinSide::Right => self.right_seen = Instant::now(),
+        }
+        let key = match side {
+            JoinSide::Left => self.left_key.window_key(message),
+            JoinSide::Right => self.right_key.window_key(message),
+        };
+        let (Some(key), Some(event_time)) = (key, self.time_extractor.event_time(message)) else {
+            debug!(
+                "Skipping {side:?} message without key or event time at offset: {}",
+                message.offset
+            );
+            self.skipped_messages += 1;
+            return Vec::new();
+        };
+
+        let event_time = event_time.as_micros();
+        let side_watermark = match side {
+            JoinSide::Left => &mut self.left_watermark,
+            JoinSide::Right => &mut self.right_watermark,
+        };
+        *side_watermark = Some(side_watermark.map_or(event_time, |w| w.max(event_time)));
+
+        let window = self.window;
+        let watermark = self.watermark();
+        let is_expired =
+            move |event_time: u64| watermark.is_some_and(|w| event_time.saturating_add(window) < w);
+        let mut pairs = Vec::new();
+        if is_expired(event_time) {
+            debug!(
+                "Late {side:?} message at offset: {} behind watermark: {watermark:?}",
+                message.offset
+            );
+            self.late_messages += 1;
+            if side == JoinSide::Left && self.kind == JoinKind::LeftOuter {
+                pairs.push(JoinedPair {
+                    key,
+                    left: clone_polled_message(message),
+                    right: None,
+                });
+            }
+        } else {
+            let (own, other) = match side {
+                JoinSide::Left => (&mut self.left, &mut self.right),
+                JoinSide::Right => (&mut self.right, &mut self.left),
+            };
+
+            let mut matched = false;
+            for buffered in other.get_mut(&key).into_iter().flatten() {
+                if buffered.event_time.abs_diff(event_time) > window {
+                    continue;
+                }
+                matched = true;
+                buffered.matched = true;
+                let (left, right) = match side {
+                    JoinSide::Left => (message, &buffered.message),
+                    JoinSide::Right => (&buffered.message, message),
+                };
+                pairs.push(JoinedPair {
+                    key: key.clone(),
+                    left: clone_polled_message(left),
+                    right: Some(clone_polled_message(right)),
+                });
+            }
+
+            own.entry(key).or_default().push(Buffered {
+                event_time,
+                message: clone_polled_message(message),
+                matched,
+            });
+        }
+
+        pairs.extend(self.expire(is_expired));
+        pairs
+    }
+
+    /// Joins a message of the given partition like `push`, unless its offset is at or before
+    /// the last offset pushed from that partition of the side, e.g. because it is delivered
+    /// again after its pairs could not be handled.
+    pub fn push_from(
+        &mut self,
+        side: JoinSide,
+        partition_id: u32,
+        message: &PolledMessage,
+    ) -> Vec<JoinedPair> {
+        if let Some(&last_offset) = self.last_offsets.get(&(side, partition_id)) {
+            if message.offset <= last_offset {
+                debug!(
+                    "Skipping already joined {side:?} offset: {} in partition: {partition_id}",
+                    message.offset
+                );
+                return Vec::new();
+            }
+        }
+
+        self.last_offsets
+            .insert((side, partition_id), message.offset);
+        self.push(side, message)
+    }
+
+    /// Removes the messages that are too far behind the join watermark, e.g. because a side
+    /// became idle since the last message.
+    ///
+    /// Returns:
+    /// The unmatched left messages that expired, if this is a left-outer join.
+    ///
+    pub fn expire_idle(&mut self) -> Vec<JoinedPair> {
+        let window = self.window;
+        let Some(watermark) = self.watermark() else {
+            return Vec::new();
+        };
+        self.expire(|event_time| event_time.saturating_add(window) < watermark)
+    }
+
+    /// Removes all buffered messages, e.g. on shutdown.
+    ///
+    /// Returns:
+    /// The unmatched left messages, if this is a left-outer join.
+    ///
+    pub fn flush(&mut self) -> Vec<JoinedPair> {
+        self.expire(|_| true)
+    }
+
+    fn expire(&mut self, is_expired: impl Fn(u64) -> bool) -> Vec<JoinedPair> {
+        for buffered in self.right.values_mut() {
+            buffered.retain(|message| !is_expired(message.event_time));
+        }
+        self.right.retain(|_, buffered| !buffered.is_empty());
+
+        let mut unmatched = Vec::new();
+        for (key, buffered) in self.left.iter_mut() {
+            let (expired, kept): (Vec<_>, Vec<_>) = buffered
+                .drain(..)
+                .partition(|message| is_expired(message.event_time));
+            *buffered = kept;
+
+            if self.kind == JoinKind::LeftOuter {
+                unmatched.extend(expired.into_iter().filter(|message| !message.matched).map(
+                    |message| {
+                        (
+                            message.event_time,
+                            JoinedPair {
+                                key: key.clone(),
+                                left: message.message,
+                                right: None,
+                            },
+                        )
+                    },
+                ));
+            }
+        }
+        self.left.retain(|_, buffered| !buffered.is_empty());
+
+        unmatched.sort_by_key(|(event_time, _)| *event_time);
+        unmatched.into_iter().map(|(_, pair)| pair).collect()
+    }
+}
@@ -0,0 +1,232 @@
+use crate::builder::pending_results::PendingResults;
+use crate::builder::{
+    decompress_polled_message, is_fatal_client_error, EventConsumer, EventConsumerError,
+    JoinResultHandler, JoinSide, JoinedPair, StreamJoin,
+};
+use futures_util::StreamExt;
+use iggy::clients::consumer::IggyConsumer;
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+/// Runs a `StreamJoin` on the messages of two consumers and passes the joined pairs
+/// to a `JoinResultHandler`.
+///
+/// Use `run` to read both consumers in a single task, or `left` and `right` to get
+/// an `EventConsumer` for each topic, e.g. for two `consume_messages` loops.
+///
+/// Pairs stay pending until the handler succeeded, so a failed handler does not lose them:
+/// the next joined message, usually the retry of the failed one, handles them first. A
+/// retried message is not joined again, and `run` skips every offset that was already joined.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let join = Arc::new(StreamJoinConsumer::new(stream_join, handler));
+/// join.run(created_consumer, confirmed_consumer, shutdown_rx).await?;
+/// ```
+#[derive(Debug)]
+pub struct StreamJoinConsumer<H> {
+    join: Mutex<StreamJoin>,
+    pending: tokio::sync::Mutex<PendingResults<JoinedPair, (JoinSide, u64, u128)>>,
+    handler: H,
+}
+
+impl<H> StreamJoinConsumer<H>
+where
+    H: JoinResultHandler + Sync,
+{
+    /// Creates a new `StreamJoinConsumer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `join` - The join window, kind and key extractors.
+    /// * `handler` - Handles the joined pairs.
+    ///
+    pub fn new(join: StreamJoin, handler: H) -> Self {
+        Self {
+            join: Mutex::new(join),
+            pending: tokio::sync::Mutex::new(PendingResults::default()),
+            handler,
+        }
+    }
+
+    /// Returns the pair handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns an `EventConsumer` for the messages of the left topic.
+    pub fn left(self: &Arc<Self>) -> JoinSideConsumer<H> {
+        JoinSideConsumer {
+            join: self.clone(),
+            side: JoinSide::Left,
+        }
+    }
+
+    /// Returns an `EventConsumer` for the messages of the right topic.
+    pub fn right(self: &Arc<Self>) -> JoinSideConsumer<H> {
+        JoinSideConsumer {
+            join: self.clone(),
+            side: JoinSide::Right,
+        }
+    }
+
+    /// Joins a message of the given side and handles the pending pairs.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a pair cannot be handled. It stays pending.
+    ///
+    pub async fn push(
+        &self,
+        side: JoinSide,
+        message: PolledMessage,
+    ) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let key = (side, message.offset, message.id);
+        if !pending.contains(&key) {
+            let pairs = self.join.lock().unwrap().push(side, &message);
+            pending.add(key, pairs);
+        }
+        pending.handle(|pair| self.handler.handle(pair)).await
+    }
+
+    /// Joins a message of the given side and partition and handles the pending pairs.
+    /// Messages at or before the last joined offset of the partition are not joined again.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a pair cannot be handled. It stays pending.
+    ///
+    pub async fn process(
+        &self,
+        side: JoinSide,
+        partition_id: u32,
+        message: PolledMessage,
+    ) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let pairs = self
+            .join
+            .lock()
+            .unwrap()
+            .push_from(side, partition_id, &message);
+        pending.add((side, message.offset, message.id), pairs);
+        pending.handle(|pair| self.handler.handle(pair)).await
+    }
+
+    /// Removes the messages that expired because a side became idle and handles the
+    /// pending pairs.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a pair cannot be handled. It stays pending.
+    ///
+    pub async fn expire_idle(&self) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let pairs = self.join.lock().unwrap().expire_idle();
+        pending.extend(pairs);
+        pending.handle(|pair| self.handler.handle(pair)).await
+    }
+
+    /// Removes all buffered messages and handles the unmatched left messages of
+    /// a left-outer join, e.g. on shutdown.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a pair cannot be handled. It stays pending.
+    ///
+    pub async fn flush(&self) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let pairs = self.join.lock().unwrap().flush();
+        pending.extend(pairs);
+        pending.handle(|pair| self.handler.handle(pair)).await
+    }
+
+    /// Reads both consumers until a shutdown signal is received or one consumer ends.
+    ///
+    /// Compressed payloads are decompressed before they are joined. With an idle timeout,
+    /// the buffers are also expired at that interval, so they expire while both topics are idle.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The consumer of the left topic.
+    /// * `right` - The consumer of the right topic.
+    /// * `shutdown_rx` - Stops the join when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the client disconnected.
+    ///
+    pub async fn run(
+        &self,
+        mut left: IggyConsumer,
+        mut right: IggyConsumer,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        let idle_timeout = self.join.lock().unwrap().idle_timeout();
+        let mut idle_timer =
+            tokio::time::interval(idle_timeout.unwrap_or(Duration::from_secs(3600)));
+        loop {
+            let (side, message) = tokio::select! {
+                _ = &mut shutdown_rx => {
+                    info!("Received shutdown signal, stopping stream join");
+                    break;
+                }
+
+                _ = idle_timer.tick(), if idle_timeout.is_some() => {
+                    if let Err(err) = self.expire_idle().await {
+                        error!("Error while handling joined pair: {err}");
+                    }
+                    continue;
+                }
+
+                message = left.next() => (JoinSide::Left, message),
+                message = right.next() => (JoinSide::Right, message),
+            };
+
+            match message {
+                Some(Ok(received_message)) => {
+                    let partition_id = received_message.partition_id;
+                    let message = match decompress_polled_message(received_message.message) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            error!("Error while decompressing {side:?} message in partition: {partition_id}: {err}");
+                            continue;
+                        }
+                    };
+                    if let Err(err) = self.process(side, partition_id, message).await {
+                        error!("Error while handling joined pair: {err}");
+                    }
+                }
+                Some(Err(err)) if is_fatal_client_error(&err) => {
+                    error!("{err:?}: shutdown stream join: {err}");
+                    return Err(err);
+                }
+                Some(Err(err)) => error!("Error while receiving {side:?} message: {err}"),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `EventConsumer` for one side of a `StreamJoinConsumer`.
+#[derive(Debug)]
+pub struct JoinSideConsumer<H> {
+    join: Arc<StreamJoinConsumer<H>>,
+    side: JoinSide,
+}
+
+impl<H> EventConsumer for JoinSideConsumer<H>
+where
+    H: JoinResultHandler + Sync + Send,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        self.join.push(self.side, message).await
+    }
+}
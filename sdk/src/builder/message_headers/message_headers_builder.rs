@@ -0,0 +1,181 @@
+use crate::builder::{
+    AGGREGATE_BATCH_ID_HEADER, AGGREGATE_EXPECTED_VERSION_HEADER, AGGREGATE_ID_HEADER,
+    AGGREGATE_VERSION_HEADER, CAUSATION_ID_HEADER, CONTENT_ENCODING_HEADER, CONTENT_TYPE_HEADER,
+    CORRELATION_ID_HEADER, CREATED_AT_HEADER, MESSAGE_TYPE_HEADER, PARTITION_KEY_HEADER,
+    REPLY_TO_HEADER,
+};
+use bytes::Bytes;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Builder for the headers of an outgoing message.
+///
+/// Invalid keys or values are reported once by `build` or `message`, so calls can be chained.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let message = MessageHeaders::new()
+///     .content_type("application/json")
+///     .correlation_id(&request_id)
+///     .message_type("order_created")
+///     .created_at(IggyTimestamp::now())
+///     .message(payload)?;
+/// producer.send_one_event(message).await?;
+/// ```
+#[derive(Debug, Default)]
+pub struct MessageHeaders {
+    headers: HashMap<HeaderKey, HeaderValue>,
+    error: Option<IggyError>,
+}
+
+impl MessageHeaders {
+    /// Creates an empty set of headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `content-type` header.
+    pub fn content_type(self, content_type: &str) -> Self {
+        self.insert_str(CONTENT_TYPE_HEADER, content_type)
+    }
+
+    /// Sets the `content-encoding` header.
+    pub fn content_encoding(self, content_encoding: &str) -> Self {
+        self.insert_str(CONTENT_ENCODING_HEADER, content_encoding)
+    }
+
+    /// Sets the `correlation-id` header.
+    pub fn correlation_id(self, correlation_id: &str) -> Self {
+        self.insert_str(CORRELATION_ID_HEADER, correlation_id)
+    }
+
+    /// Sets the `causation-id` header.
+    pub fn causation_id(self, causation_id: &str) -> Self {
+        self.insert_str(CAUSATION_ID_HEADER, causation_id)
+    }
+
+    /// Sets the `reply-to` header.
+    pub fn reply_to(self, reply_to: &str) -> Self {
+        self.insert_str(REPLY_TO_HEADER, reply_to)
+    }
+
+    /// Sets the `message-type` header.
+    pub fn message_type(self, message_type: &str) -> Self {
+        self.insert_str(MESSAGE_TYPE_HEADER, message_type)
+    }
+
+    /// Sets the `partition-key` header.
+    pub fn partition_key(self, partition_key: &str) -> Self {
+        self.insert_str(PARTITION_KEY_HEADER, partition_key)
+    }
+
+    /// Sets the `created-at` header.
+    pub fn created_at(self, created_at: IggyTimestamp) -> Self {
+        self.insert_with(
+            CREATED_AT_HEADER,
+            HeaderValue::from_uint64(created_at.into()),
+        )
+    }
+
+    /// Sets the `aggregate-id` header.
+    pub fn aggregate_id(self, aggregate_id: &str) -> Self {
+        self.insert_str(AGGREGATE_ID_HEADER, aggregate_id)
+    }
+
+    /// Sets the `aggregate-version` header.
+    pub fn aggregate_version(self, aggregate_version: u64) -> Self {
+        self.insert_with(
+            AGGREGATE_VERSION_HEADER,
+            HeaderValue::from_uint64(aggregate_version),
+        )
+    }
+
+    /// Sets the `aggregate-expected-version` header.
+    pub fn aggregate_expected_version(self, expected_version: u64) -> Self {
+        self.insert_with(
+            AGGREGATE_EXPECTED_VERSION_HEADER,
+            HeaderValue::from_uint64(expected_version),
+        )
+    }
+
+    /// Sets the `aggregate-batch-id` header.
+    pub fn aggregate_batch_id(self, batch_id: u128) -> Self {
+        self.insert_with(
+            AGGREGATE_BATCH_ID_HEADER,
+            HeaderValue::from_uint128(batch_id),
+        )
+    }
+
+    /// Sets a header with a string value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header key.
+    /// * `value` - The string value.
+    ///
+    pub fn insert_str(self, key: &str, value: &str) -> Self {
+        self.insert_with(key, HeaderValue::from_str(value))
+    }
+
+    /// Sets a header with a value of any kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header key.
+    /// * `value` - The header value.
+    ///
+    pub fn insert(self, key: &str, value: HeaderValue) -> Self {
+        self.insert_with(key, Ok(value))
+    }
+
+    /// Adds all headers from an existing header map, e.g. to forward the headers of a
+    /// consumed message.
+    pub fn extend(mut self, headers: &HashMap<HeaderKey, HeaderValue>) -> Self {
+        self.headers
+            .extend(headers.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self
+    }
+
+    /// Returns the headers.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If any of the keys or values was invalid.
+    ///
+    pub fn build(self) -> Result<HashMap<HeaderKey, HeaderValue>, IggyError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.headers),
+        }
+    }
+
+    /// Returns a new message with the given payload and these headers.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If any of the keys or values was invalid.
+    ///
+    pub fn message(self, payload: impl Into<Bytes>) -> Result<Message, IggyError> {
+        let headers = self.build()?;
+        Ok(Message::new(None, payload.into(), Some(headers)))
+    }
+
+    fn insert_with(mut self, key: &str, value: Result<HeaderValue, IggyError>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match HeaderKey::new(key).and_then(|key| value.map(|value| (key, value))) {
+            Ok((key, value)) => {
+                self.headers.insert(key, value);
+            }
+            Err(err) => self.error = Some(err),
+        }
+        self
+    }
+}
@@ -0,0 +1,103 @@
+use crate::builder::{
+    AGGREGATE_BATCH_ID_HEADER, AGGREGATE_EXPECTED_VERSION_HEADER, AGGREGATE_ID_HEADER,
+    AGGREGATE_VERSION_HEADER, CAUSATION_ID_HEADER, CONTENT_ENCODING_HEADER, CONTENT_TYPE_HEADER,
+    CORRELATION_ID_HEADER, CREATED_AT_HEADER, MESSAGE_TYPE_HEADER, PARTITION_KEY_HEADER,
+    REPLY_TO_HEADER,
+};
+use iggy::messages::send_messages::Message;
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::PolledMessage;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+
+/// Typed access to the headers of consumed and outgoing messages.
+pub trait MessageHeadersExt {
+    /// Returns the raw header map, if the message has headers.
+    fn headers_map(&self) -> Option<&HashMap<HeaderKey, HeaderValue>>;
+
+    /// Returns the value of the given header.
+    fn header(&self, key: &str) -> Option<&HeaderValue> {
+        let key = HeaderKey::new(key).ok()?;
+        self.headers_map()?.get(&key)
+    }
+
+    /// Returns the value of the given header if it is a string.
+    fn header_str(&self, key: &str) -> Option<&str> {
+        self.header(key)?.as_str().ok()
+    }
+
+    /// Returns the `content-type` header.
+    fn content_type(&self) -> Option<&str> {
+        self.header_str(CONTENT_TYPE_HEADER)
+    }
+
+    /// Returns the `content-encoding` header.
+    fn content_encoding(&self) -> Option<&str> {
+        self.header_str(CONTENT_ENCODING_HEADER)
+    }
+
+    /// Returns the `correlation-id` header.
+    fn correlation_id(&self) -> Option<&str> {
+        self.header_str(CORRELATION_ID_HEADER)
+    }
+
+    /// Returns the `causation-id` header.
+    fn causation_id(&self) -> Option<&str> {
+        self.header_str(CAUSATION_ID_HEADER)
+    }
+
+    /// Returns the `reply-to` header.
+    fn reply_to(&self) -> Option<&str> {
+        self.header_str(REPLY_TO_HEADER)
+    }
+
+    /// Returns the `message-type` header.
+    fn message_type(&self) -> Option<&str> {
+        self.header_str(MESSAGE_TYPE_HEADER)
+    }
+
+    /// Returns the `partition-key` header.
+    fn partition_key(&self) -> Option<&str> {
+        self.header_str(PARTITION_KEY_HEADER)
+    }
+
+    /// Returns the `created-at` header.
+    fn created_at(&self) -> Option<IggyTimestamp> {
+        let micros = self.header(CREATED_AT_HEADER)?.as_uint64().ok()?;
+        Some(IggyTimestamp::from(micros))
+    }
+
+    /// Returns the `aggregate-id` header.
+    fn aggregate_id(&self) -> Option<&str> {
+        self.header_str(AGGREGATE_ID_HEADER)
+    }
+
+    /// Returns the `aggregate-version` header.
+    fn aggregate_version(&self) -> Option<u64> {
+        self.header(AGGREGATE_VERSION_HEADER)?.as_uint64().ok()
+    }
+
+    /// Returns the `aggregate-expected-version` header.
+    fn aggregate_expected_version(&self) -> Option<u64> {
+        self.header(AGGREGATE_EXPECTED_VERSION_HEADER)?
+            .as_uint64()
+            .ok()
+    }
+
+    /// Returns the `aggregate-batch-id` header.
+    fn aggregate_batch_id(&self) -> Option<u128> {
+        self.header(AGGREGATE_BATCH_ID_HEADER)?.as_uint128().ok()
+    }
+}
+
+impl MessageHeadersExt for PolledMessage {
+    fn headers_map(&self) -> Option<&HashMap<HeaderKey, HeaderValue>> {
+        self.headers.as_ref()
+    }
+}
+
+impl MessageHeadersExt for Message {
+    fn headers_map(&self) -> Option<&HashMap<HeaderKey, HeaderValue>> {
+        self.headers.as_ref()
+    }
+}
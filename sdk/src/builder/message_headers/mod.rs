@@ -0,0 +1,7 @@
+mod message_headers_builder;
+mod message_headers_ext;
+mod well_known_headers;
+
+pub use message_headers_builder::MessageHeaders;
+pub use message_headers_ext::MessageHeadersExt;
+pub use well_known_headers::*;
@@ -0,0 +1,54 @@
+//! Reserved header keys used by the SDK.
+//!
+//! Application headers should not reuse these keys with a different meaning.
+
+/// The content type of the payload, e.g. `application/json`.
+pub const CONTENT_TYPE_HEADER: &str = "content-type";
+
+/// The compression algorithm of the payload, e.g. `zstd`, see `PayloadCompression`.
+pub const CONTENT_ENCODING_HEADER: &str = "content-encoding";
+
+/// The ID shared by all messages that belong to the same request or workflow.
+pub const CORRELATION_ID_HEADER: &str = "correlation-id";
+
+/// The ID of the message that caused this message to be sent.
+pub const CAUSATION_ID_HEADER: &str = "causation-id";
+
+/// The `<stream>/<topic>` an RPC server sends the reply to, see `RpcClient`.
+pub const REPLY_TO_HEADER: &str = "reply-to";
+
+/// Marks an RPC reply as an error; the value is the error message of the handler.
+pub const RPC_ERROR_HEADER: &str = "rpc-error";
+
+/// The type of the event carried in the payload, e.g. `order_created`.
+pub const MESSAGE_TYPE_HEADER: &str = "message-type";
+
+/// The time the message was created by the producer, in microseconds since the Unix epoch.
+pub const CREATED_AT_HEADER: &str = "created-at";
+
+/// The key used to select the partition of the message, see `HeaderPartitionKey`.
+pub const PARTITION_KEY_HEADER: &str = "partition-key";
+
+/// The ID shared by all chunks of a chunked message, see `ChunkingEventProducer`.
+pub const TRANSFER_ID_HEADER: &str = "transfer-id";
+
+/// The zero-based index of a chunk within its transfer.
+pub const CHUNK_INDEX_HEADER: &str = "chunk-index";
+
+/// The number of chunks in the transfer.
+pub const CHUNK_COUNT_HEADER: &str = "chunk-count";
+
+/// The CRC32 checksum of the complete payload of the transfer.
+pub const TRANSFER_CHECKSUM_HEADER: &str = "transfer-checksum";
+
+/// The ID of the event-sourced aggregate the event belongs to, see `AggregateRepository`.
+pub const AGGREGATE_ID_HEADER: &str = "aggregate-id";
+
+/// The version of the aggregate after the event, starting at 1.
+pub const AGGREGATE_VERSION_HEADER: &str = "aggregate-version";
+
+/// The version of the aggregate the batch of the event was appended at.
+pub const AGGREGATE_EXPECTED_VERSION_HEADER: &str = "aggregate-expected-version";
+
+/// The ID shared by all events appended in one batch.
+pub const AGGREGATE_BATCH_ID_HEADER: &str = "aggregate-batch-id";
@@ -1,15 +1,73 @@
+mod chunking;
+mod codec;
+mod compression;
 mod config;
+mod consumer_lag;
+mod consumer_seek;
+mod event_consumer_middleware;
 mod event_consumer_trait;
 mod event_producer_trait;
+mod event_producer_typed;
+mod event_sourcing;
 mod iggy_consumer_ext;
 mod iggy_stream;
+mod join;
+mod message_headers;
+mod outbox;
+mod partition_poll;
+mod partitioning;
+mod pending_results;
+mod producer_delivery;
+mod producer_retry;
+mod producer_spool;
+mod projection;
+mod rpc;
+mod sqlite_tables;
+mod state_store;
+mod stream_processor;
+mod table;
+mod teardown;
+mod topic_details;
+mod topology;
+mod transient_error;
+mod windowing;
 
+pub use crate::builder::chunking::*;
+pub use crate::builder::codec::*;
+pub use crate::builder::compression::*;
+pub use crate::builder::consumer_lag::{ConsumerLag, PartitionLag};
+pub use crate::builder::consumer_seek::{ReplayBound, ReplayRange, SeekTarget};
+pub use crate::builder::event_consumer_middleware::*;
 pub use crate::builder::event_consumer_trait::*;
 pub use crate::builder::event_producer_trait::*;
+pub use crate::builder::event_producer_typed::*;
+pub use crate::builder::event_sourcing::*;
 pub use crate::builder::iggy_consumer_ext::*;
 pub use crate::builder::iggy_stream::*;
+pub use crate::builder::join::*;
+pub use crate::builder::message_headers::*;
+pub use crate::builder::outbox::*;
+pub use crate::builder::partitioning::*;
+pub use crate::builder::producer_delivery::*;
+pub use crate::builder::producer_retry::*;
+pub use crate::builder::producer_spool::*;
+pub use crate::builder::projection::*;
+pub use crate::builder::rpc::*;
+pub use crate::builder::state_store::*;
+pub use crate::builder::stream_processor::*;
+pub use crate::builder::table::*;
+pub use crate::builder::teardown::{is_not_found_error, TeardownGuard};
+pub use crate::builder::topology::*;
+pub use crate::builder::transient_error::{is_fatal_client_error, is_transient_error};
+pub use crate::builder::windowing::*;
 // Re-exports
-pub use config::{config_iggy_consumer, config_iggy_producer, config_iggy_stream};
+pub use config::{
+    config_aggregate_repository, config_consume_messages, config_iggy_consumer,
+    config_iggy_producer, config_iggy_stream, config_materialized_table, config_outbox,
+    config_producer_spool, config_state_store, config_stream_join, config_stream_processor,
+    config_windowed_aggregation, join_kind, offset_reset_policy, producer_retry_policy,
+    spool_overflow_policy, window_kind,
+};
 pub use iggy::clients::client::IggyClient;
 pub use iggy::error::IggyError;
 pub use iggy::messages::send_messages::Message;
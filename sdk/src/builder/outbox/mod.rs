@@ -0,0 +1,5 @@
+mod outbox_relay;
+mod outbox_table;
+
+pub use outbox_relay::OutboxRelay;
+pub use outbox_table::{OutboxRow, OutboxTable};
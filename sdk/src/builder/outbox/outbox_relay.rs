@@ -0,0 +1,117 @@
+use crate::builder::{EventProducer, OutboxRow, OutboxTable};
+use iggy::error::IggyError;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Publishes the rows of an `OutboxTable` through an `EventProducer`.
+///
+/// Rows are sent in row ID order. When a row fails, the remaining rows of the same
+/// aggregate are held back until the next poll, so the order per aggregate is preserved
+/// while other aggregates continue.
+///
+/// The relay uses its own connection, e.g. a second connection to the same database file.
+#[derive(Debug)]
+pub struct OutboxRelay<P> {
+    connection: Mutex<Connection>,
+    outbox: OutboxTable,
+    producer: P,
+}
+
+impl<P> OutboxRelay<P>
+where
+    P: EventProducer + Send + Sync + 'static,
+{
+    /// Creates a new `OutboxRelay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - The connection used to read and update the outbox table.
+    /// * `outbox` - The outbox table.
+    /// * `producer` - The `EventProducer` used to publish the messages.
+    ///
+    pub fn new(connection: Connection, outbox: OutboxTable, producer: P) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+            outbox,
+            producer,
+        }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Publishes one batch of unsent rows.
+    ///
+    /// Returns:
+    /// The number of published rows.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the outbox table cannot be read or updated. Send errors are logged
+    ///   and the row is retried with the next call.
+    ///
+    pub async fn relay_once(&self) -> Result<usize, IggyError> {
+        let rows = self.outbox.unsent(&self.connection.lock().unwrap())?;
+
+        let mut blocked = HashSet::new();
+        let mut sent = 0;
+        for OutboxRow {
+            id,
+            aggregate_id,
+            message,
+        } in rows
+        {
+            if blocked.contains(&aggregate_id) {
+                continue;
+            }
+
+            match self.producer.send_one_event(message).await {
+                Ok(_) => {
+                    self.outbox
+                        .mark_sent(&self.connection.lock().unwrap(), id)?;
+                    sent += 1;
+                }
+                Err(err) => {
+                    warn!("Failed to publish outbox row {id} of aggregate {aggregate_id}: {err}");
+                    blocked.insert(aggregate_id);
+                }
+            }
+        }
+
+        Ok(sent)
+    }
+
+    /// Spawns a task that calls `relay_once` at the configured poll interval until
+    /// a shutdown signal is received.
+    ///
+    /// # Arguments
+    ///
+    /// * `shutdown_rx` - Stops the relay when a value is received.
+    ///
+    pub fn spawn(self, mut shutdown_rx: oneshot::Receiver<()>) -> JoinHandle<()> {
+        let interval = self.outbox.config().poll_interval();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(interval.get_duration());
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => {
+                        info!("Received shutdown signal, stopping outbox relay");
+                        break;
+                    }
+
+                    _ = timer.tick() => {
+                        if let Err(err) = self.relay_once().await {
+                            error!("Failed to relay outbox: {err}");
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use crate::builder::sqlite_tables::{create_tables, validate_table_name};
+use crate::builder::{assign_message_ids, MessageHeaders, OutboxConfig};
+use bytes::Bytes;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::utils::timestamp::IggyTimestamp;
+use rusqlite::{params, Connection};
+use tracing::error;
+
+/// An unsent event read from the outbox table.
+#[derive(Debug)]
+pub struct OutboxRow {
+    pub id: i64,
+    pub aggregate_id: String,
+    pub message: Message,
+}
+
+/// Outbox table in a SQLite database.
+///
+/// Events are written with `enqueue` on the caller's connection or transaction, so they are
+/// committed or rolled back together with the business state. The `OutboxRelay` publishes them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let outbox = OutboxTable::new(OutboxConfig::default())?;
+/// outbox.create_table(&connection)?;
+///
+/// let tx = connection.transaction()?;
+/// tx.execute("UPDATE orders SET status = 'paid' WHERE id = ?1", [order_id])?;
+/// outbox.enqueue(&tx, &order_id, message)?;
+/// tx.commit()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OutboxTable {
+    config: OutboxConfig,
+}
+
+impl OutboxTable {
+    /// Creates a new `OutboxTable`.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the table name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    ///
+    pub fn new(config: OutboxConfig) -> Result<Self, IggyError> {
+        let table_name = config.table_name();
+        validate_table_name("outbox table", table_name)?;
+
+        Ok(Self { config })
+    }
+
+    /// Returns the outbox configuration.
+    pub fn config(&self) -> &OutboxConfig {
+        &self.config
+    }
+
+    /// Creates the outbox table and its index if they do not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the statement fails.
+    ///
+    pub fn create_table(&self, connection: &Connection) -> Result<(), IggyError> {
+        let table = self.config.table_name();
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                aggregate_id TEXT NOT NULL,
+                message BLOB NOT NULL,
+                created_at INTEGER NOT NULL,
+                sent_at INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS {table}_unsent ON {table} (sent_at, id);"
+        );
+
+        create_tables(connection, "outbox table", table, &sql)
+    }
+
+    /// Writes a message into the outbox.
+    ///
+    /// Pass a `rusqlite::Transaction` to commit the message atomically with other changes.
+    /// The message gets a stable ID if it has none, and the aggregate ID is written into the
+    /// `partition-key` header, so with `HeaderPartitionKey` all events of one aggregate
+    /// are sent to the same partition.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - The connection or transaction to write to.
+    /// * `aggregate_id` - The ID of the entity the event belongs to, e.g. the order ID.
+    /// * `message` - The message to publish.
+    ///
+    /// Returns:
+    /// The row ID of the outbox entry.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the header cannot be set or the row cannot be written.
+    ///
+    pub fn enqueue(
+        &self,
+        connection: &Connection,
+        aggregate_id: &str,
+        message: Message,
+    ) -> Result<i64, IggyError> {
+        let mut headers = MessageHeaders::new();
+        if let Some(existing) = &message.headers {
+            headers = headers.extend(existing);
+        }
+        let headers = headers.partition_key(aggregate_id).build()?;
+
+        let mut messages = [Message::new(
+            Some(message.id),
+            message.payload,
+            Some(headers),
+        )];
+        assign_message_ids(&mut messages);
+        let bytes = messages[0].to_bytes();
+
+        let sql = format!(
+            "INSERT INTO {} (aggregate_id, message, created_at) VALUES (?1, ?2, ?3)",
+            self.config.table_name()
+        );
+        let created_at = IggyTimestamp::now().as_micros() as i64;
+        match connection.execute(&sql, params![aggregate_id, bytes.as_ref(), created_at]) {
+            Ok(_) => Ok(connection.last_insert_rowid()),
+            Err(err) => {
+                error!("Failed to write message to outbox: {err}");
+                Err(IggyError::CannotWriteToFile)
+            }
+        }
+    }
+
+    /// Returns the oldest unsent rows, ordered by row ID.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the rows cannot be read or a message cannot be deserialized.
+    ///
+    pub fn unsent(&self, connection: &Connection) -> Result<Vec<OutboxRow>, IggyError> {
+        let sql = format!(
+            "SELECT id, aggregate_id, message FROM {} WHERE sent_at IS NULL ORDER BY id LIMIT ?1",
+            self.config.table_name()
+        );
+
+        let rows = connection.prepare(&sql).and_then(|mut statement| {
+            statement
+                .query_map([self.config.batch_size()], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Vec<u8>>(2)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()
+        });
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(err) => {
+                error!("Failed to read outbox: {err}");
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+
+        rows.into_iter()
+            .map(|(id, aggregate_id, bytes)| {
+                Ok(OutboxRow {
+                    id,
+                    aggregate_id,
+                    message: Message::from_bytes(Bytes::from(bytes))?,
+                })
+            })
+            .collect()
+    }
+
+    /// Marks a row as sent, or deletes it if `delete_sent` is configured.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the row cannot be updated.
+    ///
+    pub fn mark_sent(&self, connection: &Connection, id: i64) -> Result<(), IggyError> {
+        let table = self.config.table_name();
+        let res = if self.config.delete_sent() {
+            connection.execute(&format!("DELETE FROM {table} WHERE id = ?1"), [id])
+        } else {
+            let sent_at = IggyTimestamp::now().as_micros() as i64;
+            connection.execute(
+                &format!("UPDATE {table} SET sent_at = ?1 WHERE id = ?2"),
+                [sent_at, id],
+            )
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!("Failed to mark outbox row {id} as sent: {err}");
+                Err(IggyError::CannotWriteToFile)
+            }
+        }
+    }
+
+    /// Returns the number of unsent rows.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the table cannot be read.
+    ///
+    pub fn pending(&self, connection: &Connection) -> Result<u64, IggyError> {
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE sent_at IS NULL",
+            self.config.table_name()
+        );
+        match connection.query_row(&sql, [], |row| row.get::<_, i64>(0)) {
+            Ok(count) => Ok(count as u64),
+            Err(err) => {
+                error!("Failed to count outbox rows: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+}
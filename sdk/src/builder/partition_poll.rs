@@ -0,0 +1,99 @@
+use crate::builder::consumer_seek::lookup_consumer;
+use crate::builder::{is_fatal_client_error, IggyConsumerConfig};
+use iggy::client::MessageClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::messages::PolledMessage;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+/// Polls every partition from its own offset with a standalone consumer until a shutdown
+/// signal is received, for readers that keep their offsets outside of the server.
+///
+/// Each non-empty batch is passed to `handle` with its partition, which returns the offset
+/// to poll next, or `None` to poll the same batch again. The partitions are polled without
+/// delay while there are messages, and after the polling interval of the config otherwise.
+///
+/// # Arguments
+///
+/// * `client` - The `IggyClient` to use.
+/// * `config` - The topic, consumer name, batch size and polling interval.
+/// * `next_offsets` - The partition IDs with the offset to start at.
+/// * `name` - The name of the reader, used in the logs.
+/// * `shutdown_rx` - Stops polling when a value is received.
+/// * `handle` - Handles a batch of one partition.
+///
+/// # Errors
+///
+/// * `IggyError` - If `handle` failed or the client disconnected.
+///
+pub(crate) async fn poll_partitions<F, Fut>(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+    mut next_offsets: BTreeMap<u32, u64>,
+    name: &str,
+    mut shutdown_rx: oneshot::Receiver<()>,
+    mut handle: F,
+) -> Result<(), IggyError>
+where
+    F: FnMut(u32, Vec<PolledMessage>) -> Fut,
+    Fut: Future<Output = Result<Option<u64>, IggyError>>,
+{
+    let polling_interval = config.polling_interval().get_duration();
+    let consumer = lookup_consumer(config)?;
+    loop {
+        let mut polled_any = false;
+        for (&partition_id, next_offset) in next_offsets.iter_mut() {
+            let polled = match client
+                .poll_messages(
+                    config.stream_id(),
+                    config.topic_id(),
+                    Some(partition_id),
+                    &consumer,
+                    &PollingStrategy::offset(*next_offset),
+                    config.batch_size(),
+                    false,
+                )
+                .await
+            {
+                Ok(polled) => polled,
+                Err(err) if is_fatal_client_error(&err) => {
+                    error!("{err:?}: shutdown {name}: {err}");
+                    return Err(err);
+                }
+                Err(err) => {
+                    error!("Error while polling partition: {partition_id}: {err}");
+                    continue;
+                }
+            };
+
+            if polled.messages.is_empty() {
+                continue;
+            }
+            if let Some(offset) = handle(partition_id, polled.messages).await? {
+                *next_offset = offset;
+                polled_any = true;
+            }
+        }
+
+        // Keep reading without delay while there are messages.
+        let delay = if polled_any {
+            Duration::ZERO
+        } else {
+            polling_interval
+        };
+        tokio::select! {
+            _ = &mut shutdown_rx => {
+                info!("Received shutdown signal, stopping {name}");
+                break;
+            }
+            _ = tokio::time::sleep(delay) => {}
+        }
+    }
+
+    Ok(())
+}
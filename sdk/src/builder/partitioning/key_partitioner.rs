@@ -0,0 +1,172 @@
+use crate::builder::PartitionKeyExtractor;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::send_messages::Message;
+use iggy::partitioner::Partitioner;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tracing::error;
+
+/// `Partitioner` that maps the key of each batch to a fixed partition.
+///
+/// The partition ID is derived from a stable hash of the key, so the mapping is the same
+/// across restarts and processes as long as the number of partitions does not change.
+/// Batches without a key are sent round-robin to all partitions.
+///
+/// The producer sends each batch to a single partition, so all keyed messages of a batch
+/// must map to the same partition; a batch with keys of different partitions is rejected.
+/// Messages without a key follow the keyed messages of their batch. Use a `KeyedEventProducer`,
+/// which splits every batch with `split_batch`, to send batches with mixed keys.
+pub struct KeyPartitioner {
+    extractor: Arc<dyn PartitionKeyExtractor>,
+    partitions_count: AtomicU32,
+    next_partition: AtomicU32,
+}
+
+impl KeyPartitioner {
+    /// Creates a new `KeyPartitioner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `extractor` - Derives the partitioning key of a message.
+    /// * `partitions_count` - The number of partitions of the topic.
+    ///
+    pub fn new(extractor: Arc<dyn PartitionKeyExtractor>, partitions_count: u32) -> Self {
+        Self {
+            extractor,
+            partitions_count: AtomicU32::new(partitions_count.max(1)),
+            next_partition: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns the number of partitions the keys are mapped to.
+    pub fn partitions_count(&self) -> u32 {
+        self.partitions_count.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of partitions, e.g. to the actual partitions of the topic.
+    pub(crate) fn set_partitions_count(&self, partitions_count: u32) {
+        self.partitions_count
+            .store(partitions_count.max(1), Ordering::Relaxed);
+    }
+
+    /// Returns the partition ID for the given key.
+    ///
+    /// Partition IDs start at 1.
+    pub fn partition_for_key(&self, key: &[u8]) -> u32 {
+        (fnv1a_hash(key) % self.partitions_count() as u64) as u32 + 1
+    }
+
+    /// Returns the partition ID for the given message.
+    pub fn partition_for_message(&self, message: &Message) -> u32 {
+        match self.extractor.partition_key(message) {
+            Some(key) => self.partition_for_key(&key),
+            None => self.next_round_robin(),
+        }
+    }
+
+    /// Splits a batch into one batch per partition, in the order of their first message.
+    ///
+    /// The order of the messages of each partition is preserved. A message without a key
+    /// follows the keyed message before it, or the first keyed message of the batch.
+    /// A batch without keys is returned as is.
+    pub fn split_batch(&self, messages: Vec<Message>) -> Vec<Vec<Message>> {
+        let mut batches: Vec<(u32, Vec<Message>)> = Vec::new();
+        let mut leading = Vec::new();
+        let mut current = None;
+        for message in messages {
+            let partition_id = self
+                .extractor
+                .partition_key(&message)
+                .map(|key| self.partition_for_key(&key));
+            let index = match partition_id {
+                Some(partition_id) => {
+                    match batches.iter().position(|(id, _)| *id == partition_id) {
+                        Some(index) => index,
+                        None => {
+                            batches.push((partition_id, Vec::new()));
+                            batches.len() - 1
+                        }
+                    }
+                }
+                None => match current {
+                    Some(index) => index,
+                    None => {
+                        leading.push(message);
+                        continue;
+                    }
+                },
+            };
+            batches[index].1.push(message);
+            current = Some(index);
+        }
+
+        let mut batches = batches
+            .into_iter()
+            .map(|(_, batch)| batch)
+            .collect::<Vec<_>>();
+        match batches.first_mut() {
+            Some(first) if !leading.is_empty() => {
+                leading.append(first);
+                *first = leading;
+            }
+            Some(_) => {}
+            None if !leading.is_empty() => batches.push(leading),
+            None => {}
+        }
+        batches
+    }
+
+    fn next_round_robin(&self) -> u32 {
+        self.next_partition.fetch_add(1, Ordering::Relaxed) % self.partitions_count() + 1
+    }
+}
+
+impl Debug for KeyPartitioner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyPartitioner")
+            .field("partitions_count", &self.partitions_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    fn calculate_partition_id(
+        &self,
+        _stream_id: &Identifier,
+        _topic_id: &Identifier,
+        messages: &[Message],
+    ) -> Result<u32, IggyError> {
+        let mut partition_id = None;
+        for key in messages
+            .iter()
+            .filter_map(|message| self.extractor.partition_key(message))
+        {
+            let key_partition_id = self.partition_for_key(&key);
+            match partition_id {
+                None => partition_id = Some(key_partition_id),
+                Some(id) if id != key_partition_id => {
+                    error!(
+                        "Batch contains keys of partitions {id} and {key_partition_id}, send one batch per key or use a KeyedEventProducer"
+                    );
+                    return Err(IggyError::InvalidCommand);
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(partition_id.unwrap_or_else(|| self.next_round_robin()))
+    }
+}
+
+/// 64-bit FNV-1a hash. Unlike the std `DefaultHasher`, the result is stable across
+/// Rust versions and processes.
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    })
+}
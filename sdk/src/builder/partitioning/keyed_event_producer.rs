@@ -0,0 +1,75 @@
+use crate::builder::{EventProducer, KeyPartitioner};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::sync::Arc;
+
+/// `EventProducer` that splits every batch into one batch per partition of its keys.
+///
+/// The `KeyPartitioner` of an `IggyProducer` rejects a batch with keys of different
+/// partitions, so a batch with a message key per message cannot be sent directly. This
+/// producer sends one batch per partition instead, in the order of their first message,
+/// so the order of the messages with the same key is preserved.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = config.with_partition_key(Arc::new(HeaderPartitionKey));
+/// let producer = IggyStreamProducer::with_key_partitioning(&client, &config).await?;
+/// producer.send_event_batch(messages).await?;
+/// ```
+#[derive(Debug)]
+pub struct KeyedEventProducer<P> {
+    producer: P,
+    partitioner: Arc<KeyPartitioner>,
+}
+
+impl<P> KeyedEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `KeyedEventProducer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    /// * `partitioner` - The `KeyPartitioner` of the producer.
+    ///
+    pub fn new(producer: P, partitioner: Arc<KeyPartitioner>) -> Self {
+        Self {
+            producer,
+            partitioner,
+        }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the partitioner used to split the batches.
+    pub fn partitioner(&self) -> &KeyPartitioner {
+        &self.partitioner
+    }
+}
+
+impl<P> EventProducer for KeyedEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.producer.send_one_event(message).await
+    }
+
+    /// Sends one batch per partition of the keys of the messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a batch cannot be sent. The batches before it were sent.
+    ///
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        for batch in self.partitioner.split_batch(messages) {
+            self.producer.send_event_batch(batch).await?;
+        }
+        Ok(())
+    }
+}
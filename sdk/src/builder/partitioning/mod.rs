@@ -0,0 +1,7 @@
+mod key_partitioner;
+mod keyed_event_producer;
+mod partition_key_extractor;
+
+pub use key_partitioner::KeyPartitioner;
+pub use keyed_event_producer::KeyedEventProducer;
+pub use partition_key_extractor::{HeaderPartitionKey, PartitionKeyExtractor};
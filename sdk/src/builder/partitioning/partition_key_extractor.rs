@@ -0,0 +1,38 @@
+use crate::builder::{MessageHeadersExt, PARTITION_KEY_HEADER};
+use iggy::messages::send_messages::Message;
+
+/// Derives the partitioning key of an outgoing message.
+///
+/// Messages with the same key are always sent to the same partition, which preserves their
+/// order, e.g. all events of one order ID. Messages without a key are spread across
+/// all partitions.
+///
+/// Implemented for closures of the form `Fn(&Message) -> Option<Vec<u8>>`.
+pub trait PartitionKeyExtractor: Send + Sync {
+    /// Returns the partitioning key of the message, or `None` if the message has no key.
+    fn partition_key(&self, message: &Message) -> Option<Vec<u8>>;
+}
+
+impl<F> PartitionKeyExtractor for F
+where
+    F: Fn(&Message) -> Option<Vec<u8>> + Send + Sync,
+{
+    fn partition_key(&self, message: &Message) -> Option<Vec<u8>> {
+        self(message)
+    }
+}
+
+/// Uses the `partition-key` header as the partitioning key.
+///
+/// This is the extractor to use with the `TypedEventProducer`, which writes the key
+/// of each value into the header.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeaderPartitionKey;
+
+impl PartitionKeyExtractor for HeaderPartitionKey {
+    fn partition_key(&self, message: &Message) -> Option<Vec<u8>> {
+        message
+            .header(PARTITION_KEY_HEADER)
+            .map(|value| value.value.to_vec())
+    }
+}
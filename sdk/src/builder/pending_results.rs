@@ -0,0 +1,61 @@
+use crate::builder::EventConsumerError;
+use std::collections::VecDeque;
+use std::future::Future;
+
+/// The results of consumed messages that were not handled yet.
+///
+/// A result stays pending until its handler succeeded, so a failed or timed out handler
+/// does not lose it, and the next attempt starts with it. The key of the last added message,
+/// e.g. its offset and ID, is kept, so a retry of that message does not add its results twice.
+#[derive(Debug)]
+pub(crate) struct PendingResults<T, K> {
+    results: VecDeque<T>,
+    last_message: Option<K>,
+}
+
+impl<T, K> Default for PendingResults<T, K> {
+    fn default() -> Self {
+        Self {
+            results: VecDeque::new(),
+            last_message: None,
+        }
+    }
+}
+
+impl<T: Clone, K: PartialEq> PendingResults<T, K> {
+    /// Returns `true` if the results of the message were already added, e.g. because
+    /// the message is retried after its handler failed.
+    pub(crate) fn contains(&self, message: &K) -> bool {
+        self.last_message.as_ref() == Some(message)
+    }
+
+    /// Adds the results of the message.
+    pub(crate) fn add(&mut self, message: K, results: Vec<T>) {
+        self.last_message = Some(message);
+        self.results.extend(results);
+    }
+
+    /// Adds results that do not belong to a message, e.g. on flush.
+    pub(crate) fn extend(&mut self, results: Vec<T>) {
+        self.results.extend(results);
+    }
+
+    /// Handles the pending results in order and removes every handled result.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a result cannot be handled. It and the results after
+    ///   it stay pending.
+    ///
+    pub(crate) async fn handle<F, Fut>(&mut self, mut handle: F) -> Result<(), EventConsumerError>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), EventConsumerError>>,
+    {
+        while let Some(result) = self.results.front() {
+            handle(result.clone()).await?;
+            self.results.pop_front();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use iggy::error::IggyError;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::oneshot;
+
+/// Resolves once the batch that contains the message has been sent.
+///
+/// Resolves to `Ok(())` if the batch was delivered and to the send error otherwise.
+/// If the producer stopped before the message was sent, it resolves to
+/// `IggyError::ClientShutdown`.
+#[derive(Debug)]
+pub struct DeliveryFuture {
+    receiver: oneshot::Receiver<Result<(), IggyError>>,
+}
+
+impl DeliveryFuture {
+    pub(crate) fn new(receiver: oneshot::Receiver<Result<(), IggyError>>) -> Self {
+        Self { receiver }
+    }
+}
+
+impl Future for DeliveryFuture {
+    type Output = Result<(), IggyError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(Ok(result)) => Poll::Ready(result),
+            Poll::Ready(Err(_)) => Poll::Ready(Err(IggyError::ClientShutdown)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::builder::{DeliveryFuture, EventProducer};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::utils::duration::IggyDuration;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, info};
+
+type DeliverySender = oneshot::Sender<Result<(), IggyError>>;
+
+enum DeliveryCommand {
+    Send(Message, DeliverySender),
+    Flush(oneshot::Sender<()>),
+}
+
+impl std::fmt::Debug for DeliveryCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryCommand::Send(message, _) => f.debug_tuple("Send").field(&message.id).finish(),
+            DeliveryCommand::Flush(_) => f.write_str("Flush"),
+        }
+    }
+}
+
+/// `EventProducer` that collects messages into batches and reports the delivery
+/// of every single message.
+///
+/// A batch is sent once it holds `batch_size` messages, when the `send_interval` elapses,
+/// or on `flush`. Each message sent with `send` gets a `DeliveryFuture` that resolves
+/// after its batch was sent. Dropping the producer sends the remaining messages.
+///
+/// At most `batch_size` messages wait in the queue, so `send` waits while a batch is sent.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let producer = IggyStreamProducer::with_delivery_reports(&client, &config).await?;
+/// let delivery = producer.send(message).await;
+/// producer
+///     .send_with_callback(other, |res| {
+///         if let Err(err) = res {
+///             error!("Failed to deliver message: {err}");
+///         }
+///     })
+///     .await;
+/// producer.flush().await?;
+/// delivery.await?;
+/// ```
+#[derive(Debug)]
+pub struct DeliveryReportProducer {
+    sender: mpsc::Sender<DeliveryCommand>,
+}
+
+impl DeliveryReportProducer {
+    /// Creates a new `DeliveryReportProducer` and spawns the task that sends the batches.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the batches.
+    /// * `batch_size` - The max number of messages in a batch.
+    /// * `send_interval` - The max time a message waits before its batch is sent.
+    ///
+    pub fn new<P>(producer: P, batch_size: u32, send_interval: IggyDuration) -> Self
+    where
+        P: EventProducer + Send + Sync + 'static,
+    {
+        let batch_size = batch_size.max(1) as usize;
+        let (sender, receiver) = mpsc::channel(batch_size);
+        tokio::spawn(run_batches(
+            Arc::new(producer),
+            receiver,
+            batch_size,
+            send_interval,
+        ));
+        Self { sender }
+    }
+
+    /// Queues a message and returns a future that resolves once the message was delivered.
+    ///
+    /// Waits while the queue is full. Once queued, the message is sent even if the returned
+    /// future is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to send.
+    ///
+    pub async fn send(&self, message: Message) -> DeliveryFuture {
+        let (sender, receiver) = oneshot::channel();
+        // If the batch task stopped, the sender is dropped and the future resolves to an error.
+        let _ = self
+            .sender
+            .send(DeliveryCommand::Send(message, sender))
+            .await;
+        DeliveryFuture::new(receiver)
+    }
+
+    /// Queues a message and calls the callback with the delivery result.
+    ///
+    /// Waits while the queue is full, but not for the delivery.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to send.
+    /// * `callback` - Called with the delivery result once the batch was sent.
+    ///
+    pub async fn send_with_callback<F>(&self, message: Message, callback: F)
+    where
+        F: FnOnce(Result<(), IggyError>) + Send + 'static,
+    {
+        let delivery = self.send(message).await;
+        tokio::spawn(async move { callback(delivery.await) });
+    }
+
+    /// Sends all queued messages and waits until they were delivered or failed.
+    ///
+    /// The result of each message is reported by its `DeliveryFuture`.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::ClientShutdown` - If the batch task has stopped.
+    ///
+    pub async fn flush(&self) -> Result<(), IggyError> {
+        let (sender, receiver) = oneshot::channel();
+        if self
+            .sender
+            .send(DeliveryCommand::Flush(sender))
+            .await
+            .is_err()
+        {
+            return Err(IggyError::ClientShutdown);
+        }
+
+        match receiver.await {
+            Ok(_) => Ok(()),
+            Err(_) => Err(IggyError::ClientShutdown),
+        }
+    }
+}
+
+impl EventProducer for DeliveryReportProducer {
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send(message).await.await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        let mut deliveries = Vec::with_capacity(messages.len());
+        for message in messages {
+            deliveries.push(self.send(message).await);
+        }
+
+        for delivery in deliveries {
+            delivery.await?;
+        }
+        Ok(())
+    }
+}
+
+async fn run_batches<P>(
+    producer: Arc<P>,
+    mut receiver: mpsc::Receiver<DeliveryCommand>,
+    batch_size: usize,
+    send_interval: IggyDuration,
+) where
+    P: EventProducer + Send + Sync,
+{
+    let mut pending: Vec<(Message, DeliverySender)> = Vec::with_capacity(batch_size);
+    let mut timer = tokio::time::interval(send_interval.get_duration());
+
+    loop {
+        tokio::select! {
+            command = receiver.recv() => match command {
+                Some(DeliveryCommand::Send(message, sender)) => {
+                    pending.push((message, sender));
+                    if pending.len() >= batch_size {
+                        send_batch(producer.as_ref(), &mut pending).await;
+                    }
+                }
+                Some(DeliveryCommand::Flush(done)) => {
+                    send_batch(producer.as_ref(), &mut pending).await;
+                    let _ = done.send(());
+                }
+                None => {
+                    info!("Producer dropped, sending remaining messages");
+                    send_batch(producer.as_ref(), &mut pending).await;
+                    break;
+                }
+            },
+
+            _ = timer.tick() => {
+                send_batch(producer.as_ref(), &mut pending).await;
+            }
+        }
+    }
+}
+
+async fn send_batch<P>(producer: &P, pending: &mut Vec<(Message, DeliverySender)>)
+where
+    P: EventProducer + Sync,
+{
+    if pending.is_empty() {
+        return;
+    }
+
+    let (messages, senders): (Vec<_>, Vec<_>) = pending.drain(..).unzip();
+    match producer.send_event_batch(messages).await {
+        Ok(_) => {
+            for sender in senders {
+                let _ = sender.send(Ok(()));
+            }
+        }
+        Err(err) => {
+            error!("Failed to send batch of {} messages: {err}", senders.len());
+            // IggyError is not Clone, so every message gets an error with the same code.
+            for sender in senders {
+                let _ = sender.send(Err(IggyError::from_code(err.as_code())));
+            }
+        }
+    }
+}
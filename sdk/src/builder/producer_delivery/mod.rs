@@ -0,0 +1,5 @@
+mod delivery_future;
+mod delivery_report_producer;
+
+pub use delivery_future::DeliveryFuture;
+pub use delivery_report_producer::DeliveryReportProducer;
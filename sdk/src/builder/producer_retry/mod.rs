@@ -0,0 +1,3 @@
+mod retrying_event_producer;
+
+pub use retrying_event_producer::{assign_message_ids, RetryingEventProducer};
@@ -0,0 +1,102 @@
+use crate::builder::{is_transient_error, EventProducer, ProducerRetryPolicy};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// `EventProducer` that retries sends which failed with a transient error.
+///
+/// Before the first attempt, every message without an ID gets a UUIDv7 ID, so all
+/// attempts send the same IDs. A message that was stored by the server although the
+/// attempt failed is then redelivered with the same ID, and consumers can drop the
+/// duplicate with the `DeduplicationLayer`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let producer = IggyStreamProducer::with_retries(&client, &config, ProducerRetryPolicy::default()).await?;
+/// producer.send_one_event(message).await?;
+/// ```
+#[derive(Debug)]
+pub struct RetryingEventProducer<P> {
+    producer: P,
+    policy: ProducerRetryPolicy,
+}
+
+impl<P> RetryingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `RetryingEventProducer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` used to send the messages.
+    /// * `policy` - The retry policy.
+    ///
+    pub fn new(producer: P, policy: ProducerRetryPolicy) -> Self {
+        Self { producer, policy }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the retry policy.
+    pub fn policy(&self) -> &ProducerRetryPolicy {
+        &self.policy
+    }
+
+    async fn send_with_retries(&self, mut messages: Vec<Message>) -> Result<(), IggyError> {
+        assign_message_ids(&mut messages);
+
+        let mut retry = 0;
+        loop {
+            let err = match self.producer.send_event_batch(messages.clone()).await {
+                Ok(_) => return Ok(()),
+                Err(err) => err,
+            };
+
+            if !is_transient_error(&err) {
+                error!("Failed to send messages: {err}");
+                return Err(err);
+            }
+
+            if retry >= self.policy.max_retries() {
+                error!("Failed to send messages after {retry} retries: {err}");
+                return Err(err);
+            }
+
+            retry += 1;
+            let backoff = self.policy.backoff(retry);
+            warn!(
+                "Failed to send messages, retry {retry}/{} in {backoff:?}: {err}",
+                self.policy.max_retries()
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+}
+
+impl<P> EventProducer for RetryingEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_with_retries(vec![message]).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        self.send_with_retries(messages).await
+    }
+}
+
+/// Assigns a UUIDv7 ID to every message that has no ID yet.
+///
+/// Messages that already have an ID keep it.
+pub fn assign_message_ids(messages: &mut [Message]) {
+    for message in messages.iter_mut().filter(|message| message.id == 0) {
+        message.id = Uuid::now_v7().as_u128();
+    }
+}
@@ -0,0 +1,437 @@
+use crate::builder::{ProducerSpoolConfig, SpoolOverflowPolicy};
+use bytes::Bytes;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use tracing::{error, warn};
+
+const RECORD_HEADER_SIZE: u64 = 4;
+
+/// Append-only file of messages that could not be sent.
+///
+/// Each record is the little-endian length of the serialized message followed by the message.
+/// The position of the oldest unsent record is stored in a `.cursor` file next to the spool,
+/// so the spool survives restarts. Once all records have been replayed, both files are
+/// truncated.
+///
+/// A record that was only partially written, e.g. because the process crashed during an append,
+/// is a torn tail: the spool is truncated to the last complete record when it is opened.
+///
+/// Messages the server rejects can be moved to a `.dead` file next to the spool, which uses the
+/// same record format and can be opened as a `MessageSpool` to inspect them.
+#[derive(Debug)]
+pub struct MessageSpool {
+    file: File,
+    cursor_path: PathBuf,
+    dead_letter_path: PathBuf,
+    cursor: u64,
+    len: u64,
+    max_size_bytes: u64,
+    overflow_policy: SpoolOverflowPolicy,
+}
+
+impl MessageSpool {
+    /// Opens the spool at the configured path, creating it if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool or cursor file cannot be opened or read.
+    ///
+    pub fn open(config: &ProducerSpoolConfig) -> Result<Self, IggyError> {
+        let path = config.path();
+        let mut file = match OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+        {
+            Ok(file) => file,
+            Err(err) => {
+                error!("Failed to open spool file {}: {err}", path.display());
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+
+        let len = match file.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(err) => {
+                error!("Failed to read spool file metadata: {err}");
+                return Err(IggyError::CannotReadFileMetadata);
+            }
+        };
+
+        let cursor_path = sibling_path(path, ".cursor");
+        let cursor = read_cursor(&cursor_path)?.min(len);
+        let complete_len = complete_records_len(&mut file, cursor, len)?;
+        if complete_len < len {
+            warn!(
+                "Spool file {} ends with a partial record, truncating {} bytes",
+                path.display(),
+                len - complete_len
+            );
+            if let Err(err) = file.set_len(complete_len).and_then(|_| file.sync_data()) {
+                error!("Failed to truncate spool file: {err}");
+                return Err(IggyError::CannotOverwriteFile);
+            }
+        }
+        let len = complete_len;
+
+        Ok(Self {
+            file,
+            cursor_path,
+            dead_letter_path: sibling_path(path, ".dead"),
+            cursor,
+            len,
+            max_size_bytes: config.max_size_bytes(),
+            overflow_policy: config.overflow_policy(),
+        })
+    }
+
+    /// Returns `true` if there are no unsent messages in the spool.
+    pub fn is_empty(&self) -> bool {
+        self.cursor >= self.len
+    }
+
+    /// Returns the size of the unsent messages in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.len - self.cursor
+    }
+
+    /// Returns the path of the file the rejected messages are moved to.
+    pub fn dead_letter_path(&self) -> &Path {
+        &self.dead_letter_path
+    }
+
+    /// Appends the messages to the spool in order, applying the overflow policy if the spool
+    /// is full. With the `Reject` policy, either all messages are spooled or none.
+    ///
+    /// Returns the number of spooled messages.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the messages do not fit and the policy is `Reject`, or the file
+    ///   cannot be written.
+    ///
+    pub fn append_all(&mut self, messages: &[Message]) -> Result<usize, IggyError> {
+        if self.overflow_policy == SpoolOverflowPolicy::Reject {
+            let batch_size: u64 = messages
+                .iter()
+                .map(|message| RECORD_HEADER_SIZE + message.to_bytes().len() as u64)
+                .sum();
+            if self.size_bytes() + batch_size > self.max_size_bytes {
+                error!("Spool is full, rejecting {} messages", messages.len());
+                return Err(IggyError::CannotAppendToFile);
+            }
+        }
+
+        let mut spooled = 0;
+        for message in messages {
+            if self.append(message)? {
+                spooled += 1;
+            }
+        }
+        Ok(spooled)
+    }
+
+    /// Appends a message to the spool, applying the overflow policy if the spool is full.
+    ///
+    /// Returns `true` if the message was spooled and `false` if it was dropped.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool is full and the policy is `Reject`, or the file cannot be written.
+    ///
+    pub fn append(&mut self, message: &Message) -> Result<bool, IggyError> {
+        let bytes = message.to_bytes();
+        let record_size = RECORD_HEADER_SIZE + bytes.len() as u64;
+
+        if self.size_bytes() + record_size > self.max_size_bytes {
+            match self.overflow_policy {
+                SpoolOverflowPolicy::Reject => {
+                    error!("Spool is full, rejecting message");
+                    return Err(IggyError::CannotAppendToFile);
+                }
+                SpoolOverflowPolicy::DropNewest => {
+                    warn!("Spool is full, dropping new message");
+                    return Ok(false);
+                }
+                SpoolOverflowPolicy::DropOldest => {
+                    if record_size > self.max_size_bytes {
+                        warn!("Message is larger than the spool, dropping it");
+                        return Ok(false);
+                    }
+                    while self.size_bytes() + record_size > self.max_size_bytes {
+                        warn!("Spool is full, dropping oldest message");
+                        self.pop()?;
+                    }
+                    self.compact()?;
+                }
+            }
+        }
+
+        let mut record = Vec::with_capacity(record_size as usize);
+        record.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        record.extend_from_slice(&bytes);
+        if let Err(err) = self
+            .file
+            .write_all(&record)
+            .and_then(|_| self.file.sync_data())
+        {
+            error!("Failed to append to spool file: {err}");
+            // Drop a partially written record, so the next append starts at a record boundary.
+            if let Err(err) = self.file.set_len(self.len) {
+                error!("Failed to truncate spool file: {err}");
+            }
+            return Err(IggyError::CannotAppendToFile);
+        }
+        self.len += record_size;
+
+        Ok(true)
+    }
+
+    /// Returns the oldest unsent message without removing it.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool file cannot be read or is corrupted.
+    ///
+    pub fn peek(&mut self) -> Result<Option<Message>, IggyError> {
+        match self.read_record()? {
+            Some((message, _)) => Ok(Some(message)),
+            None => Ok(None),
+        }
+    }
+
+    /// Removes the oldest unsent message, e.g. after it has been sent.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool file cannot be read or the cursor cannot be written.
+    ///
+    pub fn pop(&mut self) -> Result<Option<Message>, IggyError> {
+        let Some((message, record_size)) = self.read_record()? else {
+            return Ok(None);
+        };
+
+        self.advance(record_size)?;
+        Ok(Some(message))
+    }
+
+    /// Moves the oldest unsent message to the dead-letter file, e.g. because the server
+    /// rejected it, so the messages after it can be sent.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool file cannot be read, or the dead-letter or cursor file
+    ///   cannot be written.
+    ///
+    pub fn dead_letter(&mut self) -> Result<Option<Message>, IggyError> {
+        let Some((message, record_size)) = self.read_record()? else {
+            return Ok(None);
+        };
+
+        let bytes = message.to_bytes();
+        let mut record = Vec::with_capacity(record_size as usize);
+        record.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        record.extend_from_slice(&bytes);
+        if let Err(err) = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.dead_letter_path)
+            .and_then(|mut file| {
+                file.write_all(&record)?;
+                file.sync_data()
+            })
+        {
+            error!(
+                "Failed to append to dead-letter file {}: {err}",
+                self.dead_letter_path.display()
+            );
+            return Err(IggyError::CannotAppendToFile);
+        }
+
+        self.advance(record_size)?;
+        Ok(Some(message))
+    }
+
+    fn advance(&mut self, record_size: u64) -> Result<(), IggyError> {
+        self.cursor += record_size;
+        if self.is_empty() {
+            self.truncate()
+        } else {
+            write_cursor(&self.cursor_path, self.cursor)
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Option<(Message, u64)>, IggyError> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        if let Err(err) = self.file.seek(SeekFrom::Start(self.cursor)) {
+            error!("Failed to seek spool file: {err}");
+            return Err(IggyError::CannotSeekFile);
+        }
+
+        if self.cursor + RECORD_HEADER_SIZE > self.len {
+            return self.drop_torn_tail();
+        }
+        let mut length = [0u8; RECORD_HEADER_SIZE as usize];
+        if let Err(err) = self.file.read_exact(&mut length) {
+            error!("Failed to read spool record: {err}");
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let length = u32::from_le_bytes(length) as u64;
+        if self.cursor + RECORD_HEADER_SIZE + length > self.len {
+            return self.drop_torn_tail();
+        }
+        let mut bytes = vec![0u8; length as usize];
+        if let Err(err) = self.file.read_exact(&mut bytes) {
+            error!("Failed to read spool record: {err}");
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let record_size = RECORD_HEADER_SIZE + bytes.len() as u64;
+        let message = Message::from_bytes(Bytes::from(bytes))?;
+        Ok(Some((message, record_size)))
+    }
+
+    /// Truncates a partial record at the cursor, which has no complete records after it.
+    fn drop_torn_tail(&mut self) -> Result<Option<(Message, u64)>, IggyError> {
+        warn!(
+            "Spool ends with a partial record, truncating {} bytes",
+            self.size_bytes()
+        );
+        self.truncate()?;
+        Ok(None)
+    }
+
+    /// Rewrites the spool without the records before the cursor.
+    fn compact(&mut self) -> Result<(), IggyError> {
+        if self.cursor == 0 {
+            return Ok(());
+        }
+
+        if let Err(err) = self.file.seek(SeekFrom::Start(self.cursor)) {
+            error!("Failed to seek spool file: {err}");
+            return Err(IggyError::CannotSeekFile);
+        }
+        let mut remaining = Vec::with_capacity(self.size_bytes() as usize);
+        if let Err(err) = self.file.read_to_end(&mut remaining) {
+            error!("Failed to read spool file: {err}");
+            return Err(IggyError::CannotReadFile);
+        }
+
+        self.truncate()?;
+        if let Err(err) = self
+            .file
+            .write_all(&remaining)
+            .and_then(|_| self.file.sync_data())
+        {
+            error!("Failed to rewrite spool file: {err}");
+            return Err(IggyError::CannotOverwriteFile);
+        }
+        self.len = remaining.len() as u64;
+
+        Ok(())
+    }
+
+    fn truncate(&mut self) -> Result<(), IggyError> {
+        if let Err(err) = self.file.set_len(0).and_then(|_| self.file.sync_data()) {
+            error!("Failed to truncate spool file: {err}");
+            return Err(IggyError::CannotOverwriteFile);
+        }
+        self.cursor = 0;
+        self.len = 0;
+        write_cursor(&self.cursor_path, 0)
+    }
+}
+
+/// Returns the length of the spool up to the end of the last complete record after `cursor`.
+fn complete_records_len(file: &mut File, cursor: u64, len: u64) -> Result<u64, IggyError> {
+    let mut position = cursor;
+    while position + RECORD_HEADER_SIZE <= len {
+        let mut length = [0u8; RECORD_HEADER_SIZE as usize];
+        if let Err(err) = file
+            .seek(SeekFrom::Start(position))
+            .and_then(|_| file.read_exact(&mut length))
+        {
+            error!("Failed to read spool record: {err}");
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let end = position + RECORD_HEADER_SIZE + u32::from_le_bytes(length) as u64;
+        if end > len {
+            break;
+        }
+        position = end;
+    }
+    Ok(position)
+}
+
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut sibling_path = path.as_os_str().to_owned();
+    sibling_path.push(extension);
+    PathBuf::from(sibling_path)
+}
+
+fn read_cursor(path: &Path) -> Result<u64, IggyError> {
+    match std::fs::read(path) {
+        Ok(bytes) => match bytes.try_into() {
+            Ok(bytes) => Ok(u64::from_le_bytes(bytes)),
+            Err(_) => {
+                error!("Spool cursor file {} is corrupted", path.display());
+                Err(IggyError::CannotReadFile)
+            }
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(err) => {
+            error!("Failed to read spool cursor file {}: {err}", path.display());
+            Err(IggyError::CannotReadFile)
+        }
+    }
+}
+
+/// Writes the cursor to a temporary file and renames it over the cursor file, so a crash
+/// leaves either the old or the new cursor.
+fn write_cursor(path: &Path, cursor: u64) -> Result<(), IggyError> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let result = File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(&cursor.to_le_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&tmp_path, path))
+        .and_then(|_| sync_parent_dir(path));
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!(
+                "Failed to write spool cursor file {}: {err}",
+                path.display()
+            );
+            Err(IggyError::CannotWriteToFile)
+        }
+    }
+}
+
+/// Persists the rename of a file in its directory.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod message_spool;
+mod spooled_event_producer;
+
+pub use message_spool::MessageSpool;
+pub use spooled_event_producer::SpooledEventProducer;
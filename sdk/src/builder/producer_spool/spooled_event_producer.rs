@@ -0,0 +1,207 @@
+use crate::builder::{is_transient_error, EventProducer, MessageSpool, ProducerSpoolConfig};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::sync::Arc;
+use tokio::sync::{oneshot, Mutex, OwnedMutexGuard};
+use tracing::{error, info, warn};
+
+/// `EventProducer` that writes messages to a local `MessageSpool` when the server
+/// is unreachable and replays them in order once the connection returns.
+///
+/// While the spool holds unsent messages, new messages are appended to the spool as well,
+/// so the original send order is kept. Every send first tries to replay the spool.
+/// Errors that are not caused by the connection, see `is_transient_error`, are returned
+/// to the caller and the message is not spooled. A spooled message that fails with such an
+/// error during replay is moved to the dead-letter file of the spool, see `MessageSpool`.
+///
+/// The spool file is read and written on the blocking thread pool, so a slow disk does not
+/// stall the async runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let spool_config = ProducerSpoolConfig::from_path("/var/lib/orders/producer.spool");
+/// let producer = IggyStreamProducer::with_spool(&client, &config, &spool_config).await?;
+/// producer.send_one_event(message).await?;
+/// ```
+#[derive(Debug)]
+pub struct SpooledEventProducer<P> {
+    producer: P,
+    spool: Arc<Mutex<MessageSpool>>,
+}
+
+type SpoolGuard = OwnedMutexGuard<MessageSpool>;
+
+impl<P> SpooledEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `SpooledEventProducer` and opens the spool of the given config.
+    ///
+    /// Messages left in the spool from a previous run are replayed with the next send
+    /// or by the replay task.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool cannot be opened.
+    ///
+    pub fn new(producer: P, config: &ProducerSpoolConfig) -> Result<Self, IggyError> {
+        let spool = MessageSpool::open(config)?;
+        Ok(Self {
+            producer,
+            spool: Arc::new(Mutex::new(spool)),
+        })
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the size of the unsent messages in the spool in bytes.
+    pub async fn spooled_bytes(&self) -> u64 {
+        self.spool.lock().await.size_bytes()
+    }
+
+    /// Sends the spooled messages in order until the spool is empty or a send fails.
+    ///
+    /// Returns:
+    /// The number of replayed messages.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the spool or the dead-letter file cannot be read or written.
+    ///
+    pub async fn replay(&self) -> Result<usize, IggyError> {
+        let spool = self.spool.clone().lock_owned().await;
+        let (_, replayed) = self.replay_spool(spool).await?;
+        Ok(replayed)
+    }
+
+    async fn replay_spool(&self, spool: SpoolGuard) -> Result<(SpoolGuard, usize), IggyError> {
+        let mut replayed = 0;
+        let (mut spool, mut next) = blocking(spool, |spool| spool.peek()).await?;
+        while let Some(message) = next {
+            match self.producer.send_one_event(message).await {
+                Ok(_) => {
+                    (spool, next) = blocking(spool, |spool| {
+                        spool.pop()?;
+                        spool.peek()
+                    })
+                    .await?;
+                    replayed += 1;
+                }
+                Err(err) if is_transient_error(&err) => {
+                    warn!("Server still unreachable, stopping spool replay: {err}");
+                    break;
+                }
+                Err(err) => {
+                    error!("Failed to replay spooled message, moving it to the dead-letter file: {err}");
+                    (spool, next) = blocking(spool, |spool| {
+                        spool.dead_letter()?;
+                        spool.peek()
+                    })
+                    .await?;
+                }
+            }
+        }
+
+        if replayed > 0 {
+            info!("Replayed {replayed} spooled messages");
+        }
+        Ok((spool, replayed))
+    }
+
+    async fn send_or_spool(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        let mut spool = self.spool.clone().lock_owned().await;
+
+        if !spool.is_empty() {
+            (spool, _) = self.replay_spool(spool).await?;
+        }
+
+        if spool.is_empty() {
+            match self.producer.send_event_batch(messages.clone()).await {
+                Ok(_) => return Ok(()),
+                Err(err) if is_transient_error(&err) => {
+                    warn!("Failed to send messages, writing them to the spool: {err}");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        blocking(spool, move |spool| spool.append_all(&messages)).await?;
+        Ok(())
+    }
+}
+
+/// Runs the given spool operation on the blocking thread pool and hands the guard back.
+async fn blocking<T, F>(mut spool: SpoolGuard, operation: F) -> Result<(SpoolGuard, T), IggyError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut MessageSpool) -> Result<T, IggyError> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(move || {
+        let result = operation(&mut spool);
+        result.map(|value| (spool, value))
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Spool operation failed: {err}");
+            Err(IggyError::CannotReadFile)
+        }
+    }
+}
+
+impl<P> SpooledEventProducer<P>
+where
+    P: EventProducer + Send + Sync + 'static,
+{
+    /// Spawns a task that replays the spool at the configured interval, so spooled messages
+    /// are sent once the connection returns even when no new messages are produced.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The shared `SpooledEventProducer`.
+    /// * `config` - The spool configuration with the replay interval.
+    /// * `shutdown_rx` - Stops the replay task when a value is received.
+    ///
+    pub fn spawn_replay_task(
+        producer: Arc<Self>,
+        config: &ProducerSpoolConfig,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) {
+        let interval = config.replay_interval();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(interval.get_duration());
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => {
+                        info!("Received shutdown signal, stopping spool replay");
+                        break;
+                    }
+
+                    _ = timer.tick() => {
+                        if let Err(err) = producer.replay().await {
+                            error!("Failed to replay spool: {err}");
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl<P> EventProducer for SpooledEventProducer<P>
+where
+    P: EventProducer + Sync,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_or_spool(vec![message]).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        self.send_or_spool(messages).await
+    }
+}
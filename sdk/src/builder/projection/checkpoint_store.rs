@@ -0,0 +1,27 @@
+use crate::builder::{Checkpoint, EventConsumerError};
+use iggy::error::IggyError;
+
+/// Stores the offsets of a projection outside of the server, next to the projection's data.
+pub trait CheckpointStore: Send + Sync + 'static {
+    /// The handle the projection writes its data with while a batch is committed,
+    /// e.g. a SQLite transaction.
+    type Transaction<'a>;
+
+    /// Returns the checkpoints of all partitions, ordered by partition ID.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    fn load(&self) -> Result<Vec<Checkpoint>, IggyError>;
+
+    /// Runs `write` and saves the checkpoint, or neither if `write` fails.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If `write` failed or the checkpoint cannot be saved.
+    fn commit(
+        &self,
+        checkpoint: Checkpoint,
+        write: &mut dyn FnMut(&mut Self::Transaction<'_>) -> Result<(), EventConsumerError>,
+    ) -> Result<(), EventConsumerError>;
+}
@@ -0,0 +1,119 @@
+use crate::builder::{Checkpoint, CheckpointStore, EventConsumerError};
+use iggy::error::IggyError;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::error;
+
+/// `CheckpointStore` that keeps the checkpoints in a text file, one `<partition_id> <offset>`
+/// line per partition.
+///
+/// The file is replaced atomically after each batch, via a temporary file and a rename.
+/// It has no transaction: the projection writes its data itself, and the checkpoint is
+/// only saved once the projection succeeded, so a batch may be applied again after a crash.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let store = FileCheckpointStore::open("order_counts.checkpoint")?;
+/// let runner = ProjectionRunner::new(config, OrderCounts::default(), store);
+/// ```
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    checkpoints: Mutex<BTreeMap<u32, u64>>,
+}
+
+impl FileCheckpointStore {
+    /// Opens the checkpoint file, or starts without checkpoints if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::CannotReadFile` - If the file cannot be read or is invalid.
+    ///
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, IggyError> {
+        let path = path.into();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                error!("Failed to read checkpoint file {}: {err}", path.display());
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+
+        let mut checkpoints = BTreeMap::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let parsed = line.split_once(' ').and_then(|(partition_id, offset)| {
+                Some((partition_id.parse().ok()?, offset.trim().parse().ok()?))
+            });
+            let Some((partition_id, offset)) = parsed else {
+                error!("Invalid line in checkpoint file {}: {line}", path.display());
+                return Err(IggyError::CannotReadFile);
+            };
+            checkpoints.insert(partition_id, offset);
+        }
+
+        Ok(Self {
+            path,
+            checkpoints: Mutex::new(checkpoints),
+        })
+    }
+
+    /// Returns the path of the checkpoint file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, checkpoints: &BTreeMap<u32, u64>) -> std::io::Result<()> {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        let mut file = fs::File::create(&tmp_path)?;
+        for (partition_id, offset) in checkpoints {
+            writeln!(file, "{partition_id} {offset}")?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    type Transaction<'a> = ();
+
+    fn load(&self) -> Result<Vec<Checkpoint>, IggyError> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&partition_id, &offset)| Checkpoint {
+                partition_id,
+                offset,
+            })
+            .collect())
+    }
+
+    fn commit(
+        &self,
+        checkpoint: Checkpoint,
+        write: &mut dyn FnMut(&mut ()) -> Result<(), EventConsumerError>,
+    ) -> Result<(), EventConsumerError> {
+        write(&mut ())?;
+
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        let mut updated = checkpoints.clone();
+        updated.insert(checkpoint.partition_id, checkpoint.offset);
+        if let Err(err) = self.write(&updated) {
+            error!(
+                "Failed to write checkpoint file {}: {err}",
+                self.path.display()
+            );
+            return Err(EventConsumerError::new(err.to_string()));
+        }
+        *checkpoints = updated;
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+mod checkpoint_store;
+mod file_checkpoint_store;
+mod projection_runner;
+mod projection_trait;
+mod sqlite_checkpoint_store;
+
+pub use checkpoint_store::CheckpointStore;
+pub use file_checkpoint_store::FileCheckpointStore;
+pub use projection_runner::ProjectionRunner;
+pub use projection_trait::Projection;
+pub use sqlite_checkpoint_store::SqliteCheckpointStore;
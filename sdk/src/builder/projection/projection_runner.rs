@@ -0,0 +1,364 @@
+use crate::builder::iggy_consumer_ext::{clone_polled_message, report_failure, run_with_retries};
+use crate::builder::partition_poll::poll_partitions;
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{
+    decompress_polled_message_with_limit, Checkpoint, CheckpointStore, ConsumeFailure,
+    ConsumeFailureKind, ConsumeMessagesConfig, EventConsumerError, IggyConsumerConfig, Projection,
+};
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::oneshot;
+use tracing::{error, info, warn};
+
+/// Feeds the messages of a topic to a `Projection` and keeps the offsets in a
+/// `CheckpointStore` instead of on the server.
+///
+/// The runner polls every partition with its own standalone consumer, starting after the
+/// stored checkpoint, and commits the checkpoint of each batch together with the writes of
+/// the projection.
+///
+/// A failed batch is rolled back and retried with the handler timeout and retries of the
+/// `ConsumeMessagesConfig`. If it still fails, its messages are projected one at a time in
+/// the same way, and a message that fails all attempts is reported to the failure channel
+/// and skipped: its checkpoint is committed without writes.
+///
+/// `run` commits on the blocking thread pool, so the handler timeout also covers the store
+/// I/O and the runtime threads are not blocked. A timed out commit cannot be cancelled and
+/// finishes in the background; commits run one at a time, so the retry waits for it and
+/// drops the messages it already committed.
+///
+/// Only the stream, topic, consumer name, batch size and polling interval of the config are used.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let store = SqliteCheckpointStore::open(&StateStoreConfig::new("orders.db", "order_counts"))?;
+/// let runner = ProjectionRunner::new(consumer_config, OrderCounts, store);
+/// runner.run(&client, shutdown_rx).await?;
+/// ```
+pub struct ProjectionRunner<P: Projection> {
+    config: IggyConsumerConfig,
+    projection: Arc<P>,
+    store: Arc<P::Store>,
+    consume_config: ConsumeMessagesConfig,
+    commit_lock: Arc<Mutex<()>>,
+}
+
+impl<P> Debug for ProjectionRunner<P>
+where
+    P: Projection + Debug,
+    P::Store: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectionRunner")
+            .field("config", &self.config)
+            .field("projection", &self.projection)
+            .field("store", &self.store)
+            .field("consume_config", &self.consume_config)
+            .finish()
+    }
+}
+
+impl<P> ProjectionRunner<P>
+where
+    P: Projection,
+{
+    /// Creates a new `ProjectionRunner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The consumer configuration of the source topic.
+    /// * `projection` - Applies the messages to the read model.
+    /// * `store` - Stores the checkpoints next to the read model.
+    ///
+    pub fn new(config: IggyConsumerConfig, projection: P, store: P::Store) -> Self {
+        Self {
+            config,
+            projection: Arc::new(projection),
+            store: Arc::new(store),
+            consume_config: ConsumeMessagesConfig::default(),
+            commit_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Sets the handler timeout, retries and failure channel for failed batches.
+    /// By default a failed message is reported and skipped at once.
+    pub fn with_consume_config(mut self, consume_config: ConsumeMessagesConfig) -> Self {
+        self.consume_config = consume_config;
+        self
+    }
+
+    /// Returns the consumer configuration of the source topic.
+    pub fn config(&self) -> &IggyConsumerConfig {
+        &self.config
+    }
+
+    /// Returns the projection.
+    pub fn projection(&self) -> &P {
+        &self.projection
+    }
+
+    /// Returns the checkpoint store.
+    pub fn store(&self) -> &P::Store {
+        &self.store
+    }
+
+    /// Returns the failure handling of the runner.
+    pub fn consume_config(&self) -> &ConsumeMessagesConfig {
+        &self.consume_config
+    }
+
+    /// Applies a batch of messages of one partition and commits its checkpoint.
+    ///
+    /// Messages at or before the checkpoint are dropped. Messages that cannot be decompressed
+    /// are reported to the failure channel and skipped, but still covered by the checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition of the messages.
+    /// * `messages` - The messages in offset order.
+    ///
+    /// Returns:
+    /// The number of messages passed to the projection.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the projection failed or the checkpoint cannot be saved.
+    ///   Nothing is committed in that case.
+    ///
+    pub fn process_batch(
+        &self,
+        partition_id: u32,
+        messages: Vec<PolledMessage>,
+    ) -> Result<usize, EventConsumerError> {
+        commit_batch(
+            &*self.projection,
+            &*self.store,
+            &self.consume_config,
+            &self.commit_lock,
+            partition_id,
+            messages,
+        )
+    }
+
+    /// Runs `process_batch` on the blocking thread pool.
+    async fn process_batch_blocking(
+        &self,
+        partition_id: u32,
+        messages: Vec<PolledMessage>,
+    ) -> Result<usize, EventConsumerError> {
+        let projection = self.projection.clone();
+        let store = self.store.clone();
+        let consume_config = self.consume_config.clone();
+        let commit_lock = self.commit_lock.clone();
+        tokio::task::spawn_blocking(move || {
+            commit_batch(
+                &*projection,
+                &*store,
+                &consume_config,
+                &commit_lock,
+                partition_id,
+                messages,
+            )
+        })
+        .await
+        .map_err(|err| EventConsumerError::new(err.to_string()))?
+    }
+
+    /// Runs the projection until a shutdown signal is received.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `IggyClient` to use.
+    /// * `shutdown_rx` - Stops the runner when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the topic does not exist, the checkpoints cannot be read or
+    ///   the client disconnected.
+    ///
+    pub async fn run(
+        &self,
+        client: &IggyClient,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        let topic = get_topic_details(client, &self.config).await?;
+        let checkpoints = self.store.load()?;
+        let mut next_offsets = BTreeMap::new();
+        for partition in &topic.partitions {
+            let next_offset = checkpoints
+                .iter()
+                .find(|checkpoint| checkpoint.partition_id == partition.id)
+                .map_or(0, |checkpoint| checkpoint.offset + 1);
+            info!(
+                "Start projection of partition: {} at offset: {next_offset}",
+                partition.id
+            );
+            next_offsets.insert(partition.id, next_offset);
+        }
+
+        poll_partitions(
+            client,
+            &self.config,
+            next_offsets,
+            "projection runner",
+            shutdown_rx,
+            |partition_id, messages| async move {
+                Ok(self.process_polled_batch(partition_id, messages).await)
+            },
+        )
+        .await
+    }
+
+    /// Applies a polled batch like `process_batch`, with the failure handling of `run`.
+    ///
+    /// The batch is retried with the retries of the consume config. If it keeps failing,
+    /// its messages are applied one at a time, and every message that fails all attempts
+    /// is reported and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition of the messages.
+    /// * `messages` - The messages in offset order.
+    ///
+    /// Returns:
+    /// The offset to poll next, or `None` to poll the batch again because the checkpoint
+    /// of a skipped message could not be committed.
+    ///
+    pub async fn process_polled_batch(
+        &self,
+        partition_id: u32,
+        messages: Vec<PolledMessage>,
+    ) -> Option<u64> {
+        let last_offset = messages.last()?.offset;
+        let failure = match self.project_with_retries(partition_id, &messages).await {
+            Ok(()) => return Some(last_offset + 1),
+            Err(failure) => failure,
+        };
+
+        if messages.len() == 1 {
+            return self.skip(failure).await.then_some(last_offset + 1);
+        }
+
+        warn!("Projecting messages of failed batch one by one: {failure}");
+        for message in &messages {
+            let batch = std::slice::from_ref(message);
+            if let Err(failure) = self.project_with_retries(partition_id, batch).await {
+                if !self.skip(failure).await {
+                    return None;
+                }
+            }
+        }
+        Some(last_offset + 1)
+    }
+
+    async fn project_with_retries(
+        &self,
+        partition_id: u32,
+        messages: &[PolledMessage],
+    ) -> Result<(), ConsumeFailure> {
+        let offset = messages.first().map_or(0, |message| message.offset);
+        run_with_retries(&self.consume_config, partition_id, offset, move || {
+            let batch = messages.iter().map(clone_polled_message).collect();
+            async move {
+                self.process_batch_blocking(partition_id, batch)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await
+    }
+
+    /// Reports the failed message and commits its checkpoint without writes.
+    ///
+    /// Returns:
+    /// `false` if the checkpoint cannot be committed.
+    async fn skip(&self, failure: ConsumeFailure) -> bool {
+        let checkpoint = Checkpoint {
+            partition_id: failure.partition_id,
+            offset: failure.offset,
+        };
+        report_failure(&self.consume_config, failure);
+        let store = self.store.clone();
+        let commit_lock = self.commit_lock.clone();
+        let res = tokio::task::spawn_blocking(move || {
+            let _lock = commit_lock.lock().unwrap_or_else(PoisonError::into_inner);
+            store.commit(checkpoint, &mut |_| Ok(()))
+        })
+        .await
+        .unwrap_or_else(|err| Err(EventConsumerError::new(err.to_string())));
+        match res {
+            Ok(()) => true,
+            Err(err) => {
+                error!(
+                    "Error while skipping offset: {} in partition: {}: {err}",
+                    checkpoint.offset, checkpoint.partition_id
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Applies the messages after the checkpoint and commits the checkpoint of the batch.
+/// Holds the commit lock, so a retry waits for a timed out commit of the same batch.
+fn commit_batch<P: Projection>(
+    projection: &P,
+    store: &P::Store,
+    consume_config: &ConsumeMessagesConfig,
+    commit_lock: &Mutex<()>,
+    partition_id: u32,
+    messages: Vec<PolledMessage>,
+) -> Result<usize, EventConsumerError> {
+    let _lock = commit_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let checkpoint = store
+        .load()
+        .map_err(|err| EventConsumerError::new(err.to_string()))?
+        .into_iter()
+        .find(|checkpoint| checkpoint.partition_id == partition_id)
+        .map(|checkpoint| checkpoint.offset);
+
+    let Some(last_offset) = messages.last().map(|message| message.offset) else {
+        return Ok(0);
+    };
+    if checkpoint.is_some_and(|offset| last_offset <= offset) {
+        return Ok(0);
+    }
+
+    let messages: Vec<_> = messages
+        .into_iter()
+        .filter(|message| checkpoint.is_none_or(|offset| message.offset > offset))
+        .filter_map(|message| {
+            let offset = message.offset;
+            let max_size = consume_config.max_decompressed_size();
+            match decompress_polled_message_with_limit(message, max_size) {
+                Ok(message) => Some(message),
+                Err(err) => {
+                    let failure = ConsumeFailure {
+                        partition_id,
+                        offset,
+                        kind: ConsumeFailureKind::Decompression,
+                        error: EventConsumerError::new(err.to_string()),
+                        attempts: 0,
+                    };
+                    report_failure(consume_config, failure);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let checkpoint = Checkpoint {
+        partition_id,
+        offset: last_offset,
+    };
+    store.commit(checkpoint, &mut |tx| {
+        projection.project(tx, partition_id, &messages)
+    })?;
+
+    Ok(messages.len())
+}
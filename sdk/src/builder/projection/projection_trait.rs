@@ -0,0 +1,43 @@
+use crate::builder::{CheckpointStore, EventConsumerError};
+use iggy::models::messages::PolledMessage;
+
+/// A read model built from the messages of a topic, see `ProjectionRunner`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// impl Projection for OrderCounts {
+///     type Store = SqliteCheckpointStore;
+///
+///     fn project(
+///         &self,
+///         tx: &mut rusqlite::Transaction<'_>,
+///         _partition_id: u32,
+///         messages: &[PolledMessage],
+///     ) -> Result<(), EventConsumerError> {
+///         for message in messages {
+///             tx.execute("UPDATE order_counts SET count = count + 1", [])
+///                 .map_err(|err| EventConsumerError::new(err.to_string()))?;
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Projection: Send + Sync + 'static {
+    /// The store of the checkpoints.
+    type Store: CheckpointStore;
+
+    /// Applies a batch of messages of one partition.
+    ///
+    /// The writes made through `tx` are committed together with the checkpoint of the batch.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the batch cannot be applied. The batch is retried.
+    fn project(
+        &self,
+        tx: &mut <Self::Store as CheckpointStore>::Transaction<'_>,
+        partition_id: u32,
+        messages: &[PolledMessage],
+    ) -> Result<(), EventConsumerError>;
+}
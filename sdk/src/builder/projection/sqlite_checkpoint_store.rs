@@ -0,0 +1,137 @@
+use crate::builder::sqlite_tables::{create_tables, validate_table_name};
+use crate::builder::{Checkpoint, CheckpointStore, EventConsumerError, StateStoreConfig};
+use iggy::error::IggyError;
+use rusqlite::{params, Connection, Transaction};
+use std::sync::{Mutex, MutexGuard};
+use tracing::error;
+
+/// `CheckpointStore` backed by a SQLite database that also holds the projection's data.
+///
+/// The checkpoints are stored in the `<store_name>_checkpoints` table. The projection writes
+/// its data through the transaction that also saves the checkpoint, so both are committed
+/// or rolled back together.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let store = SqliteCheckpointStore::open(&StateStoreConfig::new("orders.db", "order_counts"))?;
+/// store.connection().execute_batch("CREATE TABLE IF NOT EXISTS order_counts (count INTEGER)")?;
+/// let runner = ProjectionRunner::new(config, OrderCounts, store);
+/// ```
+#[derive(Debug)]
+pub struct SqliteCheckpointStore {
+    connection: Mutex<Connection>,
+    table: String,
+}
+
+impl SqliteCheckpointStore {
+    /// Opens or creates the database and creates the checkpoint table if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotReadFile` - If the database cannot be opened.
+    /// * `IggyError::CannotWriteToFile` - If the table cannot be created.
+    ///
+    pub fn open(config: &StateStoreConfig) -> Result<Self, IggyError> {
+        let connection = match Connection::open(config.path()) {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!(
+                    "Failed to open checkpoint store {}: {err}",
+                    config.path().display()
+                );
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+        Self::with_connection(connection, config.store_name())
+    }
+
+    /// Creates the checkpoint table in the given database if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotWriteToFile` - If the table cannot be created.
+    ///
+    pub fn with_connection(connection: Connection, store_name: &str) -> Result<Self, IggyError> {
+        validate_table_name("checkpoint store", store_name)?;
+
+        let table = format!("{store_name}_checkpoints");
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                partition_id INTEGER PRIMARY KEY,
+                offset INTEGER NOT NULL
+            );"
+        );
+        create_tables(&connection, "checkpoint store", store_name, &sql)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+            table,
+        })
+    }
+
+    /// Returns the connection, e.g. to create the tables of the projection or to query them.
+    pub fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap()
+    }
+}
+
+impl CheckpointStore for SqliteCheckpointStore {
+    type Transaction<'a> = Transaction<'a>;
+
+    fn load(&self) -> Result<Vec<Checkpoint>, IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!(
+            "SELECT partition_id, offset FROM {} ORDER BY partition_id",
+            self.table
+        );
+        let checkpoints = connection.prepare(&sql).and_then(|mut statement| {
+            statement
+                .query_map([], |row| {
+                    Ok(Checkpoint {
+                        partition_id: row.get(0)?,
+                        offset: row.get::<_, i64>(1)? as u64,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+        match checkpoints {
+            Ok(checkpoints) => Ok(checkpoints),
+            Err(err) => {
+                error!("Failed to read checkpoints: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+
+    fn commit(
+        &self,
+        checkpoint: Checkpoint,
+        write: &mut dyn FnMut(&mut Transaction<'_>) -> Result<(), EventConsumerError>,
+    ) -> Result<(), EventConsumerError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut tx = connection.transaction().map_err(to_consumer_error)?;
+
+        // Dropping the transaction on error rolls back the writes of the projection.
+        write(&mut tx)?;
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO {} (partition_id, offset) VALUES (?1, ?2)",
+                self.table
+            ),
+            params![checkpoint.partition_id, checkpoint.offset as i64],
+        )
+        .map_err(to_consumer_error)?;
+        tx.commit().map_err(to_consumer_error)
+    }
+}
+
+fn to_consumer_error(err: rusqlite::Error) -> EventConsumerError {
+    error!("Failed to commit checkpoint: {err}");
+    EventConsumerError::new(err.to_string())
+}
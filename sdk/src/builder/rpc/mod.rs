@@ -0,0 +1,11 @@
+mod rpc_client;
+mod rpc_error;
+mod rpc_handler;
+mod rpc_reply_sender;
+mod rpc_server;
+
+pub use rpc_client::{RpcClient, RpcReplyRouter};
+pub use rpc_error::RpcError;
+pub use rpc_handler::RpcHandler;
+pub use rpc_reply_sender::RpcReplySender;
+pub use rpc_server::RpcServer;
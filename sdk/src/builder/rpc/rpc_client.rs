@@ -0,0 +1,275 @@
+use crate::builder::{
+    is_fatal_client_error, EventConsumer, EventConsumerError, EventProducer, IggyConsumerConfig,
+    IggyProducerConfig, IggyStreamConsumer, IggyStreamProducer, MessageHeaders, MessageHeadersExt,
+    RpcError, RPC_ERROR_HEADER,
+};
+use futures_util::StreamExt;
+use iggy::clients::client::IggyClient;
+use iggy::clients::consumer::IggyConsumer;
+use iggy::clients::producer::IggyProducer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+type PendingCalls = Arc<Mutex<HashMap<String, oneshot::Sender<PolledMessage>>>>;
+
+/// Client that sends requests through an `EventProducer` and awaits the matching replies.
+///
+/// Every request carries a new `correlation-id` and a `reply-to` header with the reply topic.
+/// The `RpcReplyRouter` of the client reads the reply topic and completes the call with the
+/// same correlation ID, so any number of calls can be in flight at once.
+///
+/// Each client instance needs its own reply topic or a standalone consumer of it, because
+/// a reply is only routed by the client that sent the request.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let (shutdown_tx, shutdown_rx) = oneshot::channel();
+/// let client = RpcClient::connect(&iggy_client, &request_config, &reply_config, Duration::from_secs(5), shutdown_rx).await?;
+/// let reply = client.call(Message::from_str("ping")?).await?;
+/// ```
+#[derive(Debug)]
+pub struct RpcClient<P> {
+    producer: P,
+    reply_to: String,
+    timeout: Duration,
+    pending: PendingCalls,
+}
+
+impl RpcClient<IggyProducer> {
+    /// Builds the request producer and the reply consumer from the given configs and
+    /// spawns the reply listener.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The Iggy client to use to connect to the Iggy server.
+    /// * `request_config` - The producer configuration of the request topic.
+    /// * `reply_config` - The consumer configuration of the reply topic.
+    /// * `timeout` - How long a call waits for its reply.
+    /// * `shutdown_rx` - Stops the reply listener when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the producer or consumer cannot be built.
+    ///
+    pub async fn connect(
+        client: &IggyClient,
+        request_config: &IggyProducerConfig,
+        reply_config: &IggyConsumerConfig,
+        timeout: Duration,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<Self, IggyError> {
+        info!("Build RPC request producer");
+        let producer = IggyStreamProducer::new(client, request_config).await?;
+
+        info!("Build RPC reply consumer");
+        let consumer = IggyStreamConsumer::new(client, reply_config).await?;
+
+        let reply_to = format!(
+            "{}/{}",
+            reply_config.stream_name(),
+            reply_config.topic_name()
+        );
+        let rpc_client = Self::new(producer, reply_to, timeout);
+        rpc_client.router().spawn(consumer, shutdown_rx);
+
+        Ok(rpc_client)
+    }
+}
+
+impl<P> RpcClient<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `RpcClient`. Replies must be passed to the `router`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` of the request topic.
+    /// * `reply_to` - The `<stream>/<topic>` the server sends the replies to.
+    /// * `timeout` - How long a call waits for its reply.
+    ///
+    pub fn new(producer: P, reply_to: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            producer,
+            reply_to: reply_to.into(),
+            timeout,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the router that completes the calls of this client with their replies.
+    pub fn router(&self) -> RpcReplyRouter {
+        RpcReplyRouter {
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Returns the number of calls waiting for their reply.
+    pub fn in_flight(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Sends a request and waits for the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request. Its headers are kept.
+    ///
+    /// # Errors
+    ///
+    /// * `RpcError::Iggy` - If the request cannot be sent.
+    /// * `RpcError::Timeout` - If no reply arrives within the timeout.
+    /// * `RpcError::Remote` - If the server handler failed.
+    /// * `RpcError::Closed` - If the reply listener stopped.
+    ///
+    pub async fn call(&self, request: Message) -> Result<PolledMessage, RpcError> {
+        let correlation_id = Uuid::now_v7().to_string();
+        let mut headers = MessageHeaders::new();
+        if let Some(existing) = &request.headers {
+            headers = headers.extend(existing);
+        }
+        let headers = headers
+            .correlation_id(&correlation_id)
+            .reply_to(&self.reply_to)
+            .build()?;
+        let request = Message::new(Some(request.id), request.payload, Some(headers));
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(correlation_id.clone(), sender);
+        // Removes the pending call on timeout, error, or when the caller drops the future.
+        let _guard = PendingCallGuard {
+            pending: &self.pending,
+            correlation_id: &correlation_id,
+        };
+
+        self.producer.send_one_event(request).await?;
+
+        let reply = match tokio::time::timeout(self.timeout, receiver).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(_)) => return Err(RpcError::Closed),
+            Err(_) => {
+                warn!(
+                    "No reply for call {correlation_id} within {:?}",
+                    self.timeout
+                );
+                return Err(RpcError::Timeout(self.timeout));
+            }
+        };
+
+        match reply.header_str(RPC_ERROR_HEADER) {
+            Some(err) => Err(RpcError::Remote(err.to_string())),
+            None => Ok(reply),
+        }
+    }
+}
+
+struct PendingCallGuard<'a> {
+    pending: &'a PendingCalls,
+    correlation_id: &'a str,
+}
+
+impl Drop for PendingCallGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.lock().unwrap().remove(self.correlation_id);
+    }
+}
+
+/// Completes the calls of an `RpcClient` with the replies read from the reply topic.
+///
+/// Implements `EventConsumer`, so it can run in any consume loop, or use `spawn`
+/// to read an `IggyConsumer` directly.
+#[derive(Debug, Clone)]
+pub struct RpcReplyRouter {
+    pending: PendingCalls,
+}
+
+impl RpcReplyRouter {
+    /// Completes the call with the correlation ID of the reply.
+    ///
+    /// Returns `false` if no call is waiting for the reply, e.g. because it timed out.
+    pub fn route(&self, reply: PolledMessage) -> bool {
+        let Some(correlation_id) = reply.correlation_id() else {
+            warn!("Reply at offset: {} has no correlation ID", reply.offset);
+            return false;
+        };
+
+        let Some(sender) = self.pending.lock().unwrap().remove(correlation_id) else {
+            debug!("No pending call for reply {correlation_id}");
+            return false;
+        };
+
+        sender.send(reply).is_ok()
+    }
+
+    /// Fails all pending calls with `RpcError::Closed`.
+    pub fn close(&self) {
+        self.pending.lock().unwrap().clear();
+    }
+
+    /// Spawns a task that routes all messages of the consumer until a shutdown signal
+    /// is received or the consumer ends. When the task stops, all pending calls fail
+    /// with `RpcError::Closed`.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the reply topic.
+    /// * `shutdown_rx` - Stops the task when a value is received.
+    ///
+    /// Returns:
+    /// The handle of the task, which fails with an `IggyError` if the client disconnected.
+    ///
+    pub fn spawn(
+        self,
+        mut consumer: IggyConsumer,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) -> JoinHandle<Result<(), IggyError>> {
+        tokio::spawn(async move {
+            let res = loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => {
+                        info!("Received shutdown signal, stopping RPC reply listener");
+                        break Ok(());
+                    }
+
+                    message = consumer.next() => match message {
+                        Some(Ok(received_message)) => {
+                            self.route(received_message.message);
+                        }
+                        Some(Err(err)) if is_fatal_client_error(&err) => {
+                            error!("{err:?}: shutdown RPC reply listener: {err}");
+                            break Err(err);
+                        }
+                        Some(Err(err)) => error!("Error while receiving RPC reply: {err}"),
+                        None => break Ok(()),
+                    }
+                }
+            };
+            self.close();
+            res
+        })
+    }
+}
+
+impl EventConsumer for RpcReplyRouter {
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        self.route(message);
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use iggy::error::IggyError;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// The reason an RPC call failed.
+#[derive(Debug)]
+pub enum RpcError {
+    /// The request could not be sent.
+    Iggy(IggyError),
+    /// No reply arrived within the timeout.
+    Timeout(Duration),
+    /// The server handler returned an error.
+    Remote(String),
+    /// The reply listener stopped before the reply arrived.
+    Closed,
+}
+
+impl Error for RpcError {}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RpcError::Iggy(err) => write!(f, "RpcError: {err}"),
+            RpcError::Timeout(timeout) => write!(f, "RpcError: no reply within {timeout:?}"),
+            RpcError::Remote(err) => write!(f, "RpcError: remote handler failed: {err}"),
+            RpcError::Closed => write!(f, "RpcError: reply listener stopped"),
+        }
+    }
+}
+
+impl From<IggyError> for RpcError {
+    fn from(err: IggyError) -> Self {
+        RpcError::Iggy(err)
+    }
+}
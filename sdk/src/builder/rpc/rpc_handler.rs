@@ -0,0 +1,21 @@
+use crate::builder::EventConsumerError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+
+/// Trait for the request handler of an `RpcServer`.
+#[allow(dead_code)] // Clippy can't see that the trait is used
+#[trait_variant::make(RpcHandler: Send)]
+pub trait LocalRpcHandler {
+    /// Handle a request and return the reply.
+    ///
+    /// The server adds the correlation ID of the request to the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request message
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the request fails. The error is sent back to the caller.
+    async fn handle(&self, request: PolledMessage) -> Result<Message, EventConsumerError>;
+}
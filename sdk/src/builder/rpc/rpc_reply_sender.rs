@@ -0,0 +1,49 @@
+use iggy::client::MessageClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::send_messages::{Message, Partitioning};
+use std::sync::Arc;
+use tracing::error;
+
+/// Trait for sending RPC replies to the topic named in the `reply-to` header.
+#[allow(dead_code)] // Clippy can't see that the trait is used
+#[trait_variant::make(RpcReplySender: Send)]
+pub trait LocalRpcReplySender {
+    /// Send a reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `reply_to` - The `<stream>/<topic>` of the caller.
+    /// * `message` - The reply.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the reply cannot be sent.
+    async fn send_reply(&self, reply_to: &str, message: Message) -> Result<(), IggyError>;
+}
+
+impl RpcReplySender for IggyClient {
+    async fn send_reply(&self, reply_to: &str, message: Message) -> Result<(), IggyError> {
+        let Some((stream, topic)) = reply_to.split_once('/') else {
+            error!("Invalid reply-to header: {reply_to}");
+            return Err(IggyError::InvalidHeaderValue);
+        };
+
+        let stream_id = Identifier::named(stream)?;
+        let topic_id = Identifier::named(topic)?;
+        self.send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::balanced(),
+            &mut [message],
+        )
+        .await
+    }
+}
+
+impl<T: RpcReplySender + Send + Sync> RpcReplySender for Arc<T> {
+    async fn send_reply(&self, reply_to: &str, message: Message) -> Result<(), IggyError> {
+        (**self).send_reply(reply_to, message).await
+    }
+}
@@ -0,0 +1,169 @@
+use crate::builder::{
+    is_fatal_client_error, EventConsumer, EventConsumerError, MessageHeaders, MessageHeadersExt,
+    RpcHandler, RpcReplySender, RPC_ERROR_HEADER,
+};
+use futures_util::StreamExt;
+use iggy::clients::consumer::IggyConsumer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+use std::sync::Arc;
+use tokio::sync::{oneshot, Semaphore};
+use tracing::{error, info, warn};
+
+/// Server that runs an `RpcHandler` on every request and sends the reply to the
+/// topic named in the `reply-to` header of the request.
+///
+/// Up to `max_in_flight` requests are handled concurrently; further requests wait for a
+/// free slot. Requests without a `correlation-id` or `reply-to` header are dropped.
+/// A handler error is sent back as a reply with the `rpc-error` header.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let consumer = IggyStreamConsumer::new(&client, &request_config).await?;
+/// let server = RpcServer::new(PingHandler, Arc::new(client), 32);
+/// server.serve(consumer, shutdown_rx).await?;
+/// ```
+#[derive(Debug)]
+pub struct RpcServer<H, S> {
+    handler: Arc<H>,
+    sender: Arc<S>,
+    in_flight: Arc<Semaphore>,
+}
+
+impl<H, S> Clone for RpcServer<H, S> {
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            sender: self.sender.clone(),
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+impl<H, S> RpcServer<H, S>
+where
+    H: RpcHandler + Send + Sync + 'static,
+    S: RpcReplySender + Send + Sync + 'static,
+{
+    /// Creates a new `RpcServer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Handles the requests.
+    /// * `sender` - Sends the replies, e.g. the `IggyClient`.
+    /// * `max_in_flight` - The max number of requests handled concurrently.
+    ///
+    pub fn new(handler: H, sender: Arc<S>, max_in_flight: usize) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            sender,
+            in_flight: Arc::new(Semaphore::new(max_in_flight.max(1))),
+        }
+    }
+
+    /// Handles the requests of the consumer until a shutdown signal is received or
+    /// the consumer ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the request topic.
+    /// * `shutdown_rx` - Stops the server when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the client disconnected.
+    ///
+    pub async fn serve(
+        &self,
+        mut consumer: IggyConsumer,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    info!("Received shutdown signal, stopping RPC server");
+                    break;
+                }
+
+                message = consumer.next() => match message {
+                    Some(Ok(received_message)) => {
+                        if let Err(err) = self.dispatch(received_message.message).await {
+                            error!("Error while dispatching RPC request: {err}");
+                        }
+                    }
+                    Some(Err(err)) if is_fatal_client_error(&err) => {
+                        error!("{err:?}: shutdown RPC server: {err}");
+                        return Err(err);
+                    }
+                    Some(Err(err)) => error!("Error while receiving RPC request: {err}"),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Waits for a free slot and handles the request in a new task.
+    async fn dispatch(&self, request: PolledMessage) -> Result<(), EventConsumerError> {
+        let permit = match self.in_flight.clone().acquire_owned().await {
+            Ok(permit) => permit,
+            Err(err) => return Err(EventConsumerError::new(err.to_string())),
+        };
+
+        let server = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = server.handle_request(request).await {
+                error!("Failed to send RPC reply: {err}");
+            }
+            drop(permit);
+        });
+        Ok(())
+    }
+
+    async fn handle_request(&self, request: PolledMessage) -> Result<(), IggyError> {
+        let (Some(correlation_id), Some(reply_to)) = (
+            request.correlation_id().map(str::to_string),
+            request.reply_to().map(str::to_string),
+        ) else {
+            warn!(
+                "Dropping RPC request without correlation ID or reply-to at offset: {}",
+                request.offset
+            );
+            return Ok(());
+        };
+
+        let reply = match self.handler.handle(request).await {
+            Ok(reply) => {
+                let mut headers = MessageHeaders::new();
+                if let Some(existing) = &reply.headers {
+                    headers = headers.extend(existing);
+                }
+                let headers = headers.correlation_id(&correlation_id).build()?;
+                Message::new(Some(reply.id), reply.payload, Some(headers))
+            }
+            Err(err) => {
+                warn!("RPC handler failed for call {correlation_id}: {err}");
+                let err = err.0;
+                MessageHeaders::new()
+                    .correlation_id(&correlation_id)
+                    .insert_str(RPC_ERROR_HEADER, &err)
+                    .message(err)?
+            }
+        };
+
+        self.sender.send_reply(&reply_to, reply).await
+    }
+}
+
+impl<H, S> EventConsumer for RpcServer<H, S>
+where
+    H: RpcHandler + Send + Sync + 'static,
+    S: RpcReplySender + Send + Sync + 'static,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        self.dispatch(message).await
+    }
+}
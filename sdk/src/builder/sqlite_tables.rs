@@ -0,0 +1,50 @@
+use iggy::error::IggyError;
+use rusqlite::Connection;
+use tracing::error;
+
+/// Checks that a name used in SQLite table names only contains ASCII letters, digits
+/// and `_`, because table names cannot be bound as parameters.
+///
+/// # Arguments
+///
+/// * `kind` - What the name belongs to, e.g. `state store`, used in the log.
+/// * `name` - The name to check.
+///
+/// # Errors
+///
+/// * `IggyError::InvalidCommand` - If the name is empty or contains other characters.
+///
+pub(crate) fn validate_table_name(kind: &str, name: &str) -> Result<(), IggyError> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        error!("Invalid {kind} name: {name}");
+        return Err(IggyError::InvalidCommand);
+    }
+    Ok(())
+}
+
+/// Runs the `CREATE TABLE IF NOT EXISTS` statements of a store.
+///
+/// # Arguments
+///
+/// * `connection` - The database connection.
+/// * `kind` - What the tables belong to, e.g. `state store`, used in the log.
+/// * `name` - The name of the store, used in the log.
+/// * `sql` - The statements.
+///
+/// # Errors
+///
+/// * `IggyError::CannotWriteToFile` - If the statements fail.
+///
+pub(crate) fn create_tables(
+    connection: &Connection,
+    kind: &str,
+    name: &str,
+    sql: &str,
+) -> Result<(), IggyError> {
+    if let Err(err) = connection.execute_batch(sql) {
+        error!("Failed to create {kind} {name}: {err}");
+        return Err(IggyError::CannotWriteToFile);
+    }
+    Ok(())
+}
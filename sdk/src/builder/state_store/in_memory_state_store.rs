@@ -0,0 +1,69 @@
+use crate::builder::{Checkpoint, StateChanges, StateStore};
+use iggy::error::IggyError;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// `StateStore` that keeps the state in memory, e.g. for tests or state that is
+/// rebuilt on every start.
+#[derive(Debug, Default)]
+pub struct InMemoryStateStore {
+    inner: Mutex<InMemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+    values: BTreeMap<Vec<u8>, Vec<u8>>,
+    checkpoints: HashMap<u32, u64>,
+}
+
+impl InMemoryStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().values.len()
+    }
+
+    /// Returns `true` if the store has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl StateStore for InMemoryStateStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, IggyError> {
+        Ok(self.inner.lock().unwrap().values.get(key).cloned())
+    }
+
+    fn commit(&self, changes: &StateChanges, checkpoint: Checkpoint) -> Result<(), IggyError> {
+        let mut inner = self.inner.lock().unwrap();
+        for (key, value) in changes.iter() {
+            match value {
+                Some(value) => inner.values.insert(key.to_vec(), value.to_vec()),
+                None => inner.values.remove(key),
+            };
+        }
+        inner
+            .checkpoints
+            .insert(checkpoint.partition_id, checkpoint.offset);
+        Ok(())
+    }
+
+    fn checkpoints(&self) -> Result<Vec<Checkpoint>, IggyError> {
+        let mut checkpoints: Vec<_> = self
+            .inner
+            .lock()
+            .unwrap()
+            .checkpoints
+            .iter()
+            .map(|(&partition_id, &offset)| Checkpoint {
+                partition_id,
+                offset,
+            })
+            .collect();
+        checkpoints.sort_by_key(|checkpoint| checkpoint.partition_id);
+        Ok(checkpoints)
+    }
+}
@@ -0,0 +1,13 @@
+mod in_memory_state_store;
+mod sqlite_state_store;
+mod state_store_trait;
+mod state_transaction;
+mod stateful_consumer;
+mod stateful_event_consumer;
+
+pub use in_memory_state_store::InMemoryStateStore;
+pub use sqlite_state_store::SqliteStateStore;
+pub use state_store_trait::{Checkpoint, StateChanges, StateStore};
+pub use state_transaction::StateTransaction;
+pub use stateful_consumer::StatefulConsumer;
+pub use stateful_event_consumer::*;
@@ -0,0 +1,189 @@
+use crate::builder::sqlite_tables::{create_tables, validate_table_name};
+use crate::builder::{Checkpoint, StateChanges, StateStore, StateStoreConfig};
+use iggy::error::IggyError;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+use tracing::error;
+
+/// `StateStore` backed by a SQLite database on disk.
+///
+/// The store uses two tables, `<store_name>_state` for the values and
+/// `<store_name>_offsets` for the checkpoints, and commits both in one transaction.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let store = SqliteStateStore::open(&StateStoreConfig::from_path("orders_state.db"))?;
+/// let consumer = StatefulConsumer::new(store, OrderTotals);
+/// ```
+#[derive(Debug)]
+pub struct SqliteStateStore {
+    connection: Mutex<Connection>,
+    state_table: String,
+    offsets_table: String,
+}
+
+impl SqliteStateStore {
+    /// Opens or creates the database and creates the tables of the store if they do not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotReadFile` - If the database cannot be opened.
+    /// * `IggyError::CannotWriteToFile` - If the tables cannot be created.
+    ///
+    pub fn open(config: &StateStoreConfig) -> Result<Self, IggyError> {
+        let connection = match Connection::open(config.path()) {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!(
+                    "Failed to open state store {}: {err}",
+                    config.path().display()
+                );
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+        Self::with_connection(connection, config.store_name())
+    }
+
+    /// Creates the tables of the store in the given database if they do not exist.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidCommand` - If the store name contains other characters than
+    ///   ASCII letters, digits and `_`.
+    /// * `IggyError::CannotWriteToFile` - If the tables cannot be created.
+    ///
+    pub fn with_connection(connection: Connection, store_name: &str) -> Result<Self, IggyError> {
+        validate_table_name("state store", store_name)?;
+
+        let state_table = format!("{store_name}_state");
+        let offsets_table = format!("{store_name}_offsets");
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {state_table} (
+                key BLOB PRIMARY KEY,
+                value BLOB NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS {offsets_table} (
+                partition_id INTEGER PRIMARY KEY,
+                offset INTEGER NOT NULL
+            );"
+        );
+        create_tables(&connection, "state store", store_name, &sql)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+            state_table,
+            offsets_table,
+        })
+    }
+
+    fn write(
+        connection: &mut Connection,
+        state_table: &str,
+        offsets_table: &str,
+        changes: &StateChanges,
+        checkpoint: Checkpoint,
+    ) -> rusqlite::Result<()> {
+        let tx = connection.transaction()?;
+        {
+            let mut upsert = tx.prepare_cached(&format!(
+                "INSERT OR REPLACE INTO {state_table} (key, value) VALUES (?1, ?2)"
+            ))?;
+            let mut delete =
+                tx.prepare_cached(&format!("DELETE FROM {state_table} WHERE key = ?1"))?;
+            for (key, value) in changes.iter() {
+                match value {
+                    Some(value) => upsert.execute(params![key, value])?,
+                    None => delete.execute(params![key])?,
+                };
+            }
+        }
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO {offsets_table} (partition_id, offset) VALUES (?1, ?2)"
+            ),
+            params![checkpoint.partition_id, checkpoint.offset as i64],
+        )?;
+        tx.commit()
+    }
+}
+
+impl StateStore for SqliteStateStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!("SELECT value FROM {} WHERE key = ?1", self.state_table);
+        match connection
+            .query_row(&sql, params![key], |row| row.get(0))
+            .optional()
+        {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                error!("Failed to read state: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+
+    fn commit(&self, changes: &StateChanges, checkpoint: Checkpoint) -> Result<(), IggyError> {
+        let mut connection = self.connection.lock().unwrap();
+        match Self::write(
+            &mut connection,
+            &self.state_table,
+            &self.offsets_table,
+            changes,
+            checkpoint,
+        ) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                error!("Failed to commit state: {err}");
+                Err(IggyError::CannotWriteToFile)
+            }
+        }
+    }
+
+    fn checkpoint(&self, partition_id: u32) -> Result<Option<u64>, IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!(
+            "SELECT offset FROM {} WHERE partition_id = ?1",
+            self.offsets_table
+        );
+        match connection
+            .query_row(&sql, params![partition_id], |row| row.get::<_, i64>(0))
+            .optional()
+        {
+            Ok(offset) => Ok(offset.map(|offset| offset as u64)),
+            Err(err) => {
+                error!("Failed to read checkpoint: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+
+    fn checkpoints(&self) -> Result<Vec<Checkpoint>, IggyError> {
+        let connection = self.connection.lock().unwrap();
+        let sql = format!(
+            "SELECT partition_id, offset FROM {} ORDER BY partition_id",
+            self.offsets_table
+        );
+        let checkpoints = connection.prepare(&sql).and_then(|mut statement| {
+            statement
+                .query_map([], |row| {
+                    Ok(Checkpoint {
+                        partition_id: row.get(0)?,
+                        offset: row.get::<_, i64>(1)? as u64,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+        match checkpoints {
+            Ok(checkpoints) => Ok(checkpoints),
+            Err(err) => {
+                error!("Failed to read checkpoints: {err}");
+                Err(IggyError::CannotReadFile)
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use iggy::error::IggyError;
+use std::collections::BTreeMap;
+
+/// The offset of the last message of a partition whose state changes were committed.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Checkpoint {
+    /// The partition ID.
+    pub partition_id: u32,
+    /// The offset of the last processed message.
+    pub offset: u64,
+}
+
+/// The writes of one message, in key order. A `None` value deletes the key.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct StateChanges {
+    changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl StateChanges {
+    pub(crate) fn get(&self, key: &[u8]) -> Option<&Option<Vec<u8>>> {
+        self.changes.get(key)
+    }
+
+    pub(crate) fn put(&mut self, key: Vec<u8>, value: Option<Vec<u8>>) {
+        self.changes.insert(key, value);
+    }
+
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of written keys.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns the written keys with their new values.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], Option<&[u8]>)> {
+        self.changes
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_deref()))
+    }
+}
+
+/// Key-value store for the state of a stateful consumer.
+///
+/// The changes of every message are committed together with the offset of the message,
+/// so after a restart the state always matches the checkpointed position.
+pub trait StateStore: Send + Sync {
+    /// Returns the committed value of the key.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, IggyError>;
+
+    /// Applies the changes and stores the checkpoint atomically.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be written. Nothing is applied in that case.
+    fn commit(&self, changes: &StateChanges, checkpoint: Checkpoint) -> Result<(), IggyError>;
+
+    /// Returns the checkpoints of all partitions, ordered by partition ID.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    fn checkpoints(&self) -> Result<Vec<Checkpoint>, IggyError>;
+
+    /// Returns the checkpointed offset of the partition.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    fn checkpoint(&self, partition_id: u32) -> Result<Option<u64>, IggyError> {
+        Ok(self
+            .checkpoints()?
+            .into_iter()
+            .find(|checkpoint| checkpoint.partition_id == partition_id)
+            .map(|checkpoint| checkpoint.offset))
+    }
+}
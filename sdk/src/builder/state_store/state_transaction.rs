@@ -0,0 +1,55 @@
+use crate::builder::{StateChanges, StateStore};
+use iggy::error::IggyError;
+
+/// The view of the state store while a message is handled.
+///
+/// Reads see the writes made earlier in the same transaction. The writes are committed
+/// together with the offset of the message once the handler succeeds, and discarded
+/// if it fails.
+pub struct StateTransaction<'a> {
+    store: &'a dyn StateStore,
+    changes: StateChanges,
+}
+
+impl<'a> StateTransaction<'a> {
+    /// Creates a new, empty `StateTransaction` on the given store.
+    pub fn new(store: &'a dyn StateStore) -> Self {
+        Self {
+            store,
+            changes: StateChanges::default(),
+        }
+    }
+
+    /// Returns the value of the key.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    ///
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, IggyError> {
+        let key = key.as_ref();
+        match self.changes.get(key) {
+            Some(value) => Ok(value.clone()),
+            None => self.store.get(key),
+        }
+    }
+
+    /// Sets the value of the key.
+    pub fn put(&mut self, key: impl AsRef<[u8]>, value: impl Into<Vec<u8>>) {
+        self.changes.put(key.as_ref().to_vec(), Some(value.into()));
+    }
+
+    /// Deletes the key.
+    pub fn delete(&mut self, key: impl AsRef<[u8]>) {
+        self.changes.put(key.as_ref().to_vec(), None);
+    }
+
+    /// Returns the writes of the transaction.
+    pub fn changes(&self) -> &StateChanges {
+        &self.changes
+    }
+
+    pub(crate) fn into_changes(self) -> StateChanges {
+        self.changes
+    }
+}
@@ -0,0 +1,260 @@
+use crate::builder::consumer_seek::seek_consumer;
+use crate::builder::iggy_consumer_ext::{clone_polled_message, report_failure, run_with_retries};
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{
+    decompress_polled_message_with_limit, is_fatal_client_error, Checkpoint, ConsumeFailure,
+    ConsumeFailureKind, ConsumeMessagesConfig, EventConsumerError, IggyConsumerConfig, SeekTarget,
+    StateChanges, StateStore, StateTransaction, StatefulEventConsumer,
+};
+use futures_util::StreamExt;
+use iggy::clients::client::IggyClient;
+use iggy::clients::consumer::IggyConsumer;
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use tokio::sync::oneshot;
+use tracing::{debug, error, info};
+
+/// Runs a `StatefulEventConsumer` and commits the state changes of every message
+/// together with its offset.
+///
+/// The checkpoints in the `StateStore` are the source of truth for the position:
+/// messages at or before the checkpoint of their partition are skipped, so messages
+/// redelivered after a restart do not change the state twice. Call `restore_position`
+/// before building the consumer, so that messages after the checkpoint are delivered
+/// again even if the server already stored a later offset.
+///
+/// Compressed payloads are decompressed first. A failed handler is handled like a failed
+/// message in `consume_messages_with_config`: its writes are discarded and it is retried with
+/// the handler timeout and retries of the `ConsumeMessagesConfig`. After the last attempt, or
+/// if the message cannot be decompressed, the failure is reported to the failure channel and
+/// the offset is checkpointed, so the message is skipped.
+///
+/// If the state cannot be committed, `run` stops, so no later offset is checkpointed before
+/// the message. Call `restore_position` again before restarting the consumer.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let stateful = StatefulConsumer::new(SqliteStateStore::open(&state_config)?, OrderTotals);
+/// stateful.restore_position(&client, &consumer_config).await?;
+/// let consumer = IggyStreamConsumer::new(&client, &consumer_config).await?;
+/// stateful.run(consumer, shutdown_rx).await?;
+/// ```
+#[derive(Debug)]
+pub struct StatefulConsumer<S, H> {
+    store: S,
+    handler: H,
+    consume_config: ConsumeMessagesConfig,
+}
+
+impl<S, H> StatefulConsumer<S, H>
+where
+    S: StateStore,
+    H: StatefulEventConsumer + Sync,
+{
+    /// Creates a new `StatefulConsumer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store of the state and checkpoints.
+    /// * `handler` - Consumes the messages and updates the state.
+    ///
+    pub fn new(store: S, handler: H) -> Self {
+        Self {
+            store,
+            handler,
+            consume_config: ConsumeMessagesConfig::default(),
+        }
+    }
+
+    /// Sets the handler timeout, retries and failure channel for failed messages.
+    /// By default a failed message is reported and skipped at once.
+    pub fn with_consume_config(mut self, consume_config: ConsumeMessagesConfig) -> Self {
+        self.consume_config = consume_config;
+        self
+    }
+
+    /// Returns the state store, e.g. to query the state.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns the failure handling of the consumer.
+    pub fn consume_config(&self) -> &ConsumeMessagesConfig {
+        &self.consume_config
+    }
+
+    /// Moves the consumer of the config to the message after the checkpoint in every
+    /// partition of the topic. Partitions without a checkpoint are moved to the first message,
+    /// so the state is built from their complete history. Their stored offset is deleted, so
+    /// build the consumer from a config without an offset reset policy other than `Earliest`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `IggyClient` used to store the offsets.
+    /// * `config` - The `IggyConsumerConfig` of the consumer.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the topic or the checkpoints cannot be read or the offsets cannot
+    ///   be stored.
+    ///
+    pub async fn restore_position(
+        &self,
+        client: &IggyClient,
+        config: &IggyConsumerConfig,
+    ) -> Result<(), IggyError> {
+        let topic = get_topic_details(client, config).await?;
+        for partition in &topic.partitions {
+            let target = match self.store.checkpoint(partition.id)? {
+                Some(offset) => {
+                    info!(
+                        "Restore partition: {} to checkpoint at offset: {offset}",
+                        partition.id
+                    );
+                    SeekTarget::Offset(offset + 1)
+                }
+                None => {
+                    info!(
+                        "Restore partition: {} without checkpoint to the first message",
+                        partition.id
+                    );
+                    SeekTarget::First
+                }
+            };
+            seek_consumer(client, config, target, Some(partition.id)).await?;
+        }
+        Ok(())
+    }
+
+    /// Handles a single message and commits its state changes with its offset.
+    ///
+    /// If the message cannot be decompressed or the handler fails all attempts, the failure
+    /// is reported and only the offset is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition of the message.
+    /// * `message` - The message to consume.
+    ///
+    /// Returns:
+    /// `false` if the message was skipped because it is at or before the checkpoint
+    /// or it failed.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the checkpoint cannot be read or the state cannot be committed.
+    ///
+    pub async fn process(
+        &self,
+        partition_id: u32,
+        message: PolledMessage,
+    ) -> Result<bool, IggyError> {
+        let offset = message.offset;
+        let checkpoint = self.store.checkpoint(partition_id)?;
+        if checkpoint.is_some_and(|checkpoint| offset <= checkpoint) {
+            debug!("Skipping checkpointed offset: {offset} in partition: {partition_id}");
+            return Ok(false);
+        }
+
+        let max_size = self.consume_config.max_decompressed_size();
+        let res = match decompress_polled_message_with_limit(message, max_size) {
+            Ok(message) => self.consume_with_retries(partition_id, &message).await,
+            Err(err) => Err(ConsumeFailure {
+                partition_id,
+                offset,
+                kind: ConsumeFailureKind::Decompression,
+                error: EventConsumerError::new(err.to_string()),
+                attempts: 0,
+            }),
+        };
+
+        let (changes, processed) = match res {
+            Ok(changes) => (changes, true),
+            Err(failure) => {
+                report_failure(&self.consume_config, failure);
+                (StateChanges::default(), false)
+            }
+        };
+
+        let checkpoint = Checkpoint {
+            partition_id,
+            offset,
+        };
+        self.store.commit(&changes, checkpoint)?;
+
+        Ok(processed)
+    }
+
+    /// Every attempt starts with a new transaction, so writes of a failed attempt are discarded.
+    async fn consume_with_retries(
+        &self,
+        partition_id: u32,
+        message: &PolledMessage,
+    ) -> Result<StateChanges, ConsumeFailure> {
+        let store = &self.store;
+        let handler = &self.handler;
+        run_with_retries(
+            &self.consume_config,
+            partition_id,
+            message.offset,
+            move || async move {
+                let mut state = StateTransaction::new(store);
+                handler
+                    .consume(clone_polled_message(message), &mut state)
+                    .await
+                    .map(|()| state.into_changes())
+            },
+        )
+        .await
+    }
+
+    /// Consumes messages until a shutdown signal is received or the consumer ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the input topic.
+    /// * `shutdown_rx` - Stops the consumer when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the state cannot be committed or the client disconnected.
+    ///
+    pub async fn run(
+        &self,
+        mut consumer: IggyConsumer,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    info!("Received shutdown signal, stopping stateful consumer");
+                    break;
+                }
+
+                message = consumer.next() => match message {
+                    Some(Ok(received_message)) => {
+                        let partition_id = received_message.partition_id;
+                        if let Err(err) = self.process(partition_id, received_message.message).await {
+                            error!("Error while committing message in partition: {partition_id}, stopping stateful consumer: {err}");
+                            return Err(err);
+                        }
+                    }
+                    Some(Err(err)) if is_fatal_client_error(&err) => {
+                        error!("{err:?}: shutdown stateful consumer: {err}");
+                        return Err(err);
+                    }
+                    Some(Err(err)) => error!("Error while receiving message: {err}"),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::builder::{EventConsumerError, StateTransaction};
+use iggy::models::messages::PolledMessage;
+
+/// Trait for consumers that keep their state in a `StateStore`.
+#[allow(dead_code)] // Clippy can't see that the trait is used
+#[trait_variant::make(StatefulEventConsumer: Send)]
+pub trait LocalStatefulEventConsumer {
+    /// Consume a message and update the state.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to consume
+    /// * `state` - Reads and writes the state. The writes are committed with the offset of the message.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the message cannot be consumed. The writes are discarded.
+    async fn consume(
+        &self,
+        message: PolledMessage,
+        state: &mut StateTransaction<'_>,
+    ) -> Result<(), EventConsumerError>;
+}
@@ -0,0 +1,237 @@
+use crate::builder::iggy_consumer_ext::{report_failure, run_with_retries};
+use crate::builder::{
+    decompress_polled_message_with_limit, is_fatal_client_error, ConsumeFailure,
+    ConsumeFailureKind, ConsumeMessagesConfig, EventConsumerError, EventProducer,
+    IggyStreamConsumer, IggyStreamProcessorConfig, IggyStreamProducer, StreamPipeline,
+};
+use futures_util::StreamExt;
+use iggy::clients::client::IggyClient;
+use iggy::clients::consumer::IggyConsumer;
+use iggy::clients::producer::IggyProducer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+/// Reads an input topic, runs every message through a `StreamPipeline` and sends the
+/// output messages to an `EventProducer`.
+///
+/// The input consumer is built with auto-commit disabled, and the offset of an input
+/// message is stored only after all of its output messages have been sent. If sending
+/// fails, `run` stops without storing the offset, so the message is processed again
+/// after a restart. Wrap the output producer in a `RetryingEventProducer` to retry
+/// transient send errors instead.
+///
+/// Input messages that fail in the pipeline are handled like failed messages in
+/// `consume_messages_with_config`: the pipeline is retried with the handler timeout and
+/// retries of the `ConsumeMessagesConfig`, then the message is reported to its failure
+/// channel and skipped. The offset of a skipped message is stored like the offset of a
+/// message that a filter dropped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = IggyStreamProcessorConfig::from_stream_topics("orders", "raw", "orders", "enriched", 100, send_interval, polling_interval);
+/// let pipeline = StreamPipeline::new().filter(is_valid).transform(enrich);
+/// let (processor, consumer) = IggyStreamProcessor::new(&client, &config, pipeline).await?;
+/// processor.run(consumer, shutdown_rx).await?;
+/// ```
+#[derive(Debug)]
+pub struct IggyStreamProcessor<P> {
+    pipeline: StreamPipeline,
+    producer: P,
+    consume_config: ConsumeMessagesConfig,
+}
+
+impl IggyStreamProcessor<IggyProducer> {
+    /// Builds the output producer and the input consumer from the given config.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The Iggy client to use to connect to the Iggy server.
+    /// * `config` - The input and output configuration of the processor.
+    /// * `pipeline` - The stages to run on every input message.
+    ///
+    /// Returns:
+    /// The processor and the input consumer to pass to `run`.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the client is not connected or the producer or consumer cannot be built.
+    ///
+    #[allow(clippy::new_ret_no_self)]
+    pub async fn new(
+        client: &IggyClient,
+        config: &IggyStreamProcessorConfig,
+        pipeline: StreamPipeline,
+    ) -> Result<(Self, IggyConsumer), IggyError> {
+        info!("Build output producer");
+        let producer = IggyStreamProducer::new(client, config.output_config()).await?;
+
+        info!("Build input consumer");
+        let input_config = config.input_config_without_auto_commit();
+        let consumer = IggyStreamConsumer::new(client, &input_config).await?;
+
+        Ok((Self::with_producer(producer, pipeline), consumer))
+    }
+}
+
+impl<P> IggyStreamProcessor<P>
+where
+    P: EventProducer + Sync,
+{
+    /// Creates a new `IggyStreamProcessor` that sends the output messages to the given producer.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` of the output topic.
+    /// * `pipeline` - The stages to run on every input message.
+    ///
+    pub fn with_producer(producer: P, pipeline: StreamPipeline) -> Self {
+        Self {
+            pipeline,
+            producer,
+            consume_config: ConsumeMessagesConfig::default(),
+        }
+    }
+
+    /// Sets the handler timeout, retries and failure channel for input messages that
+    /// fail in the pipeline. By default a failed message is reported and skipped at once.
+    pub fn with_consume_config(mut self, consume_config: ConsumeMessagesConfig) -> Self {
+        self.consume_config = consume_config;
+        self
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Returns the pipeline of the processor.
+    pub fn pipeline(&self) -> &StreamPipeline {
+        &self.pipeline
+    }
+
+    /// Returns the failure handling of the processor.
+    pub fn consume_config(&self) -> &ConsumeMessagesConfig {
+        &self.consume_config
+    }
+
+    /// Runs the pipeline on a single input message and sends the output messages.
+    /// Compressed payloads are decompressed first.
+    ///
+    /// If the message cannot be decompressed or the pipeline fails all attempts, the
+    /// failure is reported to the failure channel of the consume config and the message
+    /// is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition of the input message.
+    /// * `message` - The input message.
+    ///
+    /// Returns:
+    /// The number of sent output messages. Zero if the message was dropped or skipped.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the output messages cannot be sent.
+    ///
+    pub async fn process(
+        &self,
+        partition_id: u32,
+        message: PolledMessage,
+    ) -> Result<usize, IggyError> {
+        let config = &self.consume_config;
+        let offset = message.offset;
+        let message =
+            match decompress_polled_message_with_limit(message, config.max_decompressed_size()) {
+                Ok(message) => message,
+                Err(err) => {
+                    let failure = ConsumeFailure {
+                        partition_id,
+                        offset,
+                        kind: ConsumeFailureKind::Decompression,
+                        error: EventConsumerError::new(err.to_string()),
+                        attempts: 0,
+                    };
+                    report_failure(config, failure);
+                    return Ok(0);
+                }
+            };
+
+        let input = Message::new(Some(message.id), message.payload, message.headers);
+        let pipeline = &self.pipeline;
+        let input = &input;
+        let outputs = match run_with_retries(config, partition_id, offset, move || {
+            pipeline.process(input.clone())
+        })
+        .await
+        {
+            Ok(outputs) => outputs,
+            Err(failure) => {
+                report_failure(config, failure);
+                return Ok(0);
+            }
+        };
+
+        let produced = outputs.len();
+        match produced {
+            0 => {}
+            1 => {
+                let output = outputs.into_iter().next().expect("One output message");
+                self.producer.send_one_event(output).await?
+            }
+            _ => self.producer.send_event_batch(outputs).await?,
+        }
+
+        Ok(produced)
+    }
+
+    /// Processes the input messages of the consumer until a shutdown signal is received
+    /// or the consumer ends, storing the offset of every processed input message.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the input topic, built with auto-commit disabled.
+    /// * `shutdown_rx` - Stops the processor when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If output messages or offsets cannot be sent, or the client disconnected.
+    ///
+    pub async fn run(
+        &self,
+        mut consumer: IggyConsumer,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    info!("Received shutdown signal, stopping stream processor");
+                    break;
+                }
+
+                message = consumer.next() => match message {
+                    Some(Ok(received_message)) => {
+                        let partition_id = received_message.partition_id;
+                        let offset = received_message.message.offset;
+                        if let Err(err) = self.process(partition_id, received_message.message).await {
+                            error!("Failed to send output of offset: {offset} in partition: {partition_id}: {err}");
+                            return Err(err);
+                        }
+                        consumer.store_offset(offset, Some(partition_id)).await?;
+                    }
+                    Some(Err(err)) if is_fatal_client_error(&err) => {
+                        error!("{err:?}: shutdown stream processor: {err}");
+                        return Err(err);
+                    }
+                    Some(Err(err)) => error!("Error while receiving input message: {err}"),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
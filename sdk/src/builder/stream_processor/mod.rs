@@ -0,0 +1,5 @@
+mod iggy_stream_processor;
+mod stream_pipeline;
+
+pub use iggy_stream_processor::IggyStreamProcessor;
+pub use stream_pipeline::StreamPipeline;
@@ -0,0 +1,155 @@
+use crate::builder::EventConsumerError;
+use futures::future::BoxFuture;
+use iggy::messages::send_messages::Message;
+use std::fmt;
+use std::future::Future;
+
+type MapFn = Box<dyn Fn(Message) -> Message + Send + Sync>;
+type FilterFn = Box<dyn Fn(&Message) -> bool + Send + Sync>;
+type FlatMapFn = Box<dyn Fn(Message) -> Vec<Message> + Send + Sync>;
+type TransformFn =
+    Box<dyn Fn(Message) -> BoxFuture<'static, Result<Message, EventConsumerError>> + Send + Sync>;
+
+enum Stage {
+    Map(MapFn),
+    Filter(FilterFn),
+    FlatMap(FlatMapFn),
+    Transform(TransformFn),
+}
+
+impl Stage {
+    fn name(&self) -> &'static str {
+        match self {
+            Stage::Map(_) => "map",
+            Stage::Filter(_) => "filter",
+            Stage::FlatMap(_) => "flat_map",
+            Stage::Transform(_) => "transform",
+        }
+    }
+}
+
+/// An ordered list of stages that turns one input message into zero or more output messages.
+///
+/// The first stage receives the input message with its ID, headers and payload. Each stage
+/// runs on every message produced by the previous stage; once no message is left, the
+/// remaining stages are skipped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let pipeline = StreamPipeline::new()
+///     .filter(|message| !message.payload.is_empty())
+///     .map(|message| Message::new(Some(message.id), message.payload.to_ascii_uppercase().into(), None))
+///     .transform(|message| async move { enrich(message).await });
+/// ```
+#[derive(Default)]
+pub struct StreamPipeline {
+    stages: Vec<Stage>,
+}
+
+impl fmt::Debug for StreamPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamPipeline")
+            .field(
+                "stages",
+                &self.stages.iter().map(Stage::name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl StreamPipeline {
+    /// Creates an empty `StreamPipeline` that passes every message through unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stage that replaces every message with the result of the given function.
+    pub fn map<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Message) -> Message + Send + Sync + 'static,
+    {
+        self.stages.push(Stage::Map(Box::new(f)));
+        self
+    }
+
+    /// Adds a stage that drops every message for which the given predicate returns `false`.
+    pub fn filter<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
+        self.stages.push(Stage::Filter(Box::new(f)));
+        self
+    }
+
+    /// Adds a stage that replaces every message with all messages returned by the given function.
+    pub fn flat_map<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Message) -> Vec<Message> + Send + Sync + 'static,
+    {
+        self.stages.push(Stage::FlatMap(Box::new(f)));
+        self
+    }
+
+    /// Adds a stage that replaces every message with the result of the given async function,
+    /// e.g. to enrich the message with data from another service.
+    ///
+    /// If the function fails, the pipeline fails for the input message.
+    pub fn transform<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Message, EventConsumerError>> + Send + 'static,
+    {
+        self.stages.push(Stage::Transform(Box::new(move |message| {
+            Box::pin(f(message))
+        })));
+        self
+    }
+
+    /// Returns the number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns `true` if the pipeline has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Runs all stages on the given message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The input message.
+    ///
+    /// Returns:
+    /// The output messages in order. The list is empty if a filter dropped the message.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a transform stage failed.
+    ///
+    pub async fn process(&self, message: Message) -> Result<Vec<Message>, EventConsumerError> {
+        let mut messages = vec![message];
+        for stage in &self.stages {
+            messages = match stage {
+                Stage::Map(f) => messages.into_iter().map(f).collect(),
+                Stage::Filter(f) => messages.into_iter().filter(|message| f(message)).collect(),
+                Stage::FlatMap(f) => messages.into_iter().flat_map(f).collect(),
+                Stage::Transform(f) => {
+                    let mut transformed = Vec::with_capacity(messages.len());
+                    for message in messages {
+                        transformed.push(f(message).await?);
+                    }
+                    transformed
+                }
+            };
+
+            if messages.is_empty() {
+                break;
+            }
+        }
+
+        Ok(messages)
+    }
+}
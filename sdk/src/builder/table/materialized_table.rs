@@ -0,0 +1,325 @@
+use crate::builder::partition_poll::poll_partitions;
+use crate::builder::topic_details::get_topic_details;
+use crate::builder::{
+    decompress_polled_message, Checkpoint, MaterializedTableConfig, StateChanges, StateStore,
+    StateTransaction, TableChange, WindowKeyExtractor,
+};
+use bytes::Bytes;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::{broadcast, oneshot, watch};
+use tracing::{error, info};
+
+/// The latest value of each key of a topic, kept in a `StateStore`.
+///
+/// The table reads every partition of the source topic from the beginning, or from the
+/// checkpoints of an on-disk store, and keeps following it. A message with an empty payload
+/// deletes its key. Every change is published to the subscribers.
+///
+/// The table is caught up once it applied every message that was in the topic when `run`
+/// started; lookups before that may return outdated values.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let table = Arc::new(MaterializedTable::new(config, InMemoryStateStore::new(), HeaderWindowKey));
+/// tokio::spawn({
+///     let table = table.clone();
+///     async move { table.run(&client, shutdown_rx).await }
+/// });
+///
+/// table.wait_caught_up().await;
+/// let price = table.get("EUR/USD")?;
+/// ```
+pub struct MaterializedTable<S> {
+    config: MaterializedTableConfig,
+    store: S,
+    key_extractor: Box<dyn WindowKeyExtractor>,
+    changes: broadcast::Sender<TableChange>,
+    head_offsets: Mutex<Option<HashMap<u32, u64>>>,
+    caught_up: watch::Sender<bool>,
+    skipped_messages: AtomicU64,
+}
+
+impl<S: StateStore> fmt::Debug for MaterializedTable<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaterializedTable")
+            .field("config", &self.config)
+            .field("caught_up", &self.is_caught_up())
+            .field("skipped_messages", &self.skipped_messages())
+            .finish()
+    }
+}
+
+impl<S> MaterializedTable<S>
+where
+    S: StateStore,
+{
+    /// Creates a new `MaterializedTable`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The source topic and the capacity of the change subscriptions.
+    /// * `store` - Stores the rows, e.g. `InMemoryStateStore` or `SqliteStateStore`.
+    /// * `key_extractor` - Derives the key of a message. Messages without a key are skipped.
+    ///
+    pub fn new(
+        config: MaterializedTableConfig,
+        store: S,
+        key_extractor: impl WindowKeyExtractor + 'static,
+    ) -> Self {
+        let (changes, _) = broadcast::channel(config.changes_capacity().max(1));
+        let (caught_up, _) = watch::channel(false);
+
+        Self {
+            config,
+            store,
+            key_extractor: Box::new(key_extractor),
+            changes,
+            head_offsets: Mutex::new(None),
+            caught_up,
+            skipped_messages: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the table configuration.
+    pub fn config(&self) -> &MaterializedTableConfig {
+        &self.config
+    }
+
+    /// Returns the store of the rows.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the latest value of the key.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read.
+    ///
+    pub fn get(&self, key: &str) -> Result<Option<Bytes>, IggyError> {
+        Ok(self.store.get(key.as_bytes())?.map(Bytes::from))
+    }
+
+    /// Subscribes to the changes applied from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<TableChange> {
+        self.changes.subscribe()
+    }
+
+    /// Returns `true` once the table applied every message that was in the topic when it started.
+    pub fn is_caught_up(&self) -> bool {
+        *self.caught_up.borrow()
+    }
+
+    /// Returns a receiver that changes to `true` once the table is caught up.
+    pub fn caught_up(&self) -> watch::Receiver<bool> {
+        self.caught_up.subscribe()
+    }
+
+    /// Waits until the table is caught up.
+    pub async fn wait_caught_up(&self) {
+        let mut caught_up = self.caught_up.subscribe();
+        // The sender lives as long as the table, so the wait cannot fail.
+        let _ = caught_up.wait_for(|caught_up| *caught_up).await;
+    }
+
+    /// Returns the number of messages skipped because they had no key or could not be decompressed.
+    pub fn skipped_messages(&self) -> u64 {
+        self.skipped_messages.load(Ordering::Relaxed)
+    }
+
+    /// Sets the offsets the table must apply before it is caught up.
+    ///
+    /// `run` sets them to the latest offsets of the topic when it starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `head_offsets` - The partition IDs with the offset of their latest message,
+    ///   `None` for an empty partition.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the checkpoints cannot be read.
+    ///
+    pub fn set_head_offsets(
+        &self,
+        head_offsets: impl IntoIterator<Item = (u32, Option<u64>)>,
+    ) -> Result<(), IggyError> {
+        let head_offsets = head_offsets
+            .into_iter()
+            .filter_map(|(partition_id, offset)| offset.map(|offset| (partition_id, offset)))
+            .collect();
+        *self.head_offsets.lock().unwrap() = Some(head_offsets);
+        self.update_caught_up()
+    }
+
+    /// Applies a single message to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_id` - The partition of the message.
+    /// * `message` - The message to apply.
+    ///
+    /// Returns:
+    /// The change, or `None` if the message was skipped or is at or before the checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the store cannot be read or written.
+    ///
+    pub fn apply(
+        &self,
+        partition_id: u32,
+        message: &PolledMessage,
+    ) -> Result<Option<TableChange>, IggyError> {
+        let offset = message.offset;
+        if self
+            .store
+            .checkpoint(partition_id)?
+            .is_some_and(|checkpoint| offset <= checkpoint)
+        {
+            return Ok(None);
+        }
+
+        let Some(key) = self.key_extractor.window_key(message) else {
+            self.skip(partition_id, offset)?;
+            return Ok(None);
+        };
+
+        let mut state = StateTransaction::new(&self.store);
+        let previous = state.get(&key)?.map(Bytes::from);
+        let value = if message.payload.is_empty() {
+            state.delete(&key);
+            None
+        } else {
+            state.put(&key, message.payload.to_vec());
+            Some(message.payload.clone())
+        };
+        self.commit(&state.into_changes(), partition_id, offset)?;
+
+        let change = TableChange {
+            key,
+            previous,
+            value,
+            partition_id,
+            offset,
+        };
+        // Sending only fails without subscribers.
+        let _ = self.changes.send(change.clone());
+        Ok(Some(change))
+    }
+
+    /// Reads the source topic until a shutdown signal is received.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `IggyClient` to use.
+    /// * `shutdown_rx` - Stops the table when a value is received.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the topic does not exist, the store fails or the client disconnected.
+    ///
+    pub async fn run(
+        &self,
+        client: &IggyClient,
+        shutdown_rx: oneshot::Receiver<()>,
+    ) -> Result<(), IggyError> {
+        let config = self.config.source_config();
+        let topic = get_topic_details(client, config).await?;
+        self.set_head_offsets(topic.partitions.iter().map(|partition| {
+            let latest_offset = if partition.messages_count == 0 {
+                None
+            } else {
+                Some(partition.current_offset)
+            };
+            (partition.id, latest_offset)
+        }))?;
+
+        let mut next_offsets = BTreeMap::new();
+        for partition in &topic.partitions {
+            let next_offset = self
+                .store
+                .checkpoint(partition.id)?
+                .map_or(0, |offset| offset + 1);
+            next_offsets.insert(partition.id, next_offset);
+        }
+
+        info!(
+            "Materializing table from topic: {} in stream: {}",
+            config.topic_name(),
+            config.stream_name()
+        );
+        poll_partitions(
+            client,
+            config,
+            next_offsets,
+            "materialized table",
+            shutdown_rx,
+            |partition_id, messages| async move {
+                let mut next_offset = None;
+                for message in messages {
+                    let offset = message.offset;
+                    match decompress_polled_message(message) {
+                        Ok(message) => self.apply(partition_id, &message)?,
+                        Err(err) => {
+                            error!("Error while decompressing message: {err}");
+                            self.skip(partition_id, offset)?;
+                            None
+                        }
+                    };
+                    next_offset = Some(offset + 1);
+                }
+                Ok(next_offset)
+            },
+        )
+        .await
+    }
+
+    fn skip(&self, partition_id: u32, offset: u64) -> Result<(), IggyError> {
+        self.skipped_messages.fetch_add(1, Ordering::Relaxed);
+        self.commit(&StateChanges::default(), partition_id, offset)
+    }
+
+    fn commit(
+        &self,
+        changes: &StateChanges,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        let checkpoint = Checkpoint {
+            partition_id,
+            offset,
+        };
+        self.store.commit(changes, checkpoint)?;
+        self.update_caught_up()
+    }
+
+    fn update_caught_up(&self) -> Result<(), IggyError> {
+        if self.is_caught_up() {
+            return Ok(());
+        }
+
+        let head_offsets = self.head_offsets.lock().unwrap();
+        let Some(head_offsets) = head_offsets.as_ref() else {
+            return Ok(());
+        };
+
+        for (&partition_id, &head_offset) in head_offsets {
+            let applied = self.store.checkpoint(partition_id)?;
+            if applied.is_none_or(|offset| offset < head_offset) {
+                return Ok(());
+            }
+        }
+
+        info!("Materialized table caught up");
+        self.caught_up.send_replace(true);
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod materialized_table;
+mod table_change;
+
+pub use materialized_table::MaterializedTable;
+pub use table_change::TableChange;
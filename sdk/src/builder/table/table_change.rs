@@ -0,0 +1,16 @@
+use bytes::Bytes;
+
+/// A change of a single key of a `MaterializedTable`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TableChange {
+    /// The key of the changed row.
+    pub key: String,
+    /// The value before the change, `None` if the key was new.
+    pub previous: Option<Bytes>,
+    /// The new value, `None` if the key was deleted.
+    pub value: Option<Bytes>,
+    /// The partition of the message that caused the change.
+    pub partition_id: u32,
+    /// The offset of the message that caused the change.
+    pub offset: u64,
+}
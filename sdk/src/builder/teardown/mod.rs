@@ -0,0 +1,6 @@
+mod teardown_guard;
+mod teardown_ops;
+
+pub use teardown_guard::TeardownGuard;
+pub use teardown_ops::is_not_found_error;
+pub(crate) use teardown_ops::{delete_consumer_group, delete_stream, delete_topic, purge_topic};
@@ -0,0 +1,125 @@
+use crate::builder::teardown::delete_stream;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use std::sync::Arc;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::{error, warn};
+
+/// Deletes a stream when dropped, so tests leave no streams behind even when they fail.
+///
+/// Call `teardown` at the end of the test to delete the stream and see the error, if any.
+/// If the guard is dropped without `teardown`, e.g. because the test panicked, the stream
+/// is deleted in `drop`, which blocks the current thread until the deletion completes.
+/// Blocking requires a multi-threaded Tokio runtime, so the guard can only be created on one;
+/// use `#[tokio::test(flavor = "multi_thread")]` instead of the current-thread default.
+///
+/// # Teardown
+///
+/// `IggyStream`, `IggyStreamProducer` and `IggyStreamConsumer` also delete the resources of
+/// their config directly with `delete_stream`, `delete_topic`, `purge_topic` and, with a
+/// consumer group, `delete_consumer_group`. The `client` is the `IggyClient` used to delete
+/// them, and `teardown_guard` takes a shared one. A resource that does not exist is not an
+/// error. If it exists but cannot be deleted or purged, an `IggyError` is returned, and
+/// `teardown_guard` fails outside of a multi-threaded Tokio runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[tokio::test(flavor = "multi_thread")]
+/// async fn test_orders() {
+///     let client = Arc::new(build_client().await);
+///     let guard = IggyStream::teardown_guard(client.clone(), &config).unwrap();
+///     let (producer, consumer) = IggyStream::new(&client, &config).await.unwrap();
+///     // ...
+///     guard.teardown().await.unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TeardownGuard {
+    client: Arc<IggyClient>,
+    stream_id: Option<Identifier>,
+}
+
+impl TeardownGuard {
+    /// Creates a new `TeardownGuard` that deletes the given stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The shared `IggyClient` used to delete the stream.
+    /// * `stream_id` - The stream to delete.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidConfiguration` - If not called on a multi-threaded Tokio runtime.
+    ///
+    pub fn new(client: Arc<IggyClient>, stream_id: Identifier) -> Result<Self, IggyError> {
+        let flavor = Handle::try_current().map(|handle| handle.runtime_flavor());
+        if !matches!(flavor, Ok(RuntimeFlavor::MultiThread)) {
+            error!("A teardown guard requires a multi-threaded Tokio runtime, found: {flavor:?}");
+            return Err(IggyError::InvalidConfiguration);
+        }
+
+        Ok(Self {
+            client,
+            stream_id: Some(stream_id),
+        })
+    }
+
+    /// Returns the client of the guard.
+    pub fn client(&self) -> &IggyClient {
+        &self.client
+    }
+
+    /// Deletes the stream now.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the stream exists but cannot be deleted.
+    ///
+    pub async fn teardown(mut self) -> Result<(), IggyError> {
+        match self.stream_id.take() {
+            Some(stream_id) => delete_stream(&self.client, &stream_id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the stream that is deleted when the guard is dropped.
+    pub fn stream_id(&self) -> Option<&Identifier> {
+        self.stream_id.as_ref()
+    }
+
+    /// Keeps the stream, e.g. to inspect it after a failed test.
+    ///
+    /// Returns:
+    /// The ID of the kept stream.
+    ///
+    pub fn disarm(mut self) -> Option<Identifier> {
+        self.stream_id.take()
+    }
+}
+
+impl Drop for TeardownGuard {
+    fn drop(&mut self) {
+        let Some(stream_id) = self.stream_id.take() else {
+            return;
+        };
+
+        let Ok(handle) = Handle::try_current() else {
+            warn!("No Tokio runtime, cannot delete stream: {stream_id}");
+            return;
+        };
+
+        if handle.runtime_flavor() != RuntimeFlavor::MultiThread {
+            warn!("Not on a multi-threaded Tokio runtime, cannot delete stream: {stream_id}");
+            return;
+        }
+
+        let res = tokio::task::block_in_place(|| {
+            handle.block_on(delete_stream(&self.client, &stream_id))
+        });
+        if let Err(err) = res {
+            error!("Failed to delete stream: {stream_id}: {err}");
+        }
+    }
+}
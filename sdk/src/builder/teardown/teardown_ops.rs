@@ -0,0 +1,94 @@
+use iggy::client::{ConsumerGroupClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use tracing::info;
+
+/// Deletes the stream with all of its topics and messages.
+///
+/// # Errors
+///
+/// * `IggyError` - If the stream exists but cannot be deleted.
+///
+pub(crate) async fn delete_stream(
+    client: &IggyClient,
+    stream_id: &Identifier,
+) -> Result<(), IggyError> {
+    info!("Delete stream: {stream_id}");
+    ignore_not_found(client.delete_stream(stream_id).await)
+}
+
+/// Deletes the topic with all of its messages.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic exists but cannot be deleted.
+///
+pub(crate) async fn delete_topic(
+    client: &IggyClient,
+    stream_id: &Identifier,
+    topic_id: &Identifier,
+) -> Result<(), IggyError> {
+    info!("Delete topic: {topic_id} in stream: {stream_id}");
+    ignore_not_found(client.delete_topic(stream_id, topic_id).await)
+}
+
+/// Deletes all messages of the topic and keeps the topic.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic exists but cannot be purged.
+///
+pub(crate) async fn purge_topic(
+    client: &IggyClient,
+    stream_id: &Identifier,
+    topic_id: &Identifier,
+) -> Result<(), IggyError> {
+    info!("Purge topic: {topic_id} in stream: {stream_id}");
+    ignore_not_found(client.purge_topic(stream_id, topic_id).await)
+}
+
+/// Deletes the consumer group and its stored offsets.
+///
+/// # Errors
+///
+/// * `IggyError` - If the consumer group exists but cannot be deleted.
+///
+pub(crate) async fn delete_consumer_group(
+    client: &IggyClient,
+    stream_id: &Identifier,
+    topic_id: &Identifier,
+    consumer_group: &str,
+) -> Result<(), IggyError> {
+    info!("Delete consumer group: {consumer_group} of topic: {topic_id} in stream: {stream_id}");
+    let group_id = Identifier::named(consumer_group)?;
+    ignore_not_found(
+        client
+            .delete_consumer_group(stream_id, topic_id, &group_id)
+            .await,
+    )
+}
+
+/// Returns `true` if the error reports that a stream, topic or consumer group does not exist.
+///
+/// Teardown is idempotent, so such errors are ignored when deleting resources.
+pub fn is_not_found_error(err: &IggyError) -> bool {
+    matches!(
+        err,
+        IggyError::ResourceNotFound(_)
+            | IggyError::StreamIdNotFound(_)
+            | IggyError::StreamNameNotFound(_)
+            | IggyError::TopicIdNotFound(_, _)
+            | IggyError::TopicNameNotFound(_, _)
+            | IggyError::ConsumerGroupIdNotFound(_, _)
+            | IggyError::ConsumerGroupNameNotFound(_, _)
+    )
+}
+
+/// Teardown is idempotent, so a resource that no longer exists is not an error.
+fn ignore_not_found(res: Result<(), IggyError>) -> Result<(), IggyError> {
+    match res {
+        Err(err) if is_not_found_error(&err) => Ok(()),
+        res => res,
+    }
+}
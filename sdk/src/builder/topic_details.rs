@@ -0,0 +1,27 @@
+use crate::builder::IggyConsumerConfig;
+use iggy::client::TopicClient;
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::models::topic::TopicDetails;
+
+/// Fetches the details of the configured topic, including its partitions.
+///
+/// # Errors
+///
+/// * `IggyError` - If the topic does not exist or the details cannot be fetched.
+///
+pub(crate) async fn get_topic_details(
+    client: &IggyClient,
+    config: &IggyConsumerConfig,
+) -> Result<TopicDetails, IggyError> {
+    match client
+        .get_topic(config.stream_id(), config.topic_id())
+        .await?
+    {
+        Some(topic) => Ok(topic),
+        None => Err(IggyError::TopicNameNotFound(
+            config.topic_name().to_string(),
+            config.stream_name().to_string(),
+        )),
+    }
+}
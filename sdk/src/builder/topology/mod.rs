@@ -0,0 +1,8 @@
+mod topology_apply;
+mod topology_plan;
+mod topology_spec;
+mod topology_state;
+
+pub use topology_plan::{TopologyChange, TopologyDrift, TopologyPlan};
+pub use topology_spec::{StreamSpec, TopicSpec, TopologySpec};
+pub use topology_state::{StreamState, TopicState, TopologyState};
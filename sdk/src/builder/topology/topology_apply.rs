@@ -0,0 +1,98 @@
+use crate::builder::{TopologyChange, TopologyPlan};
+use iggy::client::{ConsumerGroupClient, PartitionClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use tracing::{error, info};
+
+/// Makes the changes of the plan in order and stops at the first failure.
+///
+/// # Errors
+///
+/// * `IggyError` - If a stream, topic, partition or consumer group cannot be created,
+///   or a topic cannot be updated.
+///
+pub(crate) async fn apply_topology_plan(
+    client: &IggyClient,
+    plan: &TopologyPlan,
+) -> Result<(), IggyError> {
+    for change in plan.changes() {
+        info!("Apply: {change}");
+        if let Err(err) = apply_change(client, change).await {
+            error!("Failed to {change}: {err}");
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+async fn apply_change(client: &IggyClient, change: &TopologyChange) -> Result<(), IggyError> {
+    match change {
+        TopologyChange::CreateStream { stream } => {
+            client.create_stream(stream, None).await?;
+        }
+        TopologyChange::CreateTopic { stream, topic } => {
+            client
+                .create_topic(
+                    &Identifier::named(stream)?,
+                    topic.name(),
+                    topic.partitions_count(),
+                    topic.compression_algorithm().unwrap_or_default(),
+                    topic.replication_factor(),
+                    None,
+                    topic.message_expiry(),
+                    topic.max_topic_size(),
+                )
+                .await?;
+        }
+        TopologyChange::CreateConsumerGroup {
+            stream,
+            topic,
+            consumer_group,
+        } => {
+            client
+                .create_consumer_group(
+                    &Identifier::named(stream)?,
+                    &Identifier::named(topic)?,
+                    consumer_group,
+                    None,
+                )
+                .await?;
+        }
+        TopologyChange::UpdateTopic {
+            stream,
+            topic,
+            compression_algorithm,
+            replication_factor,
+            message_expiry,
+            max_topic_size,
+            ..
+        } => {
+            client
+                .update_topic(
+                    &Identifier::named(stream)?,
+                    &Identifier::named(topic)?,
+                    topic,
+                    *compression_algorithm,
+                    *replication_factor,
+                    *message_expiry,
+                    *max_topic_size,
+                )
+                .await?;
+        }
+        TopologyChange::CreatePartitions {
+            stream,
+            topic,
+            partitions_count,
+        } => {
+            client
+                .create_partitions(
+                    &Identifier::named(stream)?,
+                    &Identifier::named(topic)?,
+                    *partitions_count,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
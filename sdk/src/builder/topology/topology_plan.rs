@@ -0,0 +1,241 @@
+use crate::builder::{StreamState, TopicSpec, TopicState, TopologySpec, TopologyState};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use std::fmt;
+
+/// A change `TopologySpec::apply` makes on the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopologyChange {
+    /// Create the stream.
+    CreateStream { stream: String },
+    /// Create the topic with the settings of the spec.
+    CreateTopic { stream: String, topic: TopicSpec },
+    /// Create the consumer group.
+    CreateConsumerGroup {
+        stream: String,
+        topic: String,
+        consumer_group: String,
+    },
+    /// Update the settings of an existing topic. Settings left unset or at the server
+    /// default in the spec keep their value on the server.
+    UpdateTopic {
+        stream: String,
+        topic: String,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        /// The names of the settings that differ from the spec.
+        settings: Vec<&'static str>,
+    },
+    /// Add partitions to an existing topic.
+    CreatePartitions {
+        stream: String,
+        topic: String,
+        partitions_count: u32,
+    },
+}
+
+impl fmt::Display for TopologyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopologyChange::CreateStream { stream } => write!(f, "create stream {stream}"),
+            TopologyChange::CreateTopic { stream, topic } => write!(
+                f,
+                "create topic {stream}/{} with {} partitions",
+                topic.name(),
+                topic.partitions_count()
+            ),
+            TopologyChange::CreateConsumerGroup {
+                stream,
+                topic,
+                consumer_group,
+            } => write!(
+                f,
+                "create consumer group {consumer_group} on {stream}/{topic}"
+            ),
+            TopologyChange::UpdateTopic {
+                stream,
+                topic,
+                settings,
+                ..
+            } => write!(
+                f,
+                "update {} of topic {stream}/{topic}",
+                settings.join(", ")
+            ),
+            TopologyChange::CreatePartitions {
+                stream,
+                topic,
+                partitions_count,
+            } => write!(
+                f,
+                "add {partitions_count} partitions to topic {stream}/{topic}"
+            ),
+        }
+    }
+}
+
+/// A setting of an existing topic that differs from the spec and cannot be changed.
+///
+/// The only such setting is a partition count above the spec: partitions are never deleted,
+/// because that would delete their messages.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TopologyDrift {
+    /// The stream name.
+    pub stream: String,
+    /// The topic name.
+    pub topic: String,
+    /// The name of the setting, e.g. `partitions_count`.
+    pub setting: &'static str,
+    /// The value of the spec.
+    pub expected: String,
+    /// The value on the server.
+    pub actual: String,
+}
+
+impl fmt::Display for TopologyDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}: {} is {}, expected {}",
+            self.stream, self.topic, self.setting, self.actual, self.expected
+        )
+    }
+}
+
+/// The difference between a `TopologySpec` and the server.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TopologyPlan {
+    changes: Vec<TopologyChange>,
+    drift: Vec<TopologyDrift>,
+}
+
+impl TopologyPlan {
+    /// Compares the spec with the given state of the server.
+    ///
+    /// The changes are ordered so that every stream is created before its topics
+    /// and every topic before its consumer groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The desired topology.
+    /// * `state` - The current state of the server.
+    ///
+    pub fn diff(spec: &TopologySpec, state: &TopologyState) -> Self {
+        let mut plan = Self::default();
+        for stream_spec in spec.streams() {
+            let stream = stream_spec.name();
+            let stream_state = state.stream(stream);
+            if stream_state.is_none() {
+                plan.changes.push(TopologyChange::CreateStream {
+                    stream: stream.to_string(),
+                });
+            }
+
+            for topic_spec in stream_spec.topics() {
+                plan.diff_topic(stream, topic_spec, stream_state);
+            }
+        }
+        plan
+    }
+
+    fn diff_topic(&mut self, stream: &str, spec: &TopicSpec, stream_state: Option<&StreamState>) {
+        let topic_state = stream_state.and_then(|state| state.topic(spec.name()));
+        match topic_state {
+            Some(state) => self.diff_topic_settings(stream, spec, state),
+            None => self.changes.push(TopologyChange::CreateTopic {
+                stream: stream.to_string(),
+                topic: spec.clone(),
+            }),
+        }
+
+        for consumer_group in spec.consumer_groups() {
+            let exists =
+                topic_state.is_some_and(|state| state.consumer_groups.contains(consumer_group));
+            if !exists {
+                self.changes.push(TopologyChange::CreateConsumerGroup {
+                    stream: stream.to_string(),
+                    topic: spec.name().to_string(),
+                    consumer_group: consumer_group.clone(),
+                });
+            }
+        }
+    }
+
+    fn diff_topic_settings(&mut self, stream: &str, spec: &TopicSpec, state: &TopicState) {
+        if spec.partitions_count() > state.partitions_count {
+            self.changes.push(TopologyChange::CreatePartitions {
+                stream: stream.to_string(),
+                topic: spec.name().to_string(),
+                partitions_count: spec.partitions_count() - state.partitions_count,
+            });
+        } else if spec.partitions_count() < state.partitions_count {
+            self.drift.push(TopologyDrift {
+                stream: stream.to_string(),
+                topic: spec.name().to_string(),
+                setting: "partitions_count",
+                expected: spec.partitions_count().to_string(),
+                actual: state.partitions_count.to_string(),
+            });
+        }
+
+        // Settings left unset or at the server default keep their value on the server.
+        let compression_algorithm = spec
+            .compression_algorithm()
+            .unwrap_or(state.compression_algorithm);
+        let replication_factor = spec
+            .replication_factor()
+            .unwrap_or(state.replication_factor);
+        let message_expiry = match spec.message_expiry() {
+            IggyExpiry::ServerDefault => state.message_expiry,
+            message_expiry => message_expiry,
+        };
+        let max_topic_size = match spec.max_topic_size() {
+            MaxTopicSize::ServerDefault => state.max_topic_size,
+            max_topic_size => max_topic_size,
+        };
+
+        let mut settings = Vec::new();
+        if compression_algorithm != state.compression_algorithm {
+            settings.push("compression_algorithm");
+        }
+        if replication_factor != state.replication_factor {
+            settings.push("replication_factor");
+        }
+        if message_expiry != state.message_expiry {
+            settings.push("message_expiry");
+        }
+        if max_topic_size != state.max_topic_size {
+            settings.push("max_topic_size");
+        }
+
+        if !settings.is_empty() {
+            self.changes.push(TopologyChange::UpdateTopic {
+                stream: stream.to_string(),
+                topic: spec.name().to_string(),
+                compression_algorithm,
+                replication_factor: Some(replication_factor),
+                message_expiry,
+                max_topic_size,
+                settings,
+            });
+        }
+    }
+
+    /// Returns the changes to make, in the order they are applied.
+    pub fn changes(&self) -> &[TopologyChange] {
+        &self.changes
+    }
+
+    /// Returns the settings of existing topics that differ from the spec.
+    pub fn drift(&self) -> &[TopologyDrift] {
+        &self.drift
+    }
+
+    /// Returns `true` if the server matches the spec.
+    pub fn is_in_sync(&self) -> bool {
+        self.changes.is_empty() && self.drift.is_empty()
+    }
+}
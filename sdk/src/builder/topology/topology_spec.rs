@@ -0,0 +1,197 @@
+use crate::builder::topology::topology_apply::apply_topology_plan;
+use crate::builder::topology::topology_state::fetch_topology_state;
+use crate::builder::TopologyPlan;
+use bon::Builder;
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use tracing::{info, warn};
+
+/// Declarative description of the streams, topics and consumer groups an application needs.
+///
+/// `plan` compares the spec with the server and `apply` creates everything that is missing
+/// and updates the settings of existing topics that differ.
+/// The spec is additive: streams, topics and consumer groups on the server that are not
+/// in the spec are left alone.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let spec = TopologySpec::builder()
+///     .streams(vec![StreamSpec::builder()
+///         .name("orders")
+///         .topics(vec![TopicSpec::builder()
+///             .name("created")
+///             .partitions_count(3)
+///             .consumer_groups(vec!["billing".to_string()])
+///             .build()])
+///         .build()])
+///     .build();
+///
+/// let plan = spec.apply(&client).await?;
+/// assert!(plan.drift().is_empty());
+/// ```
+#[derive(Builder, Debug, Default, Clone, PartialEq)]
+pub struct TopologySpec {
+    #[builder(default)]
+    streams: Vec<StreamSpec>,
+}
+
+impl TopologySpec {
+    pub fn new(streams: Vec<StreamSpec>) -> Self {
+        Self { streams }
+    }
+
+    pub fn streams(&self) -> &[StreamSpec] {
+        &self.streams
+    }
+
+    /// Compares the spec with the current state of the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `IggyClient` used to fetch the streams, topics and consumer groups.
+    ///
+    /// Returns:
+    /// The changes `apply` would make and the drift it cannot fix.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the current state cannot be fetched.
+    ///
+    pub async fn plan(&self, client: &IggyClient) -> Result<TopologyPlan, IggyError> {
+        info!("Fetch current topology");
+        let state = fetch_topology_state(client, self).await?;
+        Ok(TopologyPlan::diff(self, &state))
+    }
+
+    /// Creates the missing streams, topics, partitions and consumer groups, and updates
+    /// the settings of existing topics that differ from the spec.
+    ///
+    /// Partitions are never deleted: a topic with more partitions than the spec is logged
+    /// and returned as drift.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `IggyClient` used to fetch and create the streams, topics and consumer groups.
+    ///
+    /// Returns:
+    /// The applied plan with the drift that remains.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError` - If the current state cannot be fetched or a change fails.
+    ///
+    pub async fn apply(&self, client: &IggyClient) -> Result<TopologyPlan, IggyError> {
+        let plan = self.plan(client).await?;
+
+        info!("Apply {} topology changes", plan.changes().len());
+        apply_topology_plan(client, &plan).await?;
+
+        for drift in plan.drift() {
+            warn!("Topology drift: {drift}");
+        }
+
+        Ok(plan)
+    }
+}
+
+/// A stream and its topics.
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(on(String, into))]
+pub struct StreamSpec {
+    name: String,
+    #[builder(default)]
+    topics: Vec<TopicSpec>,
+}
+
+impl StreamSpec {
+    pub fn new(name: impl Into<String>, topics: Vec<TopicSpec>) -> Self {
+        Self {
+            name: name.into(),
+            topics,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn topics(&self) -> &[TopicSpec] {
+        &self.topics
+    }
+}
+
+/// A topic, its settings and its consumer groups.
+///
+/// Settings left unset or at the server default are not compared with the server.
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(on(String, into))]
+pub struct TopicSpec {
+    name: String,
+    #[builder(default = 1)]
+    partitions_count: u32,
+    replication_factor: Option<u8>,
+    #[builder(default)]
+    message_expiry: IggyExpiry,
+    compression_algorithm: Option<CompressionAlgorithm>,
+    #[builder(default)]
+    max_topic_size: MaxTopicSize,
+    #[builder(default)]
+    consumer_groups: Vec<String>,
+}
+
+impl TopicSpec {
+    /// Creates a new `TopicSpec` with the given partition count and server defaults
+    /// for all other settings.
+    ///
+    /// # Args
+    ///
+    /// * `name` - The topic name.
+    /// * `partitions_count` - The number of partitions.
+    ///
+    pub fn new(name: impl Into<String>, partitions_count: u32) -> Self {
+        Self {
+            name: name.into(),
+            partitions_count,
+            replication_factor: None,
+            message_expiry: IggyExpiry::ServerDefault,
+            compression_algorithm: None,
+            max_topic_size: MaxTopicSize::ServerDefault,
+            consumer_groups: Vec::new(),
+        }
+    }
+}
+
+// Getters.
+impl TopicSpec {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn partitions_count(&self) -> u32 {
+        self.partitions_count
+    }
+
+    pub fn replication_factor(&self) -> Option<u8> {
+        self.replication_factor
+    }
+
+    pub fn message_expiry(&self) -> IggyExpiry {
+        self.message_expiry
+    }
+
+    pub fn compression_algorithm(&self) -> Option<CompressionAlgorithm> {
+        self.compression_algorithm
+    }
+
+    pub fn max_topic_size(&self) -> MaxTopicSize {
+        self.max_topic_size
+    }
+
+    pub fn consumer_groups(&self) -> &[String] {
+        &self.consumer_groups
+    }
+}
@@ -0,0 +1,105 @@
+use crate::builder::TopologySpec;
+use iggy::client::{ConsumerGroupClient, StreamClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+
+/// The streams of a `TopologySpec` as they exist on the server.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TopologyState {
+    /// The streams that exist on the server.
+    pub streams: Vec<StreamState>,
+}
+
+impl TopologyState {
+    /// Returns the stream with the given name, if it exists.
+    pub fn stream(&self, name: &str) -> Option<&StreamState> {
+        self.streams.iter().find(|stream| stream.name == name)
+    }
+}
+
+/// A stream as it exists on the server.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StreamState {
+    /// The stream name.
+    pub name: String,
+    /// All topics of the stream.
+    pub topics: Vec<TopicState>,
+}
+
+impl StreamState {
+    /// Returns the topic with the given name, if it exists.
+    pub fn topic(&self, name: &str) -> Option<&TopicState> {
+        self.topics.iter().find(|topic| topic.name == name)
+    }
+}
+
+/// A topic as it exists on the server.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TopicState {
+    /// The topic name.
+    pub name: String,
+    /// The number of partitions.
+    pub partitions_count: u32,
+    /// The replication factor.
+    pub replication_factor: u8,
+    /// The message expiry.
+    pub message_expiry: IggyExpiry,
+    /// The compression algorithm.
+    pub compression_algorithm: CompressionAlgorithm,
+    /// The max size of the topic.
+    pub max_topic_size: MaxTopicSize,
+    /// The names of the consumer groups of the topic.
+    pub consumer_groups: Vec<String>,
+}
+
+/// Fetches the state of every stream of the spec that exists on the server, with all
+/// of its topics and their consumer groups.
+///
+/// # Errors
+///
+/// * `IggyError` - If the streams, topics or consumer groups cannot be fetched.
+///
+pub(crate) async fn fetch_topology_state(
+    client: &IggyClient,
+    spec: &TopologySpec,
+) -> Result<TopologyState, IggyError> {
+    let mut streams = Vec::with_capacity(spec.streams().len());
+    for stream_spec in spec.streams() {
+        let stream_id = Identifier::named(stream_spec.name())?;
+        let Some(stream) = client.get_stream(&stream_id).await? else {
+            continue;
+        };
+
+        let mut topics = Vec::with_capacity(stream.topics.len());
+        for topic in stream.topics {
+            let topic_id = Identifier::numeric(topic.id)?;
+            let consumer_groups = client
+                .get_consumer_groups(&stream_id, &topic_id)
+                .await?
+                .into_iter()
+                .map(|group| group.name)
+                .collect();
+
+            topics.push(TopicState {
+                name: topic.name,
+                partitions_count: topic.partitions_count,
+                replication_factor: topic.replication_factor,
+                message_expiry: topic.message_expiry,
+                compression_algorithm: topic.compression_algorithm,
+                max_topic_size: topic.max_topic_size,
+                consumer_groups,
+            });
+        }
+
+        streams.push(StreamState {
+            name: stream.name,
+            topics,
+        });
+    }
+
+    Ok(TopologyState { streams })
+}
@@ -0,0 +1,36 @@
+use iggy::error::IggyError;
+
+/// Returns `true` if the error is caused by a lost or not yet established connection
+/// to the server, so the same request may succeed once the connection returns.
+///
+/// All other errors, e.g. a missing topic or an invalid message, fail again on retry.
+pub fn is_transient_error(err: &IggyError) -> bool {
+    matches!(
+        err,
+        IggyError::Disconnected
+            | IggyError::CannotEstablishConnection
+            | IggyError::StaleClient
+            | IggyError::TcpError
+            | IggyError::QuicError
+            | IggyError::NotConnected
+            | IggyError::ConnectionClosed
+            | IggyError::Unauthenticated
+    )
+}
+
+/// Returns `true` if the error means the client lost its connection or was shut down,
+/// so a consumer loop cannot continue and must return the error.
+///
+/// All other errors while polling are logged and the loop keeps running.
+pub fn is_fatal_client_error(err: &IggyError) -> bool {
+    matches!(
+        err,
+        IggyError::Disconnected
+            | IggyError::CannotEstablishConnection
+            | IggyError::StaleClient
+            | IggyError::InvalidServerAddress
+            | IggyError::InvalidClientAddress
+            | IggyError::NotConnected
+            | IggyError::ClientShutdown
+    )
+}
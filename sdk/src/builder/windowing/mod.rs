@@ -0,0 +1,15 @@
+mod window;
+mod window_aggregate;
+mod window_extractors;
+mod window_result_handler;
+mod windowed_aggregation;
+mod windowed_event_consumer;
+
+pub use window::{Window, WindowResult};
+pub use window_aggregate::{CountAggregate, WindowAggregate};
+pub use window_extractors::{
+    EventTimeExtractor, HeaderEventTime, HeaderWindowKey, MessageTimestamp, WindowKeyExtractor,
+};
+pub use window_result_handler::{ProducerWindowHandler, WindowResultHandler};
+pub use windowed_aggregation::WindowedAggregation;
+pub use windowed_event_consumer::WindowedEventConsumer;
@@ -0,0 +1,36 @@
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// A time window. The start is inclusive and the end is exclusive.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Window {
+    /// The event time of the start of the window.
+    pub start: IggyTimestamp,
+    /// The event time of the end of the window.
+    pub end: IggyTimestamp,
+}
+
+impl Window {
+    pub(crate) fn from_micros(start: u64, end: u64) -> Self {
+        Self {
+            start: IggyTimestamp::from(start),
+            end: IggyTimestamp::from(end),
+        }
+    }
+
+    /// Returns `true` if the event time is inside the window.
+    pub fn contains(&self, event_time: IggyTimestamp) -> bool {
+        let event_time = event_time.as_micros();
+        self.start.as_micros() <= event_time && event_time < self.end.as_micros()
+    }
+}
+
+/// The aggregated value of one key in one closed window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowResult<A> {
+    /// The key of the aggregated messages.
+    pub key: String,
+    /// The window of the aggregated messages.
+    pub window: Window,
+    /// The aggregated value.
+    pub value: A,
+}
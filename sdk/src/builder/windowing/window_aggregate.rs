@@ -0,0 +1,38 @@
+use iggy::models::messages::PolledMessage;
+
+/// Aggregates the messages of a window into a single value.
+///
+/// Session windows that are bridged by a new message are combined with `merge`.
+pub trait WindowAggregate: Send + Sync {
+    /// The aggregated value.
+    type Acc: Send;
+
+    /// Returns the value of an empty window.
+    fn init(&self) -> Self::Acc;
+
+    /// Adds a message to the value of a window.
+    fn add(&self, acc: &mut Self::Acc, message: &PolledMessage);
+
+    /// Combines the values of two windows.
+    fn merge(&self, acc: Self::Acc, other: Self::Acc) -> Self::Acc;
+}
+
+/// Counts the messages of a window.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountAggregate;
+
+impl WindowAggregate for CountAggregate {
+    type Acc = u64;
+
+    fn init(&self) -> u64 {
+        0
+    }
+
+    fn add(&self, acc: &mut u64, _message: &PolledMessage) {
+        *acc += 1;
+    }
+
+    fn merge(&self, acc: u64, other: u64) -> u64 {
+        acc + other
+    }
+}
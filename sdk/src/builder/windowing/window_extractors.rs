@@ -0,0 +1,67 @@
+use crate::builder::MessageHeadersExt;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// Derives the aggregation key of a consumed message. Each key has its own windows.
+///
+/// Implemented for closures of the form `Fn(&PolledMessage) -> Option<String>`.
+pub trait WindowKeyExtractor: Send + Sync {
+    /// Returns the key of the message, or `None` to skip the message.
+    fn window_key(&self, message: &PolledMessage) -> Option<String>;
+}
+
+impl<F> WindowKeyExtractor for F
+where
+    F: Fn(&PolledMessage) -> Option<String> + Send + Sync,
+{
+    fn window_key(&self, message: &PolledMessage) -> Option<String> {
+        self(message)
+    }
+}
+
+/// Uses the `partition-key` header as the aggregation key.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeaderWindowKey;
+
+impl WindowKeyExtractor for HeaderWindowKey {
+    fn window_key(&self, message: &PolledMessage) -> Option<String> {
+        message.partition_key().map(str::to_string)
+    }
+}
+
+/// Derives the event time of a consumed message, e.g. from a header or a payload field.
+///
+/// Implemented for closures of the form `Fn(&PolledMessage) -> Option<IggyTimestamp>`.
+pub trait EventTimeExtractor: Send + Sync {
+    /// Returns the event time of the message, or `None` to skip the message.
+    fn event_time(&self, message: &PolledMessage) -> Option<IggyTimestamp>;
+}
+
+impl<F> EventTimeExtractor for F
+where
+    F: Fn(&PolledMessage) -> Option<IggyTimestamp> + Send + Sync,
+{
+    fn event_time(&self, message: &PolledMessage) -> Option<IggyTimestamp> {
+        self(message)
+    }
+}
+
+/// Uses the `created-at` header as the event time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeaderEventTime;
+
+impl EventTimeExtractor for HeaderEventTime {
+    fn event_time(&self, message: &PolledMessage) -> Option<IggyTimestamp> {
+        message.created_at()
+    }
+}
+
+/// Uses the time the server appended the message as the event time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MessageTimestamp;
+
+impl EventTimeExtractor for MessageTimestamp {
+    fn event_time(&self, message: &PolledMessage) -> Option<IggyTimestamp> {
+        Some(IggyTimestamp::from(message.timestamp))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::builder::{EventConsumerError, EventProducer, WindowResult};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use std::fmt;
+
+/// Trait for handling the results of closed windows.
+#[allow(dead_code)] // Clippy can't see that the trait is used
+#[trait_variant::make(WindowResultHandler: Send)]
+pub trait LocalWindowResultHandler<A> {
+    /// Handle the result of a closed window.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The key, window and aggregated value
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If the result cannot be handled
+    async fn handle(&self, result: WindowResult<A>) -> Result<(), EventConsumerError>;
+}
+
+/// Encodes every window result into a message and sends it to an `EventProducer`,
+/// e.g. the `IggyProducer` of an output topic.
+pub struct ProducerWindowHandler<P, F> {
+    producer: P,
+    encode: F,
+}
+
+impl<P: fmt::Debug, F> fmt::Debug for ProducerWindowHandler<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProducerWindowHandler")
+            .field("producer", &self.producer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, F> ProducerWindowHandler<P, F> {
+    /// Creates a new `ProducerWindowHandler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer` - The `EventProducer` of the output topic.
+    /// * `encode` - Encodes a window result into a message.
+    ///
+    pub fn new(producer: P, encode: F) -> Self {
+        Self { producer, encode }
+    }
+
+    /// Returns the underlying producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+}
+
+impl<A, P, F> WindowResultHandler<A> for ProducerWindowHandler<P, F>
+where
+    A: Send,
+    P: EventProducer + Sync,
+    F: Fn(&WindowResult<A>) -> Result<Message, IggyError> + Send + Sync,
+{
+    async fn handle(&self, result: WindowResult<A>) -> Result<(), EventConsumerError> {
+        let message =
+            (self.encode)(&result).map_err(|err| EventConsumerError::new(err.to_string()))?;
+        self.producer
+            .send_one_event(message)
+            .await
+            .map_err(|err| EventConsumerError::new(err.to_string()))
+    }
+}
@@ -0,0 +1,309 @@
+use crate::builder::{
+    EventTimeExtractor, Window, WindowAggregate, WindowKeyExtractor, WindowKind, WindowResult,
+    WindowedAggregationConfig,
+};
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::fmt;
+use tracing::debug;
+
+struct OpenWindow<A> {
+    start: u64,
+    end: u64,
+    acc: A,
+}
+
+/// Groups consumed messages by key into tumbling, hopping or session windows and
+/// aggregates each window.
+///
+/// The watermark is the highest event time seen so far. A window closes, and its result
+/// is returned, once the watermark reaches the end of the window plus the allowed lateness.
+/// Messages that only belong to closed windows are late; they are dropped and counted.
+/// Messages without a key or event time are skipped and counted.
+///
+/// The watermark only advances with new messages, so call `flush` on shutdown to get
+/// the results of the windows that are still open.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = WindowedAggregationConfig::tumbling(IggyDuration::from_str("1m")?, IggyDuration::from_str("5s")?);
+/// let mut aggregation = WindowedAggregation::new(config, HeaderWindowKey, HeaderEventTime, CountAggregate)?;
+/// for result in aggregation.push(&message) {
+///     println!("{}: {} messages", result.key, result.value);
+/// }
+/// ```
+pub struct WindowedAggregation<G: WindowAggregate> {
+    window: WindowKind,
+    allowed_lateness: u64,
+    key_extractor: Box<dyn WindowKeyExtractor>,
+    time_extractor: Box<dyn EventTimeExtractor>,
+    aggregate: G,
+    open: HashMap<String, Vec<OpenWindow<G::Acc>>>,
+    watermark: Option<u64>,
+    last_offsets: HashMap<u32, u64>,
+    late_messages: u64,
+    skipped_messages: u64,
+}
+
+impl<G: WindowAggregate> fmt::Debug for WindowedAggregation<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowedAggregation")
+            .field("window", &self.window)
+            .field("allowed_lateness", &self.allowed_lateness)
+            .field("open_windows", &self.open_windows())
+            .field("watermark", &self.watermark)
+            .field("late_messages", &self.late_messages)
+            .field("skipped_messages", &self.skipped_messages)
+            .finish()
+    }
+}
+
+impl<G: WindowAggregate> WindowedAggregation<G> {
+    /// Creates a new `WindowedAggregation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The window kind and allowed lateness.
+    /// * `key_extractor` - Derives the aggregation key of a message.
+    /// * `time_extractor` - Derives the event time of a message.
+    /// * `aggregate` - Aggregates the messages of a window.
+    ///
+    /// # Errors
+    ///
+    /// * `IggyError::InvalidConfiguration` - If the window config is invalid.
+    ///
+    pub fn new(
+        config: WindowedAggregationConfig,
+        key_extractor: impl WindowKeyExtractor + 'static,
+        time_extractor: impl EventTimeExtractor + 'static,
+        aggregate: G,
+    ) -> Result<Self, IggyError> {
+        config.validate()?;
+
+        Ok(Self {
+            window: config.window(),
+            allowed_lateness: config.allowed_lateness().as_micros(),
+            key_extractor: Box::new(key_extractor),
+            time_extractor: Box::new(time_extractor),
+            aggregate,
+            open: HashMap::new(),
+            watermark: None,
+            last_offsets: HashMap::new(),
+            late_messages: 0,
+            skipped_messages: 0,
+        })
+    }
+
+    /// Returns the highest event time seen so far.
+    pub fn watermark(&self) -> Option<IggyTimestamp> {
+        self.watermark.map(IggyTimestamp::from)
+    }
+
+    /// Returns the number of dropped late messages.
+    pub fn late_messages(&self) -> u64 {
+        self.late_messages
+    }
+
+    /// Returns the number of messages skipped for a missing key or event time.
+    pub fn skipped_messages(&self) -> u64 {
+        self.skipped_messages
+    }
+
+    /// Returns the number of open windows across all keys.
+    pub fn open_windows(&self) -> usize {
+        self.open.values().map(Vec::len).sum()
+    }
+
+    /// Adds a message to its windows and advances the watermark.
+    ///
+    /// Returns:
+    /// The results of all windows closed by the new watermark, ordered by window end and key.
+    ///
+    pub fn push(&mut self, message: &PolledMessage) -> Vec<WindowResult<G::Acc>> {
+        let (Some(key), Some(event_time)) = (
+            self.key_extractor.window_key(message),
+            self.time_extractor.event_time(message),
+        ) else {
+            debug!(
+                "Skipping message without key or event time at offset: {}",
+                message.offset
+            );
+            self.skipped_messages += 1;
+            return Vec::new();
+        };
+
+        let event_time = event_time.as_micros();
+        let watermark = self.watermark.map_or(event_time, |w| w.max(event_time));
+        self.watermark = Some(watermark);
+
+        let added = match self.window {
+            WindowKind::Session { gap } => {
+                self.add_to_session(key, event_time, gap.as_micros(), message)
+            }
+            _ => self.add_to_windows(key, event_time, message),
+        };
+
+        if !added {
+            debug!(
+                "Dropping late message at offset: {} behind watermark: {watermark}",
+                message.offset
+            );
+            self.late_messages += 1;
+        }
+
+        let allowed_lateness = self.allowed_lateness;
+        self.close_windows(|end| end.saturating_add(allowed_lateness) <= watermark)
+    }
+
+    /// Adds a message of the given partition like `push`, unless its offset is at or before
+    /// the last offset pushed from that partition, e.g. because it is delivered again after
+    /// its results could not be handled.
+    ///
+    /// Returns:
+    /// The results of all windows closed by the new watermark, ordered by window end and key.
+    ///
+    pub fn push_from(
+        &mut self,
+        partition_id: u32,
+        message: &PolledMessage,
+    ) -> Vec<WindowResult<G::Acc>> {
+        if let Some(&last_offset) = self.last_offsets.get(&partition_id) {
+            if message.offset <= last_offset {
+                debug!(
+                    "Skipping already aggregated offset: {} in partition: {partition_id}",
+                    message.offset
+                );
+                return Vec::new();
+            }
+        }
+
+        self.last_offsets.insert(partition_id, message.offset);
+        self.push(message)
+    }
+
+    /// Closes all open windows regardless of the watermark.
+    ///
+    /// Returns:
+    /// The results of all open windows, ordered by window end and key.
+    ///
+    pub fn flush(&mut self) -> Vec<WindowResult<G::Acc>> {
+        self.close_windows(|_| true)
+    }
+
+    fn is_closed(&self, end: u64) -> bool {
+        self.watermark
+            .is_some_and(|watermark| end.saturating_add(self.allowed_lateness) <= watermark)
+    }
+
+    /// Adds the message to every open tumbling or hopping window that contains its event time.
+    fn add_to_windows(&mut self, key: String, event_time: u64, message: &PolledMessage) -> bool {
+        let (size, advance) = match self.window {
+            WindowKind::Tumbling { size } => (size.as_micros(), size.as_micros()),
+            WindowKind::Hopping { size, advance } => (size.as_micros(), advance.as_micros()),
+            WindowKind::Session { .. } => unreachable!("Session windows are added separately"),
+        };
+
+        // The last window that starts at or before the event time; earlier windows
+        // contain the event time as long as they end after it.
+        let mut start = event_time - event_time % advance;
+        let mut starts = Vec::new();
+        loop {
+            let end = start.saturating_add(size);
+            if end > event_time && !self.is_closed(end) {
+                starts.push(start);
+            }
+            // Stop once the previous window ends at or before the event time.
+            if start < advance || (start - advance).saturating_add(size) <= event_time {
+                break;
+            }
+            start -= advance;
+        }
+
+        if starts.is_empty() {
+            return false;
+        }
+
+        let windows = self.open.entry(key).or_default();
+        for start in starts {
+            let window = match windows.iter_mut().find(|window| window.start == start) {
+                Some(window) => window,
+                None => {
+                    windows.push(OpenWindow {
+                        start,
+                        end: start.saturating_add(size),
+                        acc: self.aggregate.init(),
+                    });
+                    windows.last_mut().expect("Window was just added")
+                }
+            };
+            self.aggregate.add(&mut window.acc, message);
+        }
+        true
+    }
+
+    /// Adds the message to the session of its key, merging all sessions it bridges.
+    fn add_to_session(
+        &mut self,
+        key: String,
+        event_time: u64,
+        gap: u64,
+        message: &PolledMessage,
+    ) -> bool {
+        let end = event_time.saturating_add(gap);
+        if self.is_closed(end) {
+            return false;
+        }
+
+        let sessions = self.open.entry(key).or_default();
+        let mut session = OpenWindow {
+            start: event_time,
+            end,
+            acc: self.aggregate.init(),
+        };
+        self.aggregate.add(&mut session.acc, message);
+
+        let mut i = 0;
+        while i < sessions.len() {
+            if sessions[i].start <= session.end && session.start <= sessions[i].end {
+                let other = sessions.swap_remove(i);
+                session.start = session.start.min(other.start);
+                session.end = session.end.max(other.end);
+                session.acc = self.aggregate.merge(other.acc, session.acc);
+            } else {
+                i += 1;
+            }
+        }
+
+        sessions.push(session);
+        true
+    }
+
+    fn close_windows(&mut self, is_closed: impl Fn(u64) -> bool) -> Vec<WindowResult<G::Acc>> {
+        let mut results = Vec::new();
+        for (key, windows) in self.open.iter_mut() {
+            let mut i = 0;
+            while i < windows.len() {
+                if is_closed(windows[i].end) {
+                    let window = windows.swap_remove(i);
+                    results.push(WindowResult {
+                        key: key.clone(),
+                        window: Window::from_micros(window.start, window.end),
+                        value: window.acc,
+                    });
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.open.retain(|_, windows| !windows.is_empty());
+
+        results.sort_by(|a, b| {
+            let a_key = (a.window.end.as_micros(), a.window.start.as_micros(), &a.key);
+            a_key.cmp(&(b.window.end.as_micros(), b.window.start.as_micros(), &b.key))
+        });
+        results
+    }
+}
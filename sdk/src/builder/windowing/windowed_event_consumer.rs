@@ -0,0 +1,117 @@
+use crate::builder::pending_results::PendingResults;
+use crate::builder::{
+    EventConsumer, EventConsumerError, WindowAggregate, WindowResult, WindowResultHandler,
+    WindowedAggregation,
+};
+use iggy::models::messages::PolledMessage;
+use std::sync::Mutex;
+
+/// The pending window results, with the offset and ID of the last aggregated message.
+type PendingWindowResults<A> = tokio::sync::Mutex<PendingResults<WindowResult<A>, (u64, u128)>>;
+
+/// `EventConsumer` that runs every consumed message through a `WindowedAggregation`
+/// and passes the results of closed windows to a `WindowResultHandler`.
+///
+/// Results stay pending until the handler succeeded, so a failed handler does not lose them:
+/// the next consumed message, usually the retry of the failed one, handles them first. A
+/// retried message is not aggregated again. Use `process` with the partition of the message
+/// to skip every offset that was already aggregated.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let handler = ProducerWindowHandler::new(output_producer, encode_count);
+/// let consumer = WindowedEventConsumer::new(aggregation, handler);
+/// input_consumer.consume_messages(&consumer, shutdown_rx).await?;
+/// ```
+#[derive(Debug)]
+pub struct WindowedEventConsumer<G: WindowAggregate, H> {
+    aggregation: Mutex<WindowedAggregation<G>>,
+    pending: PendingWindowResults<G::Acc>,
+    handler: H,
+}
+
+impl<G, H> WindowedEventConsumer<G, H>
+where
+    G: WindowAggregate,
+    G::Acc: Clone,
+    H: WindowResultHandler<G::Acc> + Sync,
+{
+    /// Creates a new `WindowedEventConsumer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregation` - The windows and aggregate.
+    /// * `handler` - Handles the results of closed windows.
+    ///
+    pub fn new(aggregation: WindowedAggregation<G>, handler: H) -> Self {
+        Self {
+            aggregation: Mutex::new(aggregation),
+            pending: tokio::sync::Mutex::new(PendingResults::default()),
+            handler,
+        }
+    }
+
+    /// Returns the result handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns the number of dropped late messages.
+    pub fn late_messages(&self) -> u64 {
+        self.aggregation.lock().unwrap().late_messages()
+    }
+
+    /// Aggregates a message of the given partition and handles the pending results.
+    /// Messages at or before the last aggregated offset of the partition are not
+    /// aggregated again.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a result cannot be handled. It stays pending.
+    ///
+    pub async fn process(
+        &self,
+        partition_id: u32,
+        message: PolledMessage,
+    ) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let results = self
+            .aggregation
+            .lock()
+            .unwrap()
+            .push_from(partition_id, &message);
+        pending.add((message.offset, message.id), results);
+        pending.handle(|result| self.handler.handle(result)).await
+    }
+
+    /// Closes all open windows and handles their results, e.g. on shutdown.
+    ///
+    /// # Errors
+    ///
+    /// * `EventConsumerError` - If a result cannot be handled. It stays pending.
+    ///
+    pub async fn flush(&self) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let results = self.aggregation.lock().unwrap().flush();
+        pending.extend(results);
+        pending.handle(|result| self.handler.handle(result)).await
+    }
+}
+
+impl<G, H> EventConsumer for WindowedEventConsumer<G, H>
+where
+    G: WindowAggregate,
+    G::Acc: Clone,
+    H: WindowResultHandler<G::Acc> + Sync,
+{
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        let mut pending = self.pending.lock().await;
+        let key = (message.offset, message.id);
+        if !pending.contains(&key) {
+            let results = self.aggregation.lock().unwrap().push(&message);
+            pending.add(key, results);
+        }
+        pending.handle(|result| self.handler.handle(result)).await
+    }
+}
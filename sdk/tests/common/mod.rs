@@ -0,0 +1,5 @@
+mod polled_message;
+mod recording_event_producer;
+
+pub use polled_message::*;
+pub use recording_event_producer::*;
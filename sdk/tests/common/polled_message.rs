@@ -0,0 +1,28 @@
+use bytes::Bytes;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::{MessageState, PolledMessage};
+
+/// Returns an available message with the given offset and payload, without headers.
+/// All other fields are zero; set them with struct update syntax.
+pub fn polled_message(offset: u64, payload: impl Into<Bytes>) -> PolledMessage {
+    let payload = payload.into();
+    PolledMessage {
+        offset,
+        state: MessageState::Available,
+        timestamp: 0,
+        id: 0,
+        checksum: 0,
+        headers: None,
+        length: (payload.len() as u64).into(),
+        payload,
+    }
+}
+
+/// Returns the sent message as it is polled at the given offset, with its ID and headers.
+pub fn to_polled(offset: u64, message: Message) -> PolledMessage {
+    PolledMessage {
+        id: message.id,
+        headers: message.headers,
+        ..polled_message(offset, message.payload)
+    }
+}
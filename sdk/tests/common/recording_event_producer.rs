@@ -0,0 +1,96 @@
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use sdk::builder::EventProducer;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Records the sent messages, one entry per send call.
+///
+/// Clones share the recorded messages, so a clone can be kept to inspect a producer that
+/// was moved into a wrapper.
+#[derive(Debug, Default, Clone)]
+pub struct RecordingEventProducer {
+    batches: Arc<Mutex<Vec<Vec<Message>>>>,
+    failing_payloads: Arc<Mutex<HashSet<String>>>,
+    fail: Arc<AtomicBool>,
+}
+
+impl RecordingEventProducer {
+    /// Returns all sent messages in order.
+    pub fn sent(&self) -> Vec<Message> {
+        self.batches.lock().unwrap().concat()
+    }
+
+    /// Returns the payloads of all sent messages in order.
+    pub fn payloads(&self) -> Vec<String> {
+        self.sent().iter().map(payload).collect()
+    }
+
+    /// Returns the sent messages per send call.
+    pub fn batches(&self) -> Vec<Vec<Message>> {
+        self.batches.lock().unwrap().clone()
+    }
+
+    /// Returns the payloads of the sent messages per send call.
+    pub fn batch_payloads(&self) -> Vec<Vec<String>> {
+        let batches = self.batches.lock().unwrap();
+        batches
+            .iter()
+            .map(|batch| batch.iter().map(payload).collect())
+            .collect()
+    }
+
+    /// Fails every send with `IggyError::Disconnected`.
+    pub fn fail_all(&self) {
+        self.fail.store(true, Ordering::SeqCst);
+    }
+
+    /// Fails sending the message with this payload with `IggyError::Disconnected`.
+    /// The messages before it in the same batch are still recorded.
+    pub fn fail_payload(&self, payload: &str) {
+        self.failing_payloads
+            .lock()
+            .unwrap()
+            .insert(payload.to_string());
+    }
+
+    /// Lets all sends succeed again.
+    pub fn clear_failures(&self) {
+        self.fail.store(false, Ordering::SeqCst);
+        self.failing_payloads.lock().unwrap().clear();
+    }
+}
+
+impl EventProducer for RecordingEventProducer {
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_event_batch(vec![message]).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(IggyError::Disconnected);
+        }
+
+        let failing_payloads = self.failing_payloads.lock().unwrap();
+        let failed = messages
+            .iter()
+            .position(|message| failing_payloads.contains(&payload(message)));
+        let mut messages = messages;
+        if let Some(failed) = failed {
+            messages.truncate(failed);
+        }
+        if !messages.is_empty() {
+            self.batches.lock().unwrap().push(messages);
+        }
+
+        match failed {
+            Some(_) => Err(IggyError::Disconnected),
+            None => Ok(()),
+        }
+    }
+}
+
+fn payload(message: &Message) -> String {
+    String::from_utf8_lossy(&message.payload).to_string()
+}
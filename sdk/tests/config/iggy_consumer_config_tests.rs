@@ -0,0 +1,61 @@
+use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{IggyConsumerConfig, OffsetResetPolicy};
+use std::str::FromStr;
+
+#[test]
+fn test_consumer_config_without_offset_reset() {
+    let config = IggyConsumerConfig::default();
+    assert_eq!(config.offset_reset(), None);
+    assert_eq!(config.polling_strategy(), PollingStrategy::last());
+}
+
+#[test]
+fn test_consumer_config_with_offset_reset_keeps_polling_strategy() {
+    let config = IggyConsumerConfig::builder()
+        .stream_id(config_default().stream_id().to_owned())
+        .stream_name("test_stream")
+        .topic_id(config_default().topic_id().to_owned())
+        .topic_name("test_topic")
+        .auto_commit(config_default().auto_commit())
+        .batch_size(100)
+        .consumer_name("test_consumer")
+        .consumer_kind(config_default().consumer_kind())
+        .polling_interval(IggyDuration::from_str("5ms").unwrap())
+        .polling_strategy(PollingStrategy::last())
+        .partitions_count(1)
+        .offset_reset(OffsetResetPolicy::Latest)
+        .build();
+
+    assert_eq!(config.offset_reset(), Some(OffsetResetPolicy::Latest));
+    assert_eq!(config.polling_strategy(), PollingStrategy::last());
+}
+
+#[test]
+fn test_consumer_config_with_offset_reset() {
+    let config = IggyConsumerConfig::from_stream_topic(
+        "test_stream",
+        "test_topic",
+        100,
+        IggyDuration::from_str("5ms").unwrap(),
+    )
+    .with_offset_reset(OffsetResetPolicy::Earliest);
+
+    assert_eq!(config.offset_reset(), Some(OffsetResetPolicy::Earliest));
+    assert_eq!(config.polling_strategy().kind, PollingKind::Next);
+    assert_eq!(config.topic_name(), "test_topic");
+}
+
+#[test]
+fn test_consumer_config_with_polling_strategy_clears_offset_reset() {
+    let config = config_default()
+        .with_offset_reset(OffsetResetPolicy::Latest)
+        .with_polling_strategy(PollingStrategy::offset(0));
+
+    assert_eq!(config.offset_reset(), None);
+    assert_eq!(config.polling_strategy(), PollingStrategy::offset(0));
+}
+
+fn config_default() -> IggyConsumerConfig {
+    IggyConsumerConfig::default()
+}
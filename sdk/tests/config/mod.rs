@@ -1 +1 @@
-
+mod iggy_consumer_config_tests;
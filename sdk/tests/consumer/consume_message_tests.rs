@@ -0,0 +1,142 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    consume_message_with_config, ConsumeFailure, ConsumeFailureKind, ConsumeMessagesConfig,
+    EventConsumer, EventConsumerError,
+};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Fails the first `failures` calls, sleeps for `delay` on every call, or panics.
+#[derive(Debug, Default)]
+struct TestConsumer {
+    calls: AtomicU32,
+    failures: u32,
+    delay: Option<Duration>,
+    panics: bool,
+}
+
+impl EventConsumer for TestConsumer {
+    async fn consume(&self, _message: PolledMessage) -> Result<(), EventConsumerError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+        if self.panics {
+            panic!("handler bug");
+        }
+        if call <= self.failures {
+            return Err(EventConsumerError::new(format!("failure {call}")));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_slow_handler_times_out() {
+    let handler = TestConsumer {
+        delay: Some(Duration::from_secs(5)),
+        ..Default::default()
+    };
+    let config = config(0, Some(duration("10ms")), None);
+
+    let failure = consume_message_with_config(&handler, 1, polled_message(7, ""), &config)
+        .await
+        .unwrap_err();
+
+    assert_eq!(failure.kind, ConsumeFailureKind::Timeout);
+    assert_eq!(failure.attempts, 1);
+}
+
+#[tokio::test]
+async fn test_failing_handler_is_retried() {
+    let handler = TestConsumer {
+        failures: u32::MAX,
+        ..Default::default()
+    };
+    let config = config(2, None, None);
+
+    let failure = consume_message_with_config(&handler, 1, polled_message(7, ""), &config)
+        .await
+        .unwrap_err();
+
+    assert_eq!(failure.kind, ConsumeFailureKind::Handler);
+    assert_eq!(failure.attempts, 3);
+    assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+    assert_eq!(failure.error.to_string(), "EventConsumerError: failure 3");
+}
+
+#[tokio::test]
+async fn test_retry_succeeds() {
+    let handler = TestConsumer {
+        failures: 1,
+        ..Default::default()
+    };
+    let config = config(2, None, None);
+
+    consume_message_with_config(&handler, 1, polled_message(7, ""), &config)
+        .await
+        .unwrap();
+
+    assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_panicking_handler() {
+    let handler = TestConsumer {
+        panics: true,
+        ..Default::default()
+    };
+    let config = config(1, None, None);
+
+    let failure = consume_message_with_config(&handler, 1, polled_message(7, ""), &config)
+        .await
+        .unwrap_err();
+
+    assert_eq!(failure.kind, ConsumeFailureKind::Panic);
+    assert_eq!(failure.attempts, 2);
+    assert!(failure.error.to_string().contains("handler bug"));
+}
+
+#[tokio::test]
+async fn test_failure_sender_receives_failed_message() {
+    let handler = TestConsumer {
+        failures: u32::MAX,
+        ..Default::default()
+    };
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let config = config(0, None, Some(sender));
+
+    consume_message_with_config(&handler, 3, polled_message(42, ""), &config)
+        .await
+        .unwrap_err();
+    consume_message_with_config(&TestConsumer::default(), 3, polled_message(43, ""), &config)
+        .await
+        .unwrap();
+
+    let failure = receiver.recv().await.unwrap();
+    assert_eq!((failure.partition_id, failure.offset), (3, 42));
+    assert_eq!(failure.kind, ConsumeFailureKind::Handler);
+    assert!(receiver.try_recv().is_err());
+}
+
+fn config(
+    handler_retries: u32,
+    handler_timeout: Option<IggyDuration>,
+    failure_sender: Option<mpsc::UnboundedSender<ConsumeFailure>>,
+) -> ConsumeMessagesConfig {
+    ConsumeMessagesConfig::builder()
+        .handler_retries(handler_retries)
+        .retry_interval(duration("1ms"))
+        .chunk_timeout(duration("60s"))
+        .maybe_handler_timeout(handler_timeout)
+        .maybe_failure_sender(failure_sender)
+        .build()
+}
+
+fn duration(value: &str) -> IggyDuration {
+    IggyDuration::from_str(value).unwrap()
+}
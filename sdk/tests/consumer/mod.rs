@@ -0,0 +1,2 @@
+mod consume_message_tests;
+mod replay_range_tests;
@@ -0,0 +1,53 @@
+use crate::common::polled_message;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::timestamp::IggyTimestamp;
+use sdk::builder::{ReplayBound, ReplayRange};
+
+#[test]
+fn test_offset_range_start_strategy() {
+    let range = ReplayRange::offsets(5, 10);
+
+    assert_eq!(range.start_strategy(), PollingStrategy::offset(5));
+}
+
+#[test]
+fn test_timestamp_range_start_strategy() {
+    let start = IggyTimestamp::from(1_000);
+    let range = ReplayRange::timestamps(start, IggyTimestamp::from(2_000));
+
+    assert_eq!(range.start_strategy(), PollingStrategy::timestamp(start));
+}
+
+#[test]
+fn test_offset_range_end_is_exclusive() {
+    let range = ReplayRange::offsets(5, 10);
+
+    assert!(!range.is_past_end(&message(5, 0)));
+    assert!(!range.is_past_end(&message(9, 0)));
+    assert!(range.is_past_end(&message(10, 0)));
+    assert!(range.is_past_end(&message(11, 0)));
+}
+
+#[test]
+fn test_timestamp_range_end_is_exclusive() {
+    let range = ReplayRange::timestamps(IggyTimestamp::from(1_000), IggyTimestamp::from(2_000));
+
+    assert!(!range.is_past_end(&message(0, 1_999)));
+    assert!(range.is_past_end(&message(0, 2_000)));
+    assert!(range.is_past_end(&message(0, 2_001)));
+}
+
+#[test]
+fn test_open_range_never_ends() {
+    let range = ReplayRange::new(ReplayBound::Offset(0), None);
+
+    assert!(!range.is_past_end(&message(u64::MAX, u64::MAX)));
+}
+
+fn message(offset: u64, timestamp: u64) -> PolledMessage {
+    PolledMessage {
+        timestamp,
+        ..polled_message(offset, "")
+    }
+}
@@ -0,0 +1,25 @@
+use sdk::builder::{ConsumerLag, PartitionLag};
+
+#[test]
+fn test_partition_lag() {
+    // Empty partition has no lag.
+    assert_eq!(PartitionLag::new(1, None, None).lag, 0);
+    // Nothing committed yet, so all messages are lagging.
+    assert_eq!(PartitionLag::new(1, Some(9), None).lag, 10);
+    // Committed up to offset 4 out of 0..=9.
+    assert_eq!(PartitionLag::new(1, Some(9), Some(4)).lag, 5);
+    // Fully caught up.
+    assert_eq!(PartitionLag::new(1, Some(9), Some(9)).lag, 0);
+}
+
+#[test]
+fn test_consumer_lag_total() {
+    let lag = ConsumerLag::new(vec![
+        PartitionLag::new(1, Some(9), Some(4)),
+        PartitionLag::new(2, Some(2), None),
+    ]);
+
+    assert_eq!(lag.total(), 8);
+    assert_eq!(lag.partition(2).map(|p| p.lag), Some(3));
+    assert!(lag.partition(3).is_none());
+}
@@ -0,0 +1 @@
+mod consumer_lag_tests;
@@ -0,0 +1,194 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use sdk::builder::{
+    Aggregate, AggregateError, EventCodec, JsonCodec, LoadedAggregate, MessageHeaders,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Account {
+    balance: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum AccountEvent {
+    Deposited(i64),
+    Withdrawn(i64),
+}
+
+impl Aggregate for Account {
+    type Event = AccountEvent;
+
+    fn apply(&mut self, event: AccountEvent) {
+        match event {
+            AccountEvent::Deposited(amount) => self.balance += amount,
+            AccountEvent::Withdrawn(amount) => self.balance -= amount,
+        }
+    }
+}
+
+#[test]
+fn test_apply_events_of_aggregate() {
+    let mut account = LoadedAggregate::<Account>::new("account-1");
+
+    assert!(apply(
+        &mut account,
+        event(0, "account-1", Some(1), AccountEvent::Deposited(100))
+    ));
+    assert!(!apply(
+        &mut account,
+        event(1, "account-2", Some(1), AccountEvent::Deposited(5))
+    ));
+    assert!(apply(
+        &mut account,
+        event(2, "account-1", Some(2), AccountEvent::Withdrawn(30))
+    ));
+
+    assert_eq!(account.state().balance, 70);
+    assert_eq!(account.version(), 2);
+    assert_eq!(account.offset(), Some(2));
+}
+
+#[test]
+fn test_ignore_events_with_unexpected_version() {
+    let mut account = LoadedAggregate::<Account>::new("account-1");
+    apply(
+        &mut account,
+        event(0, "account-1", Some(1), AccountEvent::Deposited(100)),
+    );
+
+    // Appended by a writer that lost the race for version 1.
+    assert!(!apply(
+        &mut account,
+        event(1, "account-1", Some(1), AccountEvent::Deposited(5))
+    ));
+    // Already applied.
+    assert!(!apply(
+        &mut account,
+        event(0, "account-1", Some(1), AccountEvent::Deposited(100))
+    ));
+    // Events without a version are applied in order.
+    assert!(apply(
+        &mut account,
+        event(2, "account-1", None, AccountEvent::Withdrawn(10))
+    ));
+
+    assert_eq!(account.state().balance, 90);
+    assert_eq!(account.version(), 2);
+}
+
+#[test]
+fn test_ignore_batch_of_writer_that_lost_race() {
+    let mut account = LoadedAggregate::<Account>::new("account-1");
+
+    // Writer A appends one event at version 0.
+    assert!(apply(
+        &mut account,
+        batch_event(0, 0, 1, 1, AccountEvent::Deposited(100))
+    ));
+    // Writer B appended two events at version 0 as well, and lost the race.
+    assert!(!apply(
+        &mut account,
+        batch_event(1, 0, 2, 1, AccountEvent::Deposited(5))
+    ));
+    assert!(!apply(
+        &mut account,
+        batch_event(2, 0, 2, 2, AccountEvent::Withdrawn(50))
+    ));
+    // The next batch of writer A is applied.
+    assert!(apply(
+        &mut account,
+        batch_event(3, 1, 3, 2, AccountEvent::Withdrawn(10))
+    ));
+    assert!(apply(
+        &mut account,
+        batch_event(4, 1, 3, 3, AccountEvent::Withdrawn(20))
+    ));
+
+    assert_eq!(account.state().balance, 70);
+    assert_eq!(account.version(), 3);
+    assert_eq!(account.offset(), Some(4));
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let codec = JsonCodec::default();
+    let mut account = LoadedAggregate::<Account>::new("account-1");
+    assert!(account.snapshot(&codec).unwrap().is_none());
+    apply(
+        &mut account,
+        event(4, "account-1", Some(1), AccountEvent::Deposited(100)),
+    );
+
+    let snapshot = account.snapshot(&codec).unwrap().unwrap();
+    let mut restored = LoadedAggregate::<Account>::from_snapshot(&snapshot, &codec).unwrap();
+
+    assert_eq!((snapshot.version, snapshot.offset), (1, 4));
+    assert_eq!(restored.state().balance, 100);
+    // Replay continues after the snapshot.
+    assert!(!apply(
+        &mut restored,
+        event(4, "account-1", Some(1), AccountEvent::Deposited(100))
+    ));
+    assert!(apply(
+        &mut restored,
+        event(5, "account-1", Some(2), AccountEvent::Deposited(1))
+    ));
+    assert_eq!(restored.into_state().balance, 101);
+}
+
+#[test]
+fn test_conflict_error_message() {
+    let err = AggregateError::Conflict {
+        aggregate_id: "account-1".to_string(),
+        expected_version: 2,
+        actual_version: 3,
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "AggregateError: aggregate account-1 is at version 3, expected 2"
+    );
+}
+
+fn apply(account: &mut LoadedAggregate<Account>, message: PolledMessage) -> bool {
+    account
+        .apply_message(&message, &JsonCodec::default())
+        .unwrap()
+}
+
+fn event(
+    offset: u64,
+    aggregate_id: &str,
+    version: Option<u64>,
+    event: AccountEvent,
+) -> PolledMessage {
+    let mut headers = MessageHeaders::new().aggregate_id(aggregate_id);
+    if let Some(version) = version {
+        headers = headers.aggregate_version(version);
+    }
+    let payload = JsonCodec::default().encode(&event).unwrap();
+    PolledMessage {
+        headers: Some(headers.build().unwrap()),
+        ..polled_message(offset, payload)
+    }
+}
+
+fn batch_event(
+    offset: u64,
+    expected_version: u64,
+    batch_id: u128,
+    version: u64,
+    event: AccountEvent,
+) -> PolledMessage {
+    let headers = MessageHeaders::new()
+        .aggregate_id("account-1")
+        .aggregate_version(version)
+        .aggregate_expected_version(expected_version)
+        .aggregate_batch_id(batch_id);
+    let payload = JsonCodec::default().encode(&event).unwrap();
+    PolledMessage {
+        headers: Some(headers.build().unwrap()),
+        ..polled_message(offset, payload)
+    }
+}
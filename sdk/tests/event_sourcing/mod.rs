@@ -0,0 +1,2 @@
+mod loaded_aggregate_tests;
+mod snapshot_store_tests;
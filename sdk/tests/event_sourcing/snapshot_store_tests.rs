@@ -0,0 +1,59 @@
+use sdk::builder::{
+    AggregateSnapshot, InMemorySnapshotStore, SnapshotStore, SqliteSnapshotStore, StateStoreConfig,
+};
+
+#[test]
+fn test_in_memory_snapshot_store() {
+    let store = InMemorySnapshotStore::new();
+    assert!(store.load("account-1").unwrap().is_none());
+
+    store.save(&snapshot("account-1", 1)).unwrap();
+    store.save(&snapshot("account-1", 2)).unwrap();
+
+    assert_eq!(
+        store.load("account-1").unwrap(),
+        Some(snapshot("account-1", 2))
+    );
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_sqlite_snapshot_store_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::new(dir.path().join("snapshots.db"), "accounts");
+    {
+        let store = SqliteSnapshotStore::open(&config).unwrap();
+        store.save(&snapshot("account-1", 1)).unwrap();
+        store.save(&snapshot("account-1", 5)).unwrap();
+        store.save(&snapshot("account-2", 3)).unwrap();
+    }
+
+    let store = SqliteSnapshotStore::open(&config).unwrap();
+
+    assert_eq!(
+        store.load("account-1").unwrap(),
+        Some(snapshot("account-1", 5))
+    );
+    assert_eq!(
+        store.load("account-2").unwrap(),
+        Some(snapshot("account-2", 3))
+    );
+    assert!(store.load("account-3").unwrap().is_none());
+}
+
+#[test]
+fn test_sqlite_snapshot_store_invalid_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::new(dir.path().join("snapshots.db"), "accounts-v2");
+
+    assert!(SqliteSnapshotStore::open(&config).is_err());
+}
+
+fn snapshot(aggregate_id: &str, version: u64) -> AggregateSnapshot {
+    AggregateSnapshot {
+        aggregate_id: aggregate_id.to_string(),
+        version,
+        offset: version * 10,
+        state: format!("{{\"balance\":{version}}}").into_bytes(),
+    }
+}
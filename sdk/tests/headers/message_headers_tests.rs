@@ -0,0 +1,81 @@
+use bytes::Bytes;
+use iggy::models::header::HeaderValue;
+use iggy::models::messages::{MessageState, PolledMessage};
+use iggy::utils::timestamp::IggyTimestamp;
+use sdk::builder::{MessageHeaders, MessageHeadersExt};
+
+#[test]
+fn test_message_headers_well_known_keys() {
+    let created_at = IggyTimestamp::from(1_700_000_000_000_000);
+    let message = MessageHeaders::new()
+        .content_type("application/json")
+        .correlation_id("request-1")
+        .causation_id("message-0")
+        .message_type("order_created")
+        .created_at(created_at)
+        .message("{}")
+        .expect("Failed to build message");
+
+    assert_eq!(message.content_type(), Some("application/json"));
+    assert_eq!(message.correlation_id(), Some("request-1"));
+    assert_eq!(message.causation_id(), Some("message-0"));
+    assert_eq!(message.message_type(), Some("order_created"));
+    assert_eq!(message.created_at(), Some(created_at));
+}
+
+#[test]
+fn test_message_headers_custom_keys() {
+    let message = MessageHeaders::new()
+        .insert_str("tenant", "acme")
+        .insert("priority", HeaderValue::from_uint32(7).unwrap())
+        .message("payload")
+        .expect("Failed to build message");
+
+    assert_eq!(message.header_str("tenant"), Some("acme"));
+    assert_eq!(message.header("priority").unwrap().as_uint32().unwrap(), 7);
+    assert_eq!(message.header_str("priority"), None);
+    assert_eq!(message.header("missing"), None);
+}
+
+#[test]
+fn test_message_headers_invalid_key() {
+    let res = MessageHeaders::new()
+        .insert_str("", "value")
+        .content_type("application/json")
+        .build();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_polled_message_headers() {
+    let headers = MessageHeaders::new()
+        .correlation_id("request-1")
+        .build()
+        .expect("Failed to build headers");
+
+    let message = PolledMessage {
+        offset: 0,
+        state: MessageState::Available,
+        timestamp: 0,
+        id: 1,
+        checksum: 0,
+        headers: Some(headers.clone()),
+        length: 0.into(),
+        payload: Bytes::new(),
+    };
+    assert_eq!(message.correlation_id(), Some("request-1"));
+    assert_eq!(message.content_type(), None);
+
+    let forwarded = MessageHeaders::new()
+        .extend(&headers)
+        .causation_id("message-1")
+        .build()
+        .expect("Failed to build headers");
+    assert_eq!(forwarded.len(), 2);
+
+    let without_headers = PolledMessage {
+        headers: None,
+        ..message
+    };
+    assert_eq!(without_headers.correlation_id(), None);
+}
@@ -0,0 +1 @@
+mod message_headers_tests;
@@ -0,0 +1,261 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    EventConsumer, EventConsumerError, JoinResultHandler, JoinSide, JoinedPair, MessageTimestamp,
+    StreamJoin, StreamJoinConfig, StreamJoinConsumer,
+};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SECOND: u64 = 1_000_000;
+
+#[test]
+fn test_inner_join() {
+    let mut join = stream_join(StreamJoinConfig::inner(window()));
+
+    assert!(join
+        .push(JoinSide::Left, &message("1", "created", 0))
+        .is_empty());
+    assert!(join
+        .push(JoinSide::Right, &message("2", "confirmed", 1))
+        .is_empty());
+
+    let pairs = join.push(JoinSide::Right, &message("1", "confirmed", 3));
+
+    assert_eq!(summary(&pairs), vec!["1:created->confirmed"]);
+}
+
+#[test]
+fn test_join_outside_window() {
+    let mut join = stream_join(StreamJoinConfig::inner(window()));
+
+    join.push(JoinSide::Left, &message("1", "created", 0));
+    let pairs = join.push(JoinSide::Right, &message("1", "confirmed", 20));
+    assert!(pairs.is_empty());
+    // The left side has not advanced, so its message is kept.
+    assert_eq!(join.buffered(), 2);
+
+    let pairs = join.push(JoinSide::Left, &message("2", "created", 20));
+
+    assert!(pairs.is_empty());
+    // The first left message expired.
+    assert_eq!(join.buffered(), 2);
+    assert_eq!(join.watermark(), Some(20 * SECOND));
+}
+
+#[test]
+fn test_lagging_side_keeps_matches() {
+    let mut join = stream_join(StreamJoinConfig::inner(window()));
+
+    join.push(JoinSide::Right, &message("1", "confirmed", 0));
+    join.push(JoinSide::Right, &message("2", "confirmed", 20));
+    let pairs = join.push(JoinSide::Left, &message("1", "created", 2));
+
+    assert_eq!(summary(&pairs), vec!["1:created->confirmed"]);
+    assert_eq!(join.late_messages(), 0);
+}
+
+#[test]
+fn test_left_outer_join_emits_unmatched() {
+    let mut join = stream_join(StreamJoinConfig::left_outer(window()));
+
+    join.push(JoinSide::Left, &message("1", "created", 0));
+    join.push(JoinSide::Left, &message("2", "created", 1));
+    let pairs = join.push(JoinSide::Right, &message("2", "rejected", 2));
+    assert_eq!(summary(&pairs), vec!["2:created->rejected"]);
+
+    // Only the right side advances, so nothing expires yet.
+    let pairs = join.push(JoinSide::Right, &message("3", "confirmed", 20));
+    assert!(pairs.is_empty());
+
+    // Advances the watermark past the window of both left messages.
+    let pairs = join.push(JoinSide::Left, &message("4", "created", 20));
+
+    assert_eq!(summary(&pairs), vec!["1:created->none"]);
+}
+
+#[test]
+fn test_left_outer_flush() {
+    let mut join = stream_join(StreamJoinConfig::left_outer(window()));
+
+    join.push(JoinSide::Left, &message("1", "created", 0));
+    let pairs = join.flush();
+
+    assert_eq!(summary(&pairs), vec!["1:created->none"]);
+    assert_eq!(join.buffered(), 0);
+}
+
+#[test]
+fn test_late_message_is_dropped() {
+    let mut join = stream_join(StreamJoinConfig::inner(window()));
+
+    join.push(JoinSide::Left, &message("1", "created", 30));
+    join.push(JoinSide::Right, &message("1", "confirmed", 30));
+    let pairs = join.push(JoinSide::Right, &message("1", "confirmed", 1));
+
+    assert!(pairs.is_empty());
+    assert_eq!(join.late_messages(), 1);
+    assert_eq!(join.buffered(), 2);
+}
+
+#[test]
+fn test_left_outer_join_emits_late_left_message() {
+    let mut join = stream_join(StreamJoinConfig::left_outer(window()));
+
+    join.push(JoinSide::Left, &message("1", "created", 30));
+    join.push(JoinSide::Right, &message("1", "confirmed", 30));
+    let pairs = join.push(JoinSide::Left, &message("2", "created", 1));
+
+    assert_eq!(summary(&pairs), vec!["2:created->none"]);
+    assert_eq!(join.late_messages(), 1);
+}
+
+#[test]
+fn test_idle_side_does_not_hold_back_watermark() {
+    let config = StreamJoinConfig::left_outer(window())
+        .with_idle_timeout(IggyDuration::from_str("50ms").unwrap());
+    let mut join = stream_join(config);
+
+    assert!(join
+        .push(JoinSide::Left, &message("1", "created", 0))
+        .is_empty());
+    std::thread::sleep(Duration::from_millis(100));
+
+    // The right side never received a message, so only the left watermark counts.
+    let pairs = join.push(JoinSide::Left, &message("2", "created", 20));
+
+    assert_eq!(summary(&pairs), vec!["1:created->none"]);
+    assert_eq!(join.watermark(), Some(20 * SECOND));
+    assert_eq!(join.buffered(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_pairs_stay_pending() {
+    let join = Arc::new(StreamJoinConsumer::new(
+        stream_join(StreamJoinConfig::inner(window())),
+        RecordingJoinHandler::default(),
+    ));
+    join.left()
+        .consume(message("1", "created", 0))
+        .await
+        .unwrap();
+
+    join.handler().fail.store(true, Ordering::SeqCst);
+    assert!(join
+        .right()
+        .consume(message("1", "confirmed", 1))
+        .await
+        .is_err());
+    // Retried like `run_with_retries` does.
+    assert!(join
+        .right()
+        .consume(message("1", "confirmed", 1))
+        .await
+        .is_err());
+    join.handler().fail.store(false, Ordering::SeqCst);
+    join.right()
+        .consume(message("1", "confirmed", 1))
+        .await
+        .unwrap();
+
+    let pairs = join.handler().pairs.lock().unwrap();
+    assert_eq!(summary(&pairs), vec!["1:created->confirmed"]);
+}
+
+#[tokio::test]
+async fn test_process_skips_joined_offsets() {
+    let join = StreamJoinConsumer::new(
+        stream_join(StreamJoinConfig::inner(window())),
+        RecordingJoinHandler::default(),
+    );
+
+    join.process(JoinSide::Left, 1, message("1", "created", 0))
+        .await
+        .unwrap();
+    join.process(JoinSide::Right, 1, message("1", "confirmed", 1))
+        .await
+        .unwrap();
+    // Delivered again.
+    join.process(JoinSide::Right, 1, message("1", "confirmed", 1))
+        .await
+        .unwrap();
+
+    let pairs = join.handler().pairs.lock().unwrap();
+    assert_eq!(summary(&pairs), vec!["1:created->confirmed"]);
+}
+
+#[tokio::test]
+async fn test_join_side_consumers() {
+    let join = Arc::new(StreamJoinConsumer::new(
+        stream_join(StreamJoinConfig::inner(window())),
+        RecordingJoinHandler::default(),
+    ));
+
+    join.left()
+        .consume(message("1", "created", 0))
+        .await
+        .unwrap();
+    join.right()
+        .consume(message("1", "confirmed", 1))
+        .await
+        .unwrap();
+
+    let pairs = join.handler().pairs.lock().unwrap();
+    assert_eq!(summary(&pairs), vec!["1:created->confirmed"]);
+}
+
+fn stream_join(config: StreamJoinConfig) -> StreamJoin {
+    StreamJoin::new(config, order_id, order_id, MessageTimestamp)
+}
+
+fn window() -> IggyDuration {
+    IggyDuration::from_str("5s").unwrap()
+}
+
+/// The payload is `<order id>:<event>`.
+fn order_id(message: &PolledMessage) -> Option<String> {
+    let payload = String::from_utf8_lossy(&message.payload).to_string();
+    payload.split_once(':').map(|(id, _)| id.to_string())
+}
+
+fn event(message: &PolledMessage) -> String {
+    let payload = String::from_utf8_lossy(&message.payload).to_string();
+    payload.split_once(':').unwrap().1.to_string()
+}
+
+fn message(order_id: &str, event: &str, seconds: u64) -> PolledMessage {
+    PolledMessage {
+        timestamp: seconds * SECOND,
+        ..polled_message(seconds, format!("{order_id}:{event}"))
+    }
+}
+
+/// Formats each pair as `<key>:<left event>-><right event or none>`.
+fn summary(pairs: &[JoinedPair]) -> Vec<String> {
+    pairs
+        .iter()
+        .map(|pair| {
+            let right = pair.right.as_ref().map_or("none".to_string(), event);
+            format!("{}:{}->{right}", pair.key, event(&pair.left))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct RecordingJoinHandler {
+    pairs: Mutex<Vec<JoinedPair>>,
+    fail: AtomicBool,
+}
+
+impl JoinResultHandler for RecordingJoinHandler {
+    async fn handle(&self, pair: JoinedPair) -> Result<(), EventConsumerError> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(EventConsumerError::new("failed".to_string()));
+        }
+        self.pairs.lock().unwrap().push(pair);
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use sdk::builder::{DeduplicationLayer, EventConsumer, EventConsumerError, EventConsumerStack};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_deduplication_layer_skips_seen_ids() {
+    let inner = RecordingEventConsumer::default();
+    let consumer = EventConsumerStack::new(inner.clone())
+        .layer(DeduplicationLayer::new(100, Duration::from_secs(60)))
+        .build();
+
+    for (offset, id) in [(0, 1), (1, 2), (2, 1), (3, 3), (4, 2)] {
+        assert!(consumer.consume(test_message(offset, id)).await.is_ok());
+    }
+
+    assert_eq!(inner.offsets(), vec![0, 1, 3]);
+}
+
+#[tokio::test]
+async fn test_deduplication_layer_evicts_oldest_ids() {
+    let inner = RecordingEventConsumer::default();
+    let consumer = EventConsumerStack::new(inner.clone())
+        .layer(DeduplicationLayer::new(2, Duration::from_secs(60)))
+        .build();
+
+    for (offset, id) in [(0, 1), (1, 2), (2, 3), (3, 1), (4, 3)] {
+        assert!(consumer.consume(test_message(offset, id)).await.is_ok());
+    }
+
+    assert_eq!(inner.offsets(), vec![0, 1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_deduplication_layer_evicts_expired_ids() {
+    let inner = RecordingEventConsumer::default();
+    let consumer = EventConsumerStack::new(inner.clone())
+        .layer(DeduplicationLayer::new(100, Duration::from_millis(10)))
+        .build();
+
+    assert!(consumer.consume(test_message(0, 1)).await.is_ok());
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(consumer.consume(test_message(1, 1)).await.is_ok());
+
+    assert_eq!(inner.offsets(), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn test_deduplication_layer_does_not_record_failed_messages() {
+    let inner = RecordingEventConsumer::default();
+    inner.fail.store(true, Ordering::SeqCst);
+    let consumer = EventConsumerStack::new(inner.clone())
+        .layer(DeduplicationLayer::new(100, Duration::from_secs(60)))
+        .build();
+
+    assert!(consumer.consume(test_message(0, 1)).await.is_err());
+    inner.fail.store(false, Ordering::SeqCst);
+    assert!(consumer.consume(test_message(1, 1)).await.is_ok());
+
+    assert_eq!(inner.offsets(), vec![1]);
+}
+
+#[derive(Debug, Default, Clone)]
+struct RecordingEventConsumer {
+    offsets: Arc<Mutex<Vec<u64>>>,
+    fail: Arc<AtomicBool>,
+}
+
+impl RecordingEventConsumer {
+    fn offsets(&self) -> Vec<u64> {
+        self.offsets.lock().unwrap().clone()
+    }
+}
+
+impl EventConsumer for RecordingEventConsumer {
+    async fn consume(&self, message: PolledMessage) -> Result<(), EventConsumerError> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(EventConsumerError::new("failed".to_string()));
+        }
+        self.offsets.lock().unwrap().push(message.offset);
+        Ok(())
+    }
+}
+
+fn test_message(offset: u64, id: u128) -> PolledMessage {
+    PolledMessage {
+        id,
+        ..polled_message(offset, "test")
+    }
+}
@@ -0,0 +1,75 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use sdk::builder::{
+    CatchPanicLayer, EventConsumer, EventConsumerError, EventConsumerStack, MetricsLayer,
+    TimeoutLayer, TracingLayer,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_stacked_layers_pass_through_success() {
+    let metrics = MetricsLayer::new();
+    let consumer = EventConsumerStack::new(TestEventConsumer::Ok)
+        .layer(CatchPanicLayer::new())
+        .layer(TimeoutLayer::new(Duration::from_secs(1)))
+        .layer(TracingLayer::new("test"))
+        .layer(metrics.clone())
+        .build();
+
+    let res = consumer.consume(test_message(1)).await;
+    assert!(res.is_ok());
+    assert_eq!(metrics.metrics().consumed(), 1);
+    assert_eq!(metrics.metrics().failed(), 0);
+}
+
+#[tokio::test]
+async fn test_timeout_layer_cancels_slow_consumer() {
+    let metrics = MetricsLayer::new();
+    let consumer = EventConsumerStack::new(TestEventConsumer::Slow)
+        .layer(TimeoutLayer::new(Duration::from_millis(10)))
+        .layer(metrics.clone())
+        .build();
+
+    let res = consumer.consume(test_message(7)).await;
+    assert!(res.is_err());
+    assert!(res.unwrap_err().to_string().contains("offset: 7"));
+    assert_eq!(metrics.metrics().failed(), 1);
+}
+
+#[tokio::test]
+async fn test_catch_panic_layer_converts_panic_to_error() {
+    let consumer = EventConsumerStack::new(TestEventConsumer::Panic)
+        .layer(CatchPanicLayer::new())
+        .build();
+
+    let res = consumer.consume(test_message(3)).await;
+    assert!(res.is_err());
+    assert!(res.unwrap_err().to_string().contains("boom"));
+}
+
+#[derive(Debug)]
+enum TestEventConsumer {
+    Ok,
+    Slow,
+    Panic,
+}
+
+impl EventConsumer for TestEventConsumer {
+    async fn consume(&self, _message: PolledMessage) -> Result<(), EventConsumerError> {
+        match self {
+            TestEventConsumer::Ok => Ok(()),
+            TestEventConsumer::Slow => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            }
+            TestEventConsumer::Panic => panic!("boom"),
+        }
+    }
+}
+
+fn test_message(offset: u64) -> PolledMessage {
+    PolledMessage {
+        id: offset as u128,
+        ..polled_message(offset, "test")
+    }
+}
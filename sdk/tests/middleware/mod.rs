@@ -0,0 +1,2 @@
+mod deduplication_layer_tests;
+mod event_consumer_middleware_tests;
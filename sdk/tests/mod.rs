@@ -1,2 +1,19 @@
+mod common;
 mod config;
+mod consumer;
+mod consumer_lag;
+mod event_sourcing;
+mod headers;
+mod join;
+mod middleware;
+mod outbox;
+mod processor;
+mod producer;
+mod projection;
+mod rpc;
+mod state;
 mod stream;
+mod table;
+mod teardown;
+mod topology;
+mod windowing;
@@ -0,0 +1 @@
+mod outbox_tests;
@@ -0,0 +1,142 @@
+use crate::common::RecordingEventProducer;
+use iggy::messages::send_messages::Message;
+use rusqlite::Connection;
+use sdk::builder::{MessageHeadersExt, OutboxConfig, OutboxRelay, OutboxTable};
+use std::path::Path;
+use std::str::FromStr;
+use tokio::sync::oneshot;
+
+#[test]
+fn test_outbox_rolls_back_with_transaction() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut connection, outbox) = open_outbox(&dir.path().join("app.db"));
+
+    let tx = connection.transaction().unwrap();
+    tx.execute(
+        "INSERT INTO orders (id, status) VALUES ('order-1', 'new')",
+        [],
+    )
+    .unwrap();
+    outbox.enqueue(&tx, "order-1", message("created")).unwrap();
+    tx.rollback().unwrap();
+    assert_eq!(outbox.pending(&connection).unwrap(), 0);
+
+    let tx = connection.transaction().unwrap();
+    tx.execute(
+        "INSERT INTO orders (id, status) VALUES ('order-1', 'new')",
+        [],
+    )
+    .unwrap();
+    outbox.enqueue(&tx, "order-1", message("created")).unwrap();
+    tx.commit().unwrap();
+    assert_eq!(outbox.pending(&connection).unwrap(), 1);
+
+    let rows = outbox.unsent(&connection).unwrap();
+    assert_eq!(rows[0].aggregate_id, "order-1");
+    assert_eq!(rows[0].message.partition_key(), Some("order-1"));
+    assert_ne!(rows[0].message.id, 0);
+}
+
+#[tokio::test]
+async fn test_outbox_relay_publishes_in_order_and_marks_sent() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.db");
+    let (connection, outbox) = open_outbox(&path);
+    for (aggregate_id, payload) in [("a", "a1"), ("b", "b1"), ("a", "a2")] {
+        outbox
+            .enqueue(&connection, aggregate_id, message(payload))
+            .unwrap();
+    }
+
+    let relay = OutboxRelay::new(
+        Connection::open(&path).unwrap(),
+        outbox.clone(),
+        RecordingEventProducer::default(),
+    );
+    assert_eq!(relay.relay_once().await.unwrap(), 3);
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+    assert_eq!(relay.producer().payloads(), vec!["a1", "b1", "a2"]);
+    assert_eq!(outbox.pending(&connection).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_outbox_relay_holds_back_failed_aggregate() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.db");
+    let (connection, outbox) = open_outbox(&path);
+    for (aggregate_id, payload) in [("a", "a1"), ("b", "b1"), ("a", "a2"), ("b", "b2")] {
+        outbox
+            .enqueue(&connection, aggregate_id, message(payload))
+            .unwrap();
+    }
+
+    let producer = RecordingEventProducer::default();
+    producer.fail_payload("a1");
+    let relay = OutboxRelay::new(Connection::open(&path).unwrap(), outbox.clone(), producer);
+
+    assert_eq!(relay.relay_once().await.unwrap(), 2);
+    assert_eq!(relay.producer().payloads(), vec!["b1", "b2"]);
+    assert_eq!(outbox.pending(&connection).unwrap(), 2);
+
+    relay.producer().clear_failures();
+    assert_eq!(relay.relay_once().await.unwrap(), 2);
+    assert_eq!(relay.producer().payloads(), vec!["b1", "b2", "a1", "a2"]);
+}
+
+#[tokio::test]
+async fn test_outbox_relay_task_deletes_sent_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.db");
+    let config = OutboxConfig::builder()
+        .table_name("events_outbox")
+        .batch_size(10)
+        .poll_interval(iggy::utils::duration::IggyDuration::from_str("10ms").unwrap())
+        .delete_sent(true)
+        .build();
+    let outbox = OutboxTable::new(config).unwrap();
+    let connection = Connection::open(&path).unwrap();
+    outbox.create_table(&connection).unwrap();
+    outbox.enqueue(&connection, "a", message("a1")).unwrap();
+
+    let relay = OutboxRelay::new(
+        Connection::open(&path).unwrap(),
+        outbox.clone(),
+        RecordingEventProducer::default(),
+    );
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let handle = relay.spawn(shutdown_rx);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    shutdown_tx.send(()).unwrap();
+    handle.await.unwrap();
+
+    let rows: i64 = connection
+        .query_row("SELECT COUNT(*) FROM events_outbox", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(rows, 0);
+}
+
+#[test]
+fn test_outbox_rejects_invalid_table_name() {
+    let config = OutboxConfig::builder()
+        .table_name("outbox; DROP TABLE orders")
+        .batch_size(10)
+        .poll_interval(iggy::utils::duration::IggyDuration::from_str("10ms").unwrap())
+        .delete_sent(false)
+        .build();
+    assert!(OutboxTable::new(config).is_err());
+}
+
+fn open_outbox(path: &Path) -> (Connection, OutboxTable) {
+    let connection = Connection::open(path).unwrap();
+    connection
+        .execute_batch("CREATE TABLE orders (id TEXT PRIMARY KEY, status TEXT NOT NULL)")
+        .unwrap();
+    let outbox = OutboxTable::new(OutboxConfig::default()).unwrap();
+    outbox.create_table(&connection).unwrap();
+    (connection, outbox)
+}
+
+fn message(payload: &str) -> Message {
+    Message::from_str(payload).unwrap()
+}
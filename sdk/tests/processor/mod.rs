@@ -0,0 +1,2 @@
+mod stream_pipeline_tests;
+mod stream_processor_tests;
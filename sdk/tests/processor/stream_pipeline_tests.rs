@@ -0,0 +1,84 @@
+use bytes::Bytes;
+use iggy::messages::send_messages::Message;
+use sdk::builder::{EventConsumerError, StreamPipeline};
+use std::str::FromStr;
+
+#[tokio::test]
+async fn test_empty_pipeline_passes_message_through() {
+    let pipeline = StreamPipeline::new();
+    assert!(pipeline.is_empty());
+
+    let outputs = pipeline.process(message("a")).await.unwrap();
+
+    assert_eq!(payloads(&outputs), vec!["a"]);
+}
+
+#[tokio::test]
+async fn test_pipeline_runs_stages_in_order() {
+    let pipeline = StreamPipeline::new()
+        .filter(|message| !message.payload.is_empty())
+        .map(|message| with_payload(&message, message.payload.to_ascii_uppercase()))
+        .flat_map(|message| {
+            message
+                .payload
+                .split(|byte| *byte == b',')
+                .map(|part| with_payload(&message, part.to_vec()))
+                .collect()
+        })
+        .transform(|message| async move {
+            let payload = format!("{}!", String::from_utf8_lossy(&message.payload));
+            Ok(with_payload(&message, payload.into_bytes()))
+        });
+    assert_eq!(pipeline.len(), 4);
+
+    let outputs = pipeline.process(message("a,b,c")).await.unwrap();
+
+    assert_eq!(payloads(&outputs), vec!["A!", "B!", "C!"]);
+}
+
+#[tokio::test]
+async fn test_pipeline_filter_drops_message() {
+    let pipeline = StreamPipeline::new()
+        .filter(|message| message.payload.as_ref() != b"drop")
+        .transform(|_| async { panic!("Stage after an empty result must not run") });
+
+    let outputs = pipeline.process(message("drop")).await.unwrap();
+
+    assert!(outputs.is_empty());
+}
+
+#[tokio::test]
+async fn test_pipeline_keeps_input_id() {
+    let pipeline = StreamPipeline::new().map(|message| message);
+    let input = message("a");
+    let id = input.id;
+
+    let outputs = pipeline.process(input).await.unwrap();
+
+    assert_eq!(outputs[0].id, id);
+}
+
+#[tokio::test]
+async fn test_pipeline_transform_error() {
+    let pipeline = StreamPipeline::new()
+        .transform(|_| async { Err(EventConsumerError::new("lookup failed".to_string())) });
+
+    let res = pipeline.process(message("a")).await;
+
+    assert!(res.is_err());
+}
+
+fn message(payload: &str) -> Message {
+    Message::from_str(payload).unwrap()
+}
+
+fn with_payload(message: &Message, payload: Vec<u8>) -> Message {
+    Message::new(None, Bytes::from(payload), message.headers.clone())
+}
+
+fn payloads(messages: &[Message]) -> Vec<String> {
+    messages
+        .iter()
+        .map(|message| String::from_utf8_lossy(&message.payload).to_string())
+        .collect()
+}
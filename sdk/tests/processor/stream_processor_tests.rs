@@ -0,0 +1,96 @@
+use crate::common::{polled_message, RecordingEventProducer};
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    ConsumeFailureKind, ConsumeMessagesConfig, EventConsumerError, IggyStreamProcessor,
+    IggyStreamProcessorConfig, MessageHeaders, StreamPipeline,
+};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn test_processor_sends_outputs() {
+    let pipeline = StreamPipeline::new().flat_map(|message| vec![message.clone(), message]);
+    let processor = IggyStreamProcessor::with_producer(RecordingEventProducer::default(), pipeline);
+
+    let produced = processor.process(1, polled_message(0, "a")).await.unwrap();
+
+    assert_eq!(produced, 2);
+    assert_eq!(processor.producer().sent().len(), 2);
+}
+
+#[tokio::test]
+async fn test_processor_keeps_input_headers() {
+    let processor = IggyStreamProcessor::with_producer(
+        RecordingEventProducer::default(),
+        StreamPipeline::new(),
+    );
+    let mut input = polled_message(0, "a");
+    input.headers = Some(MessageHeaders::new().correlation_id("42").build().unwrap());
+
+    processor.process(1, input).await.unwrap();
+
+    let sent = processor.producer().sent();
+    assert_eq!(
+        sent[0].headers.as_ref().map(|headers| headers.len()),
+        Some(1)
+    );
+}
+
+#[tokio::test]
+async fn test_processor_skips_failed_message() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let pipeline = StreamPipeline::new().transform({
+        let attempts = attempts.clone();
+        move |_| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(EventConsumerError::new("invalid order".to_string())) }
+        }
+    });
+    let (failure_sender, mut failures) = mpsc::unbounded_channel();
+    let processor = IggyStreamProcessor::with_producer(RecordingEventProducer::default(), pipeline)
+        .with_consume_config(ConsumeMessagesConfig::new(
+            None,
+            2,
+            IggyDuration::from_str("1ms").unwrap(),
+            Some(failure_sender),
+        ));
+
+    let produced = processor.process(1, polled_message(0, "a")).await.unwrap();
+
+    assert_eq!(produced, 0);
+    assert!(processor.producer().sent().is_empty());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    let failure = failures.try_recv().unwrap();
+    assert_eq!(failure.kind, ConsumeFailureKind::Handler);
+    assert_eq!(failure.attempts, 3);
+}
+
+#[tokio::test]
+async fn test_processor_returns_send_error() {
+    let producer = RecordingEventProducer::default();
+    producer.fail_all();
+    let processor = IggyStreamProcessor::with_producer(producer, StreamPipeline::new());
+
+    let res = processor.process(1, polled_message(0, "a")).await;
+
+    assert!(matches!(res, Err(IggyError::Disconnected)));
+}
+
+#[test]
+fn test_processor_config_from_stream_topics() {
+    let config = IggyStreamProcessorConfig::from_stream_topics(
+        "orders",
+        "raw",
+        "orders",
+        "enriched",
+        100,
+        IggyDuration::from_str("5ms").unwrap(),
+        IggyDuration::from_str("5ms").unwrap(),
+    );
+
+    assert_eq!(config.input_config().topic_name(), "raw");
+    assert_eq!(config.output_config().topic_name(), "enriched");
+}
@@ -0,0 +1,215 @@
+use crate::common::{to_polled, RecordingEventProducer};
+use bytes::Bytes;
+use iggy::messages::send_messages::Message;
+use sdk::builder::{
+    ChunkAssembler, ChunkingEventProducer, EventProducer, MessageHeaders, MessageHeadersExt,
+    CHUNK_COUNT_HEADER,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_chunks_roundtrip() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 100);
+    let message = MessageHeaders::new()
+        .content_type("application/json")
+        .message(document(1050))
+        .unwrap();
+
+    producer.send_one_event(message).await.unwrap();
+
+    let chunks = producer.producer().sent();
+    assert_eq!(chunks.len(), 11);
+    assert!(chunks.iter().all(|chunk| chunk.payload.len() <= 100));
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60));
+    let mut complete = Vec::new();
+    for (offset, chunk) in chunks.into_iter().enumerate() {
+        if let Some(message) = assembler.push(1, to_polled(offset as u64, chunk)).unwrap() {
+            complete.push(message);
+        }
+    }
+
+    assert_eq!(complete.len(), 1);
+    assert_eq!(complete[0].payload, Bytes::from(document(1050)));
+    assert_eq!(complete[0].offset, 10);
+    assert_eq!(complete[0].content_type(), Some("application/json"));
+    assert!(complete[0].header(CHUNK_COUNT_HEADER).is_none());
+    assert_eq!(assembler.pending_transfers(), 0);
+}
+
+#[test]
+fn test_chunk_ids_are_stable_across_splits() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 100);
+    let message = Message::new(Some(7), Bytes::from(document(350)), None);
+
+    let first = producer.split(message.clone()).unwrap();
+    let second = producer.split(message).unwrap();
+
+    let ids = first.iter().map(|chunk| chunk.id).collect::<Vec<_>>();
+    assert_eq!(ids, second.iter().map(|chunk| chunk.id).collect::<Vec<_>>());
+    let mut unique = ids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 4);
+    assert!(ids.iter().all(|id| *id != 0 && *id != 7));
+}
+
+#[tokio::test]
+async fn test_small_messages_are_not_chunked() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 100);
+
+    producer
+        .send_event_batch(vec![message(42, "small"), message(0, &document(150))])
+        .await
+        .unwrap();
+
+    let sent = producer.producer().sent();
+    assert_eq!(sent.len(), 3);
+    assert_eq!(sent[0].payload, Bytes::from("small"));
+    assert!(sent[0].headers.is_none());
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60));
+    let passed = assembler
+        .push(1, to_polled(0, sent[0].clone()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(passed.id, 42);
+}
+
+#[tokio::test]
+async fn test_interleaved_and_out_of_order_chunks() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 10);
+    producer
+        .send_one_event(message(1, &document(30)))
+        .await
+        .unwrap();
+    producer
+        .send_one_event(message(2, &document(25)))
+        .await
+        .unwrap();
+
+    let mut chunks = producer.producer().sent();
+    chunks.swap(0, 2);
+    chunks.swap(1, 4);
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60));
+    let mut complete = Vec::new();
+    for (offset, chunk) in chunks.into_iter().enumerate() {
+        if let Some(message) = assembler.push(1, to_polled(offset as u64, chunk)).unwrap() {
+            complete.push(message);
+        }
+    }
+
+    let mut ids = complete
+        .iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2]);
+    for message in complete {
+        let expected = if message.id == 1 { 30 } else { 25 };
+        assert_eq!(message.payload, Bytes::from(document(expected)));
+    }
+}
+
+#[tokio::test]
+async fn test_corrupted_chunk_fails_checksum() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 10);
+    producer
+        .send_one_event(message(1, &document(20)))
+        .await
+        .unwrap();
+
+    let mut chunks = producer.producer().sent();
+    chunks[1].payload = Bytes::from("xxxxxxxxxx");
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60));
+    assert!(assembler
+        .push(1, to_polled(0, chunks.remove(0)))
+        .unwrap()
+        .is_none());
+    assert!(assembler.push(1, to_polled(1, chunks.remove(0))).is_err());
+    assert_eq!(assembler.pending_transfers(), 0);
+}
+
+#[tokio::test]
+async fn test_incomplete_transfer_expires() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 10);
+    producer
+        .send_one_event(message(7, &document(30)))
+        .await
+        .unwrap();
+    let first = producer.producer().sent().remove(0);
+
+    let mut assembler = ChunkAssembler::new(Duration::from_millis(10));
+    assert!(assembler.push(3, to_polled(5, first)).unwrap().is_none());
+    assert!(assembler.evict_expired().is_empty());
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let expired = assembler.evict_expired();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].transfer_id, 7);
+    assert_eq!(expired[0].partition_id, 3);
+    assert_eq!(expired[0].first_offset, 5);
+    assert_eq!(expired[0].received_chunks, 1);
+    assert_eq!(expired[0].chunk_count, 3);
+    assert_eq!(assembler.pending_transfers(), 0);
+}
+
+#[tokio::test]
+async fn test_transfer_with_too_many_chunks_is_rejected() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 10);
+    producer
+        .send_one_event(message(1, &document(50)))
+        .await
+        .unwrap();
+    let first = producer.producer().sent().remove(0);
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60)).with_limits(4, 1024);
+    assert!(assembler.push(1, to_polled(0, first)).is_err());
+    assert_eq!(assembler.pending_transfers(), 0);
+    assert_eq!(assembler.pending_bytes(), 0);
+}
+
+#[tokio::test]
+async fn test_pending_bytes_limit_drops_transfer() {
+    let producer = ChunkingEventProducer::new(RecordingEventProducer::default(), 10);
+    producer
+        .send_one_event(message(1, &document(30)))
+        .await
+        .unwrap();
+    producer
+        .send_one_event(message(2, &document(30)))
+        .await
+        .unwrap();
+    let chunks = producer.producer().sent();
+
+    let (first, second) = chunks.split_at(3);
+    let push = |assembler: &mut ChunkAssembler, offset: u64, chunk: &Message| {
+        assembler.push(1, to_polled(offset, chunk.clone()))
+    };
+
+    let mut assembler = ChunkAssembler::new(Duration::from_secs(60)).with_limits(16, 30);
+    assert!(push(&mut assembler, 0, &first[0]).unwrap().is_none());
+    assert!(push(&mut assembler, 1, &second[0]).unwrap().is_none());
+    assert!(push(&mut assembler, 2, &second[1]).unwrap().is_none());
+    assert_eq!(assembler.pending_bytes(), 30);
+
+    // The next chunk of the first transfer would exceed the limit, so the transfer is dropped.
+    assert!(push(&mut assembler, 3, &first[1]).is_err());
+    assert_eq!(assembler.pending_transfers(), 1);
+    assert_eq!(assembler.pending_bytes(), 20);
+
+    // The second transfer fits into the freed space.
+    let complete = push(&mut assembler, 4, &second[2]).unwrap().unwrap();
+    assert_eq!(complete.id, 2);
+    assert_eq!(assembler.pending_bytes(), 0);
+}
+
+fn document(len: usize) -> String {
+    (0..len).map(|i| (b'a' + (i % 26) as u8) as char).collect()
+}
+
+fn message(id: u128, payload: &str) -> Message {
+    Message::new(Some(id), Bytes::from(payload.to_string()), None)
+}
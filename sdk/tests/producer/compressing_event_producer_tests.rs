@@ -0,0 +1,122 @@
+use crate::common::{to_polled, RecordingEventProducer};
+use bytes::Bytes;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use sdk::builder::{
+    decompress_polled_message, decompress_polled_message_with_limit, CompressingEventProducer,
+    EventProducer, MessageHeaders, MessageHeadersExt, PayloadCompression,
+};
+
+#[tokio::test]
+async fn test_compressed_payload_roundtrip() {
+    for compression in [
+        PayloadCompression::zstd(),
+        PayloadCompression::lz4(),
+        PayloadCompression::gzip(),
+    ] {
+        let producer =
+            CompressingEventProducer::new(RecordingEventProducer::default(), compression, 64);
+        let message = MessageHeaders::new()
+            .content_type("application/json")
+            .message(large_json())
+            .unwrap();
+
+        producer.send_one_event(message).await.unwrap();
+
+        let sent = producer.producer().sent().remove(0);
+        assert_eq!(sent.content_encoding(), Some(compression.name()));
+        assert_eq!(sent.content_type(), Some("application/json"));
+        assert!(sent.payload.len() < large_json().len());
+
+        let decompressed = decompress_polled_message(to_polled(0, sent)).unwrap();
+        assert_eq!(decompressed.payload, Bytes::from(large_json()));
+        assert_eq!(decompressed.content_encoding(), None);
+        assert_eq!(decompressed.content_type(), Some("application/json"));
+    }
+}
+
+#[tokio::test]
+async fn test_small_payload_is_not_compressed() {
+    let producer = CompressingEventProducer::new(
+        RecordingEventProducer::default(),
+        PayloadCompression::zstd(),
+        1024,
+    );
+
+    producer
+        .send_event_batch(vec![Message::new(None, "small".into(), None)])
+        .await
+        .unwrap();
+
+    let sent = producer.producer().sent();
+    assert_eq!(sent[0].payload, Bytes::from("small"));
+    assert_eq!(sent[0].content_encoding(), None);
+    assert_eq!(producer.stats().compressed_messages(), 0);
+}
+
+#[tokio::test]
+async fn test_compression_stats_ratio() {
+    let producer = CompressingEventProducer::new(
+        RecordingEventProducer::default(),
+        PayloadCompression::lz4(),
+        0,
+    );
+    assert_eq!(producer.stats().ratio(), 1.0);
+
+    producer
+        .send_one_event(Message::new(None, large_json().into(), None))
+        .await
+        .unwrap();
+
+    let stats = producer.stats();
+    assert_eq!(stats.messages(), 1);
+    assert_eq!(stats.compressed_messages(), 1);
+    assert_eq!(stats.uncompressed_bytes(), large_json().len() as u64);
+    assert!(stats.ratio() > 2.0);
+}
+
+#[test]
+fn test_decompress_rejects_unknown_encoding() {
+    let message = MessageHeaders::new()
+        .content_encoding("brotli")
+        .message("payload")
+        .unwrap();
+    assert!(decompress_polled_message(to_polled(0, message)).is_err());
+
+    let plain = Message::new(None, "payload".into(), None);
+    let polled = decompress_polled_message(to_polled(0, plain)).unwrap();
+    assert_eq!(polled.payload, Bytes::from("payload"));
+}
+
+#[test]
+fn test_decompress_enforces_max_size() {
+    let payload = vec![0u8; 1024 * 1024];
+    for compression in [
+        PayloadCompression::zstd(),
+        PayloadCompression::lz4(),
+        PayloadCompression::gzip(),
+    ] {
+        let compressed = compression.compress(&payload).unwrap();
+        assert!(compressed.len() < 64 * 1024);
+
+        let decompressed = compression.decompress(&compressed, payload.len()).unwrap();
+        assert_eq!(decompressed, payload);
+        assert_eq!(
+            compression.decompress(&compressed, payload.len() - 1),
+            Err(IggyError::CannotDeserializeResource)
+        );
+    }
+
+    let message = MessageHeaders::new()
+        .content_encoding("zstd")
+        .message(PayloadCompression::zstd().compress(&payload).unwrap())
+        .unwrap();
+    assert!(decompress_polled_message_with_limit(to_polled(0, message), 1024).is_err());
+}
+
+fn large_json() -> String {
+    let items = (0..200)
+        .map(|i| format!(r#"{{"order_id":{i},"currency_pair":"EUR/USD","price":1.08}}"#))
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
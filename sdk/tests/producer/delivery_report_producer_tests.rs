@@ -0,0 +1,108 @@
+use crate::common::RecordingEventProducer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::DeliveryReportProducer;
+use std::str::FromStr;
+use tokio::sync::oneshot;
+
+#[tokio::test]
+async fn test_delivery_reports_after_full_batch() {
+    let recording = RecordingEventProducer::default();
+    let producer =
+        DeliveryReportProducer::new(recording.clone(), 2, IggyDuration::from_str("1h").unwrap());
+
+    let first = producer.send(message("1")).await;
+    let second = producer.send(message("2")).await;
+    assert!(first.await.is_ok());
+    assert!(second.await.is_ok());
+
+    assert_eq!(recording.batch_payloads(), vec![vec!["1", "2"]]);
+}
+
+#[tokio::test]
+async fn test_flush_sends_partial_batch() {
+    let recording = RecordingEventProducer::default();
+    let producer =
+        DeliveryReportProducer::new(recording.clone(), 10, IggyDuration::from_str("1h").unwrap());
+
+    let delivery = producer.send(message("1")).await;
+    producer.send(message("2")).await;
+    producer.flush().await.unwrap();
+
+    assert_eq!(recording.batch_payloads(), vec![vec!["1", "2"]]);
+    assert!(delivery.await.is_ok());
+}
+
+#[tokio::test]
+async fn test_send_interval_sends_partial_batch() {
+    let recording = RecordingEventProducer::default();
+    let producer = DeliveryReportProducer::new(
+        recording.clone(),
+        10,
+        IggyDuration::from_str("10ms").unwrap(),
+    );
+
+    let res = producer.send(message("1")).await.await;
+
+    assert!(res.is_ok());
+    assert_eq!(recording.batch_payloads(), vec![vec!["1"]]);
+}
+
+#[tokio::test]
+async fn test_delivery_reports_failure() {
+    let recording = RecordingEventProducer::default();
+    recording.fail_all();
+    let producer =
+        DeliveryReportProducer::new(recording, 10, IggyDuration::from_str("1h").unwrap());
+
+    let first = producer.send(message("1")).await;
+    let (sender, receiver) = oneshot::channel();
+    producer
+        .send_with_callback(message("2"), move |res| {
+            let _ = sender.send(res);
+        })
+        .await;
+    producer.flush().await.unwrap();
+
+    assert_eq!(first.await, Err(IggyError::Disconnected));
+    assert_eq!(receiver.await.unwrap(), Err(IggyError::Disconnected));
+}
+
+#[tokio::test]
+async fn test_send_waits_for_queue_capacity() {
+    let recording = RecordingEventProducer::default();
+    let producer =
+        DeliveryReportProducer::new(recording.clone(), 2, IggyDuration::from_str("1h").unwrap());
+
+    let mut deliveries = Vec::new();
+    for payload in ["1", "2", "3", "4", "5"] {
+        deliveries.push(producer.send(message(payload)).await);
+    }
+    producer.flush().await.unwrap();
+    for delivery in deliveries {
+        assert!(delivery.await.is_ok());
+    }
+
+    assert_eq!(
+        recording.batch_payloads(),
+        vec![vec!["1", "2"], vec!["3", "4"], vec!["5"]]
+    );
+}
+
+#[tokio::test]
+async fn test_drop_sends_remaining_messages() {
+    let recording = RecordingEventProducer::default();
+    let producer =
+        DeliveryReportProducer::new(recording.clone(), 10, IggyDuration::from_str("1h").unwrap());
+
+    let delivery = producer.send(message("1")).await;
+    drop(producer);
+
+    assert!(delivery.await.is_ok());
+    assert_eq!(recording.batch_payloads(), vec![vec!["1"]]);
+}
+
+fn message(payload: &str) -> Message {
+    Message::from_str(payload).unwrap()
+}
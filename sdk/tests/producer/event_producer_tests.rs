@@ -0,0 +1,38 @@
+use crate::common::RecordingEventProducer;
+use iggy::clients::producer::IggyProducer;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use sdk::builder::EventProducer;
+use std::str::FromStr;
+
+#[test]
+fn test_iggy_producer_implements_event_producer() {
+    fn assert_event_producer<P: EventProducer>() {}
+    assert_event_producer::<IggyProducer>();
+}
+
+#[tokio::test]
+async fn test_application_code_against_event_producer() {
+    let producer = RecordingEventProducer::default();
+
+    let res = publish_greetings(&producer).await;
+    assert!(res.is_ok());
+
+    let sent = producer.sent();
+    assert_eq!(sent.len(), 3);
+    assert_eq!(sent[0].payload.as_ref(), b"Hello World");
+}
+
+// Application code only depends on the trait, so it works with
+// an `IggyProducer` as well as with the test double below.
+async fn publish_greetings<P: EventProducer>(producer: &P) -> Result<(), IggyError> {
+    producer
+        .send_one_event(Message::from_str("Hello World")?)
+        .await?;
+    producer
+        .send_event_batch(vec![
+            Message::from_str("Hola Iggy")?,
+            Message::from_str("Hi Apache")?,
+        ])
+        .await
+}
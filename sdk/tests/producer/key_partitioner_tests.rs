@@ -0,0 +1,224 @@
+use crate::common::RecordingEventProducer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::send_messages::Message;
+use iggy::partitioner::Partitioner;
+use sdk::builder::{
+    EventProducer, HeaderPartitionKey, KeyPartitioner, KeyedEventProducer, MessageHeaders,
+    MessageHeadersExt, TypedEventProducer,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct OrderEvent {
+    order_id: u64,
+    sequence: u32,
+}
+
+#[test]
+fn test_key_partitioner_same_key_same_partition() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 8);
+
+    for order_id in 0..100 {
+        let key = format!("order-{order_id}");
+        let first = partitioner.partition_for_message(&keyed_message(&key));
+        let second = partitioner.partition_for_message(&keyed_message(&key));
+        assert_eq!(first, second);
+        assert!((1..=8).contains(&first));
+    }
+}
+
+#[test]
+fn test_key_partitioner_spreads_keys() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 4);
+
+    let mut used = (0..100)
+        .map(|order_id| partitioner.partition_for_key(format!("order-{order_id}").as_bytes()))
+        .collect::<Vec<_>>();
+    used.sort();
+    used.dedup();
+    assert_eq!(used, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_key_partitioner_without_key_round_robin() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 3);
+    let stream_id = Identifier::named("stream").unwrap();
+    let topic_id = Identifier::named("topic").unwrap();
+    let message = Message::new(None, "payload".into(), None);
+
+    let partitions = (0..6)
+        .map(|_| {
+            partitioner
+                .calculate_partition_id(&stream_id, &topic_id, std::slice::from_ref(&message))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(partitions, vec![1, 2, 3, 1, 2, 3]);
+}
+
+#[test]
+fn test_key_partitioner_closure_extractor() {
+    let extractor = |message: &Message| Some(message.payload[..1].to_vec());
+    let partitioner = KeyPartitioner::new(Arc::new(extractor), 16);
+
+    let first = Message::new(None, "a-1".into(), None);
+    let second = Message::new(None, "a-2".into(), None);
+    assert_eq!(
+        partitioner.partition_for_message(&first),
+        partitioner.partition_for_message(&second)
+    );
+}
+
+#[test]
+fn test_key_partitioner_rejects_mixed_key_batch() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 8);
+    let stream_id = Identifier::named("stream").unwrap();
+    let topic_id = Identifier::named("topic").unwrap();
+
+    let first = keyed_message("order-1");
+    let other = (2..100)
+        .map(|order_id| keyed_message(&format!("order-{order_id}")))
+        .find(|message| {
+            partitioner.partition_for_message(message) != partitioner.partition_for_message(&first)
+        })
+        .unwrap();
+
+    let res = partitioner.calculate_partition_id(&stream_id, &topic_id, &[first, other]);
+    assert!(matches!(res, Err(IggyError::InvalidCommand)));
+}
+
+#[test]
+fn test_key_partitioner_batch_follows_keyed_messages() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 8);
+    let stream_id = Identifier::named("stream").unwrap();
+    let topic_id = Identifier::named("topic").unwrap();
+    let keyed = keyed_message("order-1");
+    let expected = partitioner.partition_for_message(&keyed);
+
+    let messages = [
+        Message::new(None, "payload".into(), None),
+        keyed.clone(),
+        Message::new(None, "payload".into(), None),
+        keyed,
+    ];
+    for _ in 0..8 {
+        let partition_id = partitioner
+            .calculate_partition_id(&stream_id, &topic_id, &messages)
+            .unwrap();
+        assert_eq!(partition_id, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_typed_producer_groups_batch_by_key() {
+    let producer = TypedEventProducer::<OrderEvent, _>::json(RecordingEventProducer::default())
+        .with_partition_key(|event| event.order_id.to_string());
+
+    let events = [
+        event(1, 1),
+        event(2, 1),
+        event(1, 2),
+        event(2, 2),
+        event(3, 1),
+    ];
+    let res = producer.send_batch(&events).await;
+    assert!(res.is_ok());
+
+    let keys = producer
+        .producer()
+        .batches()
+        .iter()
+        .map(|batch| {
+            batch
+                .iter()
+                .map(|message| message.partition_key().unwrap().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec![vec!["1", "1"], vec!["2", "2"], vec!["3"]]);
+}
+
+#[tokio::test]
+async fn test_keyed_producer_splits_mixed_key_batch() {
+    let partitioner = Arc::new(KeyPartitioner::new(Arc::new(HeaderPartitionKey), 8));
+    let stream_id = Identifier::named("stream").unwrap();
+    let topic_id = Identifier::named("topic").unwrap();
+    let first = keyed_message("order-1");
+    let other_key = (2..100)
+        .map(|order_id| format!("order-{order_id}"))
+        .find(|key| {
+            partitioner.partition_for_message(&keyed_message(key))
+                != partitioner.partition_for_message(&first)
+        })
+        .unwrap();
+    let producer = KeyedEventProducer::new(RecordingEventProducer::default(), partitioner.clone());
+
+    let messages = vec![
+        Message::new(None, "leading".into(), None),
+        first,
+        keyed_message(&other_key),
+        Message::new(None, "unkeyed".into(), None),
+        keyed_message("order-1"),
+    ];
+    producer.send_event_batch(messages).await.unwrap();
+
+    let batches = producer.producer().batches();
+    let payloads = batches
+        .iter()
+        .map(|batch| {
+            batch
+                .iter()
+                .map(|message| {
+                    message
+                        .partition_key()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| String::from_utf8(message.payload.to_vec()).unwrap())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        payloads,
+        vec![
+            vec![
+                "leading".to_string(),
+                "order-1".to_string(),
+                "order-1".to_string()
+            ],
+            vec![other_key.clone(), "unkeyed".to_string()],
+        ]
+    );
+    for batch in &batches {
+        assert!(partitioner
+            .calculate_partition_id(&stream_id, &topic_id, batch)
+            .is_ok());
+    }
+}
+
+#[test]
+fn test_key_partitioner_split_batch_without_keys() {
+    let partitioner = KeyPartitioner::new(Arc::new(HeaderPartitionKey), 8);
+    let messages = vec![
+        Message::new(None, "1".into(), None),
+        Message::new(None, "2".into(), None),
+    ];
+
+    let batches = partitioner.split_batch(messages);
+
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].len(), 2);
+    assert!(partitioner.split_batch(Vec::new()).is_empty());
+}
+
+fn keyed_message(key: &str) -> Message {
+    MessageHeaders::new()
+        .partition_key(key)
+        .message("payload")
+        .unwrap()
+}
+
+fn event(order_id: u64, sequence: u32) -> OrderEvent {
+    OrderEvent { order_id, sequence }
+}
@@ -0,0 +1,8 @@
+mod chunking_event_producer_tests;
+mod compressing_event_producer_tests;
+mod delivery_report_producer_tests;
+mod event_producer_tests;
+mod key_partitioner_tests;
+mod producer_spool_tests;
+mod retrying_event_producer_tests;
+mod typed_event_producer_tests;
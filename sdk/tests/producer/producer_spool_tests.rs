@@ -0,0 +1,287 @@
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use sdk::builder::{
+    EventProducer, MessageSpool, ProducerSpoolConfig, SpoolOverflowPolicy, SpooledEventProducer,
+};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+#[tokio::test]
+async fn test_spooled_producer_sends_directly_when_connected() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ProducerSpoolConfig::from_path(dir.path().join("producer.spool"));
+    let producer = SpooledEventProducer::new(FlakyEventProducer::connected(), &config).unwrap();
+
+    producer.send_one_event(message("1")).await.unwrap();
+
+    assert_eq!(producer.producer().sent_payloads(), vec!["1"]);
+    assert_eq!(producer.spooled_bytes().await, 0);
+}
+
+#[tokio::test]
+async fn test_spooled_producer_replays_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ProducerSpoolConfig::from_path(dir.path().join("producer.spool"));
+    let producer = SpooledEventProducer::new(FlakyEventProducer::connected(), &config).unwrap();
+
+    producer.send_one_event(message("1")).await.unwrap();
+    producer.producer().set_connected(false);
+    producer.send_one_event(message("2")).await.unwrap();
+    producer
+        .send_event_batch(vec![message("3"), message("4")])
+        .await
+        .unwrap();
+    assert!(producer.spooled_bytes().await > 0);
+
+    producer.producer().set_connected(true);
+    producer.send_one_event(message("5")).await.unwrap();
+
+    assert_eq!(
+        producer.producer().sent_payloads(),
+        vec!["1", "2", "3", "4", "5"]
+    );
+    assert_eq!(producer.spooled_bytes().await, 0);
+}
+
+#[tokio::test]
+async fn test_spooled_producer_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ProducerSpoolConfig::from_path(dir.path().join("producer.spool"));
+
+    {
+        let producer =
+            SpooledEventProducer::new(FlakyEventProducer::disconnected(), &config).unwrap();
+        producer.send_one_event(message("1")).await.unwrap();
+        producer.send_one_event(message("2")).await.unwrap();
+    }
+
+    let producer = SpooledEventProducer::new(FlakyEventProducer::connected(), &config).unwrap();
+    let replayed = producer.replay().await.unwrap();
+
+    assert_eq!(replayed, 2);
+    assert_eq!(producer.producer().sent_payloads(), vec!["1", "2"]);
+}
+
+#[tokio::test]
+async fn test_spooled_producer_returns_non_transient_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ProducerSpoolConfig::from_path(dir.path().join("producer.spool"));
+    let producer = SpooledEventProducer::new(FlakyEventProducer::connected(), &config).unwrap();
+    producer.producer().fail_with_invalid_message();
+
+    let res = producer.send_one_event(message("1")).await;
+
+    assert!(res.is_err());
+    assert_eq!(producer.spooled_bytes().await, 0);
+}
+
+#[tokio::test]
+async fn test_spooled_producer_moves_rejected_message_to_dead_letter_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ProducerSpoolConfig::from_path(dir.path().join("producer.spool"));
+    let producer = SpooledEventProducer::new(FlakyEventProducer::disconnected(), &config).unwrap();
+    producer
+        .send_event_batch(vec![message("1"), message("2"), message("3")])
+        .await
+        .unwrap();
+
+    producer.producer().set_connected(true);
+    producer.producer().reject_payload("2");
+    producer.send_one_event(message("4")).await.unwrap();
+
+    assert_eq!(producer.producer().sent_payloads(), vec!["1", "3", "4"]);
+    assert_eq!(producer.spooled_bytes().await, 0);
+    let dead_letters = ProducerSpoolConfig::from_path(dir.path().join("producer.spool.dead"));
+    let mut dead_letters = MessageSpool::open(&dead_letters).unwrap();
+    assert_eq!(drain(&mut dead_letters), vec!["2"]);
+}
+
+#[test]
+fn test_spool_rejects_whole_batch_that_does_not_fit() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = spool_config(
+        dir.path().join("reject.spool"),
+        1024,
+        SpoolOverflowPolicy::Reject,
+    );
+    let mut spool = MessageSpool::open(&config).unwrap();
+    let messages = vec![message("1"), message(&"2".repeat(1000)), message("3")];
+
+    assert!(spool.append_all(&messages).is_err());
+    assert!(spool.is_empty());
+
+    assert_eq!(spool.append_all(&messages[..1]).unwrap(), 1);
+    assert_eq!(drain(&mut spool), vec!["1"]);
+}
+
+#[test]
+fn test_spool_overflow_policies() {
+    let dir = tempfile::tempdir().unwrap();
+    let record_size = {
+        let config = spool_config(
+            dir.path().join("size.spool"),
+            1024,
+            SpoolOverflowPolicy::Reject,
+        );
+        let mut spool = MessageSpool::open(&config).unwrap();
+        spool.append(&message("1")).unwrap();
+        spool.size_bytes()
+    };
+
+    let config = spool_config(
+        dir.path().join("reject.spool"),
+        record_size * 2,
+        SpoolOverflowPolicy::Reject,
+    );
+    let mut spool = MessageSpool::open(&config).unwrap();
+    assert!(spool.append(&message("1")).unwrap());
+    assert!(spool.append(&message("2")).unwrap());
+    assert!(spool.append(&message("3")).is_err());
+
+    let config = spool_config(
+        dir.path().join("drop_newest.spool"),
+        record_size * 2,
+        SpoolOverflowPolicy::DropNewest,
+    );
+    let mut spool = MessageSpool::open(&config).unwrap();
+    spool.append(&message("1")).unwrap();
+    spool.append(&message("2")).unwrap();
+    assert!(!spool.append(&message("3")).unwrap());
+    assert_eq!(drain(&mut spool), vec!["1", "2"]);
+
+    let config = spool_config(
+        dir.path().join("drop_oldest.spool"),
+        record_size * 2,
+        SpoolOverflowPolicy::DropOldest,
+    );
+    let mut spool = MessageSpool::open(&config).unwrap();
+    spool.append(&message("1")).unwrap();
+    spool.append(&message("2")).unwrap();
+    assert!(spool.append(&message("3")).unwrap());
+    assert_eq!(drain(&mut spool), vec!["2", "3"]);
+    assert!(spool.is_empty());
+}
+
+#[test]
+fn test_spool_truncates_torn_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    for torn_bytes in [2, 10] {
+        let path = dir.path().join(format!("torn_{torn_bytes}.spool"));
+        let config = spool_config(path.clone(), 1024, SpoolOverflowPolicy::Reject);
+
+        let mut spool = MessageSpool::open(&config).unwrap();
+        spool.append(&message("1")).unwrap();
+        spool.append(&message("2")).unwrap();
+        let complete_len = std::fs::metadata(&path).unwrap().len();
+        drop(spool);
+
+        // Simulate a crash in the middle of appending a third record.
+        let mut record = (message("3").to_bytes().len() as u32)
+            .to_le_bytes()
+            .to_vec();
+        record.extend_from_slice(&message("3").to_bytes());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&record[..torn_bytes]).unwrap();
+        drop(file);
+
+        let mut spool = MessageSpool::open(&config).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), complete_len);
+        spool.append(&message("4")).unwrap();
+        assert_eq!(drain(&mut spool), vec!["1", "2", "4"]);
+    }
+}
+
+fn spool_config(
+    path: std::path::PathBuf,
+    max_size_bytes: u64,
+    overflow_policy: SpoolOverflowPolicy,
+) -> ProducerSpoolConfig {
+    ProducerSpoolConfig::builder()
+        .path(path)
+        .max_size_bytes(max_size_bytes)
+        .overflow_policy(overflow_policy)
+        .replay_interval(iggy::utils::duration::IggyDuration::from_str("1s").unwrap())
+        .build()
+}
+
+fn drain(spool: &mut MessageSpool) -> Vec<String> {
+    let mut payloads = Vec::new();
+    while let Some(message) = spool.pop().unwrap() {
+        payloads.push(String::from_utf8(message.payload.to_vec()).unwrap());
+    }
+    payloads
+}
+
+fn message(payload: &str) -> Message {
+    Message::from_str(payload).unwrap()
+}
+
+#[derive(Debug, Default)]
+struct FlakyEventProducer {
+    connected: AtomicBool,
+    invalid_message: AtomicBool,
+    rejected_payload: Mutex<Option<String>>,
+    sent: Mutex<Vec<Message>>,
+}
+
+impl FlakyEventProducer {
+    fn connected() -> Self {
+        let producer = Self::default();
+        producer.set_connected(true);
+        producer
+    }
+
+    fn disconnected() -> Self {
+        Self::default()
+    }
+
+    fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::SeqCst);
+    }
+
+    fn fail_with_invalid_message(&self) {
+        self.invalid_message.store(true, Ordering::SeqCst);
+    }
+
+    fn reject_payload(&self, payload: &str) {
+        *self.rejected_payload.lock().unwrap() = Some(payload.to_string());
+    }
+
+    fn sent_payloads(&self) -> Vec<String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|message| String::from_utf8(message.payload.to_vec()).unwrap())
+            .collect()
+    }
+}
+
+impl EventProducer for FlakyEventProducer {
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_event_batch(vec![message]).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        if self.invalid_message.load(Ordering::SeqCst) {
+            return Err(IggyError::InvalidMessagePayloadLength);
+        }
+        if !self.connected.load(Ordering::SeqCst) {
+            return Err(IggyError::Disconnected);
+        }
+        if let Some(rejected) = self.rejected_payload.lock().unwrap().as_deref() {
+            if messages
+                .iter()
+                .any(|message| *message.payload == *rejected.as_bytes())
+            {
+                return Err(IggyError::InvalidMessagePayloadLength);
+            }
+        }
+        self.sent.lock().unwrap().extend(messages);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{EventProducer, ProducerRetryPolicy, RetryingEventProducer};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_retrying_producer_keeps_message_ids_across_attempts() {
+    let producer = RetryingEventProducer::new(FailingEventProducer::new(2), retry_policy(3));
+
+    let res = producer.send_one_event(message("1")).await;
+
+    assert!(res.is_ok());
+    let attempts = producer.producer().attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 3);
+    assert_ne!(attempts[0], 0);
+    assert!(attempts.iter().all(|id| *id == attempts[0]));
+}
+
+#[tokio::test]
+async fn test_retrying_producer_keeps_existing_ids() {
+    let producer = RetryingEventProducer::new(FailingEventProducer::new(0), retry_policy(3));
+
+    let res = producer
+        .send_one_event(Message::new(Some(42), "1".into(), None))
+        .await;
+
+    assert!(res.is_ok());
+    assert_eq!(*producer.producer().attempts.lock().unwrap(), vec![42]);
+}
+
+#[tokio::test]
+async fn test_retrying_producer_gives_up_after_max_retries() {
+    let producer = RetryingEventProducer::new(FailingEventProducer::new(10), retry_policy(2));
+
+    let res = producer.send_one_event(message("1")).await;
+
+    assert_eq!(res, Err(IggyError::Disconnected));
+    assert_eq!(producer.producer().attempts.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_retrying_producer_does_not_retry_permanent_errors() {
+    let failing = FailingEventProducer::new(10);
+    failing.permanent.store(1, Ordering::SeqCst);
+    let producer = RetryingEventProducer::new(failing, retry_policy(3));
+
+    let res = producer.send_one_event(message("1")).await;
+
+    assert_eq!(
+        res,
+        Err(IggyError::TopicNameNotFound(String::new(), String::new()))
+    );
+    assert_eq!(producer.producer().attempts.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = ProducerRetryPolicy::new(
+        5,
+        IggyDuration::from_str("100ms").unwrap(),
+        IggyDuration::from_str("1s").unwrap(),
+    );
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.backoff(5), Duration::from_secs(1));
+    assert_eq!(policy.backoff(64), Duration::from_secs(1));
+}
+
+fn retry_policy(max_retries: u32) -> ProducerRetryPolicy {
+    ProducerRetryPolicy::new(
+        max_retries,
+        IggyDuration::from_str("1ms").unwrap(),
+        IggyDuration::from_str("5ms").unwrap(),
+    )
+}
+
+fn message(payload: &str) -> Message {
+    Message::from_str(payload).unwrap()
+}
+
+#[derive(Debug)]
+struct FailingEventProducer {
+    failures: AtomicU32,
+    permanent: AtomicU32,
+    attempts: Mutex<Vec<u128>>,
+}
+
+impl FailingEventProducer {
+    fn new(failures: u32) -> Self {
+        Self {
+            failures: AtomicU32::new(failures),
+            permanent: AtomicU32::new(0),
+            attempts: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl EventProducer for FailingEventProducer {
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.send_event_batch(vec![message]).await
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        self.attempts.lock().unwrap().push(messages[0].id);
+        if self.permanent.load(Ordering::SeqCst) > 0 {
+            return Err(IggyError::TopicNameNotFound(String::new(), String::new()));
+        }
+        if self.failures.load(Ordering::SeqCst) > 0 {
+            self.failures.fetch_sub(1, Ordering::SeqCst);
+            return Err(IggyError::Disconnected);
+        }
+        Ok(())
+    }
+}
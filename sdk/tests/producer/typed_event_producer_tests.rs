@@ -0,0 +1,70 @@
+use crate::common::RecordingEventProducer;
+use sdk::builder::{
+    BincodeCodec, EventCodec, JsonCodec, MessagePackCodec, TypedEventProducer, CONTENT_TYPE_HEADER,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct OrderCreated {
+    order_id: u64,
+    currency_pair: String,
+    price: f64,
+}
+
+#[tokio::test]
+async fn test_typed_producer_sets_content_type() {
+    let producer = TypedEventProducer::<OrderCreated, _>::json(RecordingEventProducer::default());
+
+    let res = producer.send(&order(1)).await;
+    assert!(res.is_ok());
+
+    let sent = producer.producer().sent();
+    let headers = sent[0].headers.as_ref().expect("Missing headers");
+    let content_type = headers
+        .get(&CONTENT_TYPE_HEADER.try_into().unwrap())
+        .expect("Missing content type");
+    assert_eq!(content_type.as_str().unwrap(), "application/json");
+
+    let decoded: OrderCreated = JsonCodec::default().decode(&sent[0].payload).unwrap();
+    assert_eq!(decoded, order(1));
+}
+
+#[tokio::test]
+async fn test_typed_producer_send_batch() {
+    let producer = TypedEventProducer::<OrderCreated, _, _>::new(
+        RecordingEventProducer::default(),
+        MessagePackCodec::default(),
+    );
+
+    let res = producer.send_batch(&[order(1), order(2)]).await;
+    assert!(res.is_ok());
+
+    let sent = producer.producer().sent();
+    assert_eq!(sent.len(), 2);
+    let decoded: OrderCreated = MessagePackCodec::default()
+        .decode(&sent[1].payload)
+        .unwrap();
+    assert_eq!(decoded, order(2));
+}
+
+#[test]
+fn test_codecs_roundtrip() {
+    assert_roundtrip(JsonCodec::default());
+    assert_roundtrip(BincodeCodec::default());
+    assert_roundtrip(MessagePackCodec::default());
+}
+
+fn assert_roundtrip<C: EventCodec>(codec: C) {
+    let payload = codec.encode(&order(42)).unwrap();
+    let decoded: OrderCreated = codec.decode(&payload).unwrap();
+    assert_eq!(decoded, order(42));
+    assert!(codec.decode::<OrderCreated>(b"\xff\xfe").is_err());
+}
+
+fn order(order_id: u64) -> OrderCreated {
+    OrderCreated {
+        order_id,
+        currency_pair: "EUR/USD".to_string(),
+        price: 1.08,
+    }
+}
@@ -0,0 +1,87 @@
+use sdk::builder::{
+    Checkpoint, CheckpointStore, EventConsumerError, FileCheckpointStore, SqliteCheckpointStore,
+    StateStoreConfig,
+};
+
+#[test]
+fn test_file_checkpoint_store_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.checkpoint");
+    {
+        let store = FileCheckpointStore::open(&path).unwrap();
+        assert!(store.load().unwrap().is_empty());
+
+        store.commit(checkpoint(2, 10), &mut |_| Ok(())).unwrap();
+        store.commit(checkpoint(1, 4), &mut |_| Ok(())).unwrap();
+        store.commit(checkpoint(2, 12), &mut |_| Ok(())).unwrap();
+    }
+
+    let store = FileCheckpointStore::open(&path).unwrap();
+
+    assert_eq!(
+        store.load().unwrap(),
+        vec![checkpoint(1, 4), checkpoint(2, 12)]
+    );
+}
+
+#[test]
+fn test_file_checkpoint_store_keeps_checkpoint_on_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileCheckpointStore::open(dir.path().join("orders.checkpoint")).unwrap();
+    store.commit(checkpoint(1, 4), &mut |_| Ok(())).unwrap();
+
+    let res = store.commit(checkpoint(1, 8), &mut |_| {
+        Err(EventConsumerError::new("failed".to_string()))
+    });
+
+    assert!(res.is_err());
+    assert_eq!(store.load().unwrap(), vec![checkpoint(1, 4)]);
+}
+
+#[test]
+fn test_file_checkpoint_store_invalid_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.checkpoint");
+    std::fs::write(&path, "1 4\nnot a checkpoint\n").unwrap();
+
+    assert!(FileCheckpointStore::open(&path).is_err());
+}
+
+#[test]
+fn test_sqlite_checkpoint_store_commits_with_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::new(dir.path().join("orders.db"), "orders");
+    let store = SqliteCheckpointStore::open(&config).unwrap();
+    store
+        .connection()
+        .execute_batch("CREATE TABLE orders (id TEXT)")
+        .unwrap();
+
+    store
+        .commit(checkpoint(1, 0), &mut |tx| {
+            tx.execute("INSERT INTO orders VALUES ('1')", [])
+                .map_err(|err| EventConsumerError::new(err.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+    let res = store.commit(checkpoint(1, 1), &mut |tx| {
+        tx.execute("INSERT INTO orders VALUES ('2')", [])
+            .map_err(|err| EventConsumerError::new(err.to_string()))?;
+        Err(EventConsumerError::new("failed".to_string()))
+    });
+
+    assert!(res.is_err());
+    let orders: i64 = store
+        .connection()
+        .query_row("SELECT COUNT(*) FROM orders", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(orders, 1);
+    assert_eq!(store.load().unwrap(), vec![checkpoint(1, 0)]);
+}
+
+fn checkpoint(partition_id: u32, offset: u64) -> Checkpoint {
+    Checkpoint {
+        partition_id,
+        offset,
+    }
+}
@@ -0,0 +1,2 @@
+mod checkpoint_store_tests;
+mod projection_runner_tests;
@@ -0,0 +1,175 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use rusqlite::Transaction;
+use sdk::builder::{
+    CheckpointStore, ConsumeMessagesConfig, EventConsumerError, IggyConsumerConfig, Projection,
+    ProjectionRunner, SqliteCheckpointStore, StateStoreConfig,
+};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Stores the payload of every message, fails on the payload `fail` and blocks
+/// for 100ms on the payload `slow`.
+#[derive(Debug)]
+struct OrderIds;
+
+impl Projection for OrderIds {
+    type Store = SqliteCheckpointStore;
+
+    fn project(
+        &self,
+        tx: &mut Transaction<'_>,
+        partition_id: u32,
+        messages: &[PolledMessage],
+    ) -> Result<(), EventConsumerError> {
+        for message in messages {
+            let id = String::from_utf8_lossy(&message.payload).to_string();
+            tx.execute(
+                "INSERT INTO order_ids (partition_id, id) VALUES (?1, ?2)",
+                rusqlite::params![partition_id, id],
+            )
+            .map_err(|err| EventConsumerError::new(err.to_string()))?;
+            if id == "fail" {
+                return Err(EventConsumerError::new("failed".to_string()));
+            }
+            if id == "slow" {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_process_batch_commits_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = runner(&dir);
+
+    let projected = runner
+        .process_batch(1, vec![polled_message(0, "a"), polled_message(1, "b")])
+        .unwrap();
+
+    assert_eq!(projected, 2);
+    assert_eq!(order_ids(&runner), vec!["a", "b"]);
+    assert_eq!(runner.store().load().unwrap()[0].offset, 1);
+}
+
+#[test]
+fn test_process_batch_skips_checkpointed_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = runner(&dir);
+    runner
+        .process_batch(1, vec![polled_message(0, "a"), polled_message(1, "b")])
+        .unwrap();
+
+    // Redelivered after a restart, overlapping the checkpoint.
+    assert_eq!(
+        runner
+            .process_batch(1, vec![polled_message(0, "a")])
+            .unwrap(),
+        0
+    );
+    let projected = runner
+        .process_batch(1, vec![polled_message(1, "b"), polled_message(2, "c")])
+        .unwrap();
+
+    assert_eq!(projected, 1);
+    assert_eq!(order_ids(&runner), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_process_batch_rolls_back_on_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = runner(&dir);
+    runner
+        .process_batch(1, vec![polled_message(0, "a")])
+        .unwrap();
+
+    let res = runner.process_batch(1, vec![polled_message(1, "b"), polled_message(2, "fail")]);
+
+    assert!(res.is_err());
+    assert_eq!(order_ids(&runner), vec!["a"]);
+    assert_eq!(runner.store().load().unwrap()[0].offset, 0);
+}
+
+#[tokio::test]
+async fn test_process_polled_batch_skips_failed_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let (failure_sender, mut failures) = mpsc::unbounded_channel();
+    let runner = runner(&dir).with_consume_config(ConsumeMessagesConfig::new(
+        None,
+        1,
+        IggyDuration::from_str("1ms").unwrap(),
+        Some(failure_sender),
+    ));
+
+    let next_offset = runner
+        .process_polled_batch(
+            1,
+            vec![
+                polled_message(0, "a"),
+                polled_message(1, "fail"),
+                polled_message(2, "b"),
+            ],
+        )
+        .await;
+
+    assert_eq!(next_offset, Some(3));
+    assert_eq!(order_ids(&runner), vec!["a", "b"]);
+    assert_eq!(runner.store().load().unwrap()[0].offset, 2);
+    // Only the failed message is reported, not the batch that contained it.
+    assert_eq!(failures.try_recv().unwrap().offset, 1);
+    assert!(failures.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_process_polled_batch_times_out_blocking_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    let (failure_sender, mut failures) = mpsc::unbounded_channel();
+    let runner = runner(&dir).with_consume_config(ConsumeMessagesConfig::new(
+        Some(IggyDuration::from_str("20ms").unwrap()),
+        20,
+        IggyDuration::from_str("1ms").unwrap(),
+        Some(failure_sender),
+    ));
+
+    let started = Instant::now();
+    let (next_offset, ticked_after) = tokio::join!(
+        runner.process_polled_batch(1, vec![polled_message(0, "slow")]),
+        async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            started.elapsed()
+        }
+    );
+
+    // The commit runs off the runtime thread, so other tasks keep running.
+    assert!(ticked_after < Duration::from_millis(90));
+    assert_eq!(next_offset, Some(1));
+    // The retries wait for the timed out commit instead of projecting the message again.
+    assert_eq!(order_ids(&runner), vec!["slow"]);
+    assert!(failures.try_recv().is_err());
+}
+
+fn runner(dir: &tempfile::TempDir) -> ProjectionRunner<OrderIds> {
+    let config = StateStoreConfig::new(dir.path().join("orders.db"), "order_ids");
+    let store = SqliteCheckpointStore::open(&config).unwrap();
+    store
+        .connection()
+        .execute_batch("CREATE TABLE order_ids (partition_id INTEGER, id TEXT)")
+        .unwrap();
+    ProjectionRunner::new(IggyConsumerConfig::default(), OrderIds, store)
+}
+
+fn order_ids(runner: &ProjectionRunner<OrderIds>) -> Vec<String> {
+    let connection = runner.store().connection();
+    let mut statement = connection
+        .prepare("SELECT id FROM order_ids ORDER BY rowid")
+        .unwrap();
+    statement
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
@@ -0,0 +1 @@
+mod rpc_tests;
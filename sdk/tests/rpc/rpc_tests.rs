@@ -0,0 +1,206 @@
+use crate::common::{to_polled, RecordingEventProducer};
+use futures::future::join_all;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+use sdk::builder::{
+    EventConsumer, EventConsumerError, EventProducer, MessageHeadersExt, RpcClient, RpcError,
+    RpcHandler, RpcReplyRouter, RpcReplySender, RpcServer,
+};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+const REPLY_TO: &str = "replies/rpc";
+
+#[tokio::test]
+async fn test_rpc_round_trip() {
+    let client = loopback_client(EchoHandler, Duration::from_secs(5));
+
+    let reply = client
+        .call(Message::from_str("ping").unwrap())
+        .await
+        .expect("Call failed");
+
+    assert_eq!(reply.payload.as_ref(), b"echo: ping");
+    assert!(reply.correlation_id().is_some());
+    assert_eq!(client.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_rpc_request_headers() {
+    let producer = RecordingEventProducer::default();
+    let client = RpcClient::new(producer, REPLY_TO, Duration::from_millis(50));
+
+    let res = client.call(Message::from_str("ping").unwrap()).await;
+    assert!(matches!(res, Err(RpcError::Timeout(_))));
+
+    let sent = client_requests(&client);
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].reply_to(), Some(REPLY_TO));
+    assert!(sent[0].correlation_id().is_some());
+}
+
+#[tokio::test]
+async fn test_rpc_timeout_removes_pending_call() {
+    let client = RpcClient::new(
+        RecordingEventProducer::default(),
+        REPLY_TO,
+        Duration::from_millis(50),
+    );
+
+    let res = client.call(Message::from_str("ping").unwrap()).await;
+
+    assert!(matches!(res, Err(RpcError::Timeout(_))));
+    assert_eq!(client.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_rpc_remote_error() {
+    let client = loopback_client(FailingHandler, Duration::from_secs(5));
+
+    let res = client.call(Message::from_str("ping").unwrap()).await;
+
+    match res {
+        Err(RpcError::Remote(err)) => assert_eq!(err, "unknown command"),
+        other => panic!("Expected remote error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_concurrent_calls() {
+    let client = loopback_client(DelayedEchoHandler, Duration::from_secs(5));
+
+    let calls = (0..20).map(|i| {
+        let client = &client;
+        async move {
+            let reply = client
+                .call(Message::from_str(&i.to_string()).unwrap())
+                .await
+                .expect("Call failed");
+            (i, reply)
+        }
+    });
+
+    for (i, reply) in join_all(calls).await {
+        assert_eq!(reply.payload.as_ref(), format!("echo: {i}").as_bytes());
+    }
+    assert_eq!(client.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_router_close_fails_pending_calls() {
+    let client = Arc::new(RpcClient::new(
+        RecordingEventProducer::default(),
+        REPLY_TO,
+        Duration::from_secs(5),
+    ));
+
+    let call = {
+        let client = client.clone();
+        tokio::spawn(async move { client.call(Message::from_str("ping").unwrap()).await })
+    };
+    while client.in_flight() == 0 {
+        tokio::task::yield_now().await;
+    }
+    client.router().close();
+
+    let res = call.await.unwrap();
+    assert!(matches!(res, Err(RpcError::Closed)));
+    assert_eq!(client.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_router_ignores_unknown_reply() {
+    let client = RpcClient::new(
+        RecordingEventProducer::default(),
+        REPLY_TO,
+        Duration::from_millis(50),
+    );
+    let router = client.router();
+
+    assert!(!router.route(to_polled(0, Message::from_str("orphan").unwrap())));
+    assert!(router
+        .consume(to_polled(0, Message::from_str("orphan").unwrap()))
+        .await
+        .is_ok());
+}
+
+fn loopback_client<H>(handler: H, timeout: Duration) -> RpcClient<LoopbackEventProducer<H>>
+where
+    H: RpcHandler + Send + Sync + 'static,
+{
+    let sender = Arc::new(RouterReplySender::default());
+    let server = RpcServer::new(handler, sender.clone(), 4);
+    let client = RpcClient::new(LoopbackEventProducer { server }, REPLY_TO, timeout);
+    sender.router.set(client.router()).unwrap();
+    client
+}
+
+fn client_requests(client: &RpcClient<RecordingEventProducer>) -> Vec<Message> {
+    client.producer().sent()
+}
+
+struct EchoHandler;
+
+impl RpcHandler for EchoHandler {
+    async fn handle(&self, request: PolledMessage) -> Result<Message, EventConsumerError> {
+        let payload = String::from_utf8_lossy(&request.payload).to_string();
+        Ok(Message::from_str(&format!("echo: {payload}")).unwrap())
+    }
+}
+
+struct DelayedEchoHandler;
+
+impl RpcHandler for DelayedEchoHandler {
+    async fn handle(&self, request: PolledMessage) -> Result<Message, EventConsumerError> {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        EchoHandler.handle(request).await
+    }
+}
+
+struct FailingHandler;
+
+impl RpcHandler for FailingHandler {
+    async fn handle(&self, _request: PolledMessage) -> Result<Message, EventConsumerError> {
+        Err(EventConsumerError::new("unknown command".to_string()))
+    }
+}
+
+/// Hands every request straight to the server.
+struct LoopbackEventProducer<H> {
+    server: RpcServer<H, RouterReplySender>,
+}
+
+impl<H> EventProducer for LoopbackEventProducer<H>
+where
+    H: RpcHandler + Send + Sync + 'static,
+{
+    async fn send_one_event(&self, message: Message) -> Result<(), IggyError> {
+        self.server
+            .consume(to_polled(0, message))
+            .await
+            .map_err(|_| IggyError::InvalidCommand)
+    }
+
+    async fn send_event_batch(&self, messages: Vec<Message>) -> Result<(), IggyError> {
+        for message in messages {
+            self.send_one_event(message).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Hands every reply straight to the router of the client.
+#[derive(Default)]
+struct RouterReplySender {
+    router: OnceLock<RpcReplyRouter>,
+}
+
+impl RpcReplySender for RouterReplySender {
+    async fn send_reply(&self, reply_to: &str, message: Message) -> Result<(), IggyError> {
+        assert_eq!(reply_to, REPLY_TO);
+        self.router.get().unwrap().route(to_polled(0, message));
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+mod state_store_tests;
+mod stateful_consumer_tests;
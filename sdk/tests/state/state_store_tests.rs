@@ -0,0 +1,96 @@
+use sdk::builder::{
+    Checkpoint, InMemoryStateStore, SqliteStateStore, StateStore, StateStoreConfig,
+    StateTransaction,
+};
+
+#[test]
+fn test_sqlite_state_store_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::from_path(dir.path().join("state.db"));
+
+    {
+        let store = SqliteStateStore::open(&config).unwrap();
+        let mut state = StateTransaction::new(&store);
+        state.put("order-1", "paid");
+        state.put("order-2", "created");
+        let changes = state.changes().clone();
+        store.commit(&changes, checkpoint(0, 7)).unwrap();
+    }
+
+    let store = SqliteStateStore::open(&config).unwrap();
+
+    assert_eq!(store.get(b"order-1").unwrap(), Some(b"paid".to_vec()));
+    assert_eq!(store.get(b"order-2").unwrap(), Some(b"created".to_vec()));
+    assert_eq!(store.checkpoint(0).unwrap(), Some(7));
+    assert_eq!(store.checkpoint(1).unwrap(), None);
+}
+
+#[test]
+fn test_sqlite_state_store_delete() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::from_path(dir.path().join("state.db"));
+    let store = SqliteStateStore::open(&config).unwrap();
+    commit(
+        &store,
+        |state| state.put("order-1", "paid"),
+        checkpoint(0, 1),
+    );
+
+    commit(&store, |state| state.delete("order-1"), checkpoint(0, 2));
+
+    assert_eq!(store.get(b"order-1").unwrap(), None);
+    assert_eq!(store.checkpoint(0).unwrap(), Some(2));
+}
+
+#[test]
+fn test_sqlite_state_store_invalid_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::new(dir.path().join("state.db"), "orders; DROP TABLE x");
+
+    assert!(SqliteStateStore::open(&config).is_err());
+}
+
+#[test]
+fn test_checkpoints_per_partition() {
+    let store = InMemoryStateStore::new();
+    commit(&store, |_| {}, checkpoint(2, 5));
+    commit(&store, |_| {}, checkpoint(1, 3));
+    commit(&store, |_| {}, checkpoint(2, 6));
+
+    assert_eq!(
+        store.checkpoints().unwrap(),
+        vec![checkpoint(1, 3), checkpoint(2, 6)]
+    );
+}
+
+#[test]
+fn test_transaction_reads_own_writes() {
+    let store = InMemoryStateStore::new();
+    commit(&store, |state| state.put("a", "1"), checkpoint(0, 0));
+
+    let mut state = StateTransaction::new(&store);
+    assert_eq!(state.get("a").unwrap(), Some(b"1".to_vec()));
+    state.put("a", "2");
+    state.put("b", "3");
+    state.delete("b");
+
+    assert_eq!(state.get("a").unwrap(), Some(b"2".to_vec()));
+    assert_eq!(state.get("b").unwrap(), None);
+    // Nothing is visible in the store before the commit.
+    assert_eq!(store.get(b"a").unwrap(), Some(b"1".to_vec()));
+    assert_eq!(state.changes().len(), 2);
+}
+
+fn commit(store: &dyn StateStore, write: impl FnOnce(&mut StateTransaction), at: Checkpoint) {
+    let mut state = StateTransaction::new(store);
+    write(&mut state);
+    let changes = state.changes().clone();
+    store.commit(&changes, at).unwrap();
+}
+
+fn checkpoint(partition_id: u32, offset: u64) -> Checkpoint {
+    Checkpoint {
+        partition_id,
+        offset,
+    }
+}
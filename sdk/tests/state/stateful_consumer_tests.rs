@@ -0,0 +1,110 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    ConsumeMessagesConfig, EventConsumerError, InMemoryStateStore, MessageHeaders,
+    PayloadCompression, StateStore, StateTransaction, StatefulConsumer, StatefulEventConsumer,
+};
+use std::str::FromStr;
+use tokio::sync::mpsc;
+
+/// Counts the messages per payload and fails on the payload `fail`.
+#[derive(Debug)]
+struct CountByPayload;
+
+impl StatefulEventConsumer for CountByPayload {
+    async fn consume(
+        &self,
+        message: PolledMessage,
+        state: &mut StateTransaction<'_>,
+    ) -> Result<(), EventConsumerError> {
+        let key = message.payload.to_vec();
+        let count = state
+            .get(&key)
+            .map_err(|err| EventConsumerError::new(err.to_string()))?
+            .map_or(0, |value| value[0]);
+        state.put(&key, vec![count + 1]);
+
+        if key == b"fail" {
+            return Err(EventConsumerError::new("failed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_stateful_consumer_commits_state_with_offset() {
+    let consumer = StatefulConsumer::new(InMemoryStateStore::new(), CountByPayload);
+
+    assert!(consumer.process(1, polled_message(0, "a")).await.unwrap());
+    assert!(consumer.process(1, polled_message(1, "a")).await.unwrap());
+    assert!(consumer.process(2, polled_message(0, "b")).await.unwrap());
+
+    assert_eq!(count(&consumer, "a"), 2);
+    assert_eq!(count(&consumer, "b"), 1);
+    assert_eq!(consumer.store().checkpoint(1).unwrap(), Some(1));
+    assert_eq!(consumer.store().checkpoint(2).unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_stateful_consumer_skips_checkpointed_messages() {
+    let consumer = StatefulConsumer::new(InMemoryStateStore::new(), CountByPayload);
+    consumer.process(1, polled_message(0, "a")).await.unwrap();
+    consumer.process(1, polled_message(1, "a")).await.unwrap();
+
+    // Redelivered after a restart.
+    assert!(!consumer.process(1, polled_message(1, "a")).await.unwrap());
+
+    assert_eq!(count(&consumer, "a"), 2);
+}
+
+#[tokio::test]
+async fn test_stateful_consumer_discards_failed_changes() {
+    let (failure_sender, mut failures) = mpsc::unbounded_channel();
+    let consumer = StatefulConsumer::new(InMemoryStateStore::new(), CountByPayload)
+        .with_consume_config(ConsumeMessagesConfig::new(
+            None,
+            1,
+            IggyDuration::from_str("1ms").unwrap(),
+            Some(failure_sender),
+        ));
+
+    assert!(!consumer
+        .process(1, polled_message(4, "fail"))
+        .await
+        .unwrap());
+
+    // Neither attempt left a write behind.
+    assert_eq!(count(&consumer, "fail"), 0);
+    assert_eq!(consumer.store().checkpoint(1).unwrap(), Some(4));
+    let failure = failures.try_recv().unwrap();
+    assert_eq!(failure.offset, 4);
+    assert_eq!(failure.attempts, 2);
+}
+
+#[tokio::test]
+async fn test_stateful_consumer_decompresses_payload() {
+    let consumer = StatefulConsumer::new(InMemoryStateStore::new(), CountByPayload);
+    let compression = PayloadCompression::gzip();
+    let message = PolledMessage {
+        headers: Some(
+            MessageHeaders::new()
+                .content_encoding(compression.name())
+                .build()
+                .unwrap(),
+        ),
+        ..polled_message(0, compression.compress(b"a").unwrap())
+    };
+
+    assert!(consumer.process(1, message).await.unwrap());
+
+    assert_eq!(count(&consumer, "a"), 1);
+}
+
+fn count(consumer: &StatefulConsumer<InMemoryStateStore, CountByPayload>, key: &str) -> u8 {
+    consumer
+        .store()
+        .get(key.as_bytes())
+        .unwrap()
+        .map_or(0, |value| value[0])
+}
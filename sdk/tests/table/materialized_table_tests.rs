@@ -0,0 +1,123 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use sdk::builder::{
+    HeaderWindowKey, InMemoryStateStore, MaterializedTable, MaterializedTableConfig,
+    MessageHeaders, SqliteStateStore, StateStore, StateStoreConfig,
+};
+
+#[test]
+fn test_table_keeps_latest_value() {
+    let table = table(InMemoryStateStore::new());
+
+    table.apply(0, &message(0, "EUR/USD", "1.08")).unwrap();
+    table.apply(0, &message(1, "GBP/USD", "1.27")).unwrap();
+    table.apply(0, &message(2, "EUR/USD", "1.09")).unwrap();
+
+    assert_eq!(table.get("EUR/USD").unwrap().unwrap(), "1.09");
+    assert_eq!(table.get("GBP/USD").unwrap().unwrap(), "1.27");
+    assert_eq!(table.get("USD/JPY").unwrap(), None);
+}
+
+#[test]
+fn test_empty_payload_deletes_key() {
+    let table = table(InMemoryStateStore::new());
+    table.apply(0, &message(0, "EUR/USD", "1.08")).unwrap();
+
+    let change = table.apply(0, &message(1, "EUR/USD", "")).unwrap().unwrap();
+
+    assert_eq!(change.previous.unwrap(), "1.08");
+    assert_eq!(change.value, None);
+    assert_eq!(table.get("EUR/USD").unwrap(), None);
+}
+
+#[test]
+fn test_messages_without_key_are_skipped() {
+    let table = table(InMemoryStateStore::new());
+    let mut message = message(0, "EUR/USD", "1.08");
+    message.headers = None;
+
+    assert!(table.apply(0, &message).unwrap().is_none());
+
+    assert_eq!(table.skipped_messages(), 1);
+    assert_eq!(table.store().checkpoint(0).unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_subscribers_receive_changes() {
+    let table = table(InMemoryStateStore::new());
+    let mut changes = table.subscribe();
+
+    table.apply(3, &message(0, "EUR/USD", "1.08")).unwrap();
+    table.apply(3, &message(1, "EUR/USD", "1.09")).unwrap();
+
+    let first = changes.recv().await.unwrap();
+    assert_eq!((first.partition_id, first.offset), (3, 0));
+    assert_eq!(first.previous, None);
+    let second = changes.recv().await.unwrap();
+    assert_eq!(second.previous.unwrap(), "1.08");
+    assert_eq!(second.value.unwrap(), "1.09");
+}
+
+#[test]
+fn test_caught_up_after_head_offsets() {
+    let table = table(InMemoryStateStore::new());
+    table.apply(1, &message(0, "EUR/USD", "1.08")).unwrap();
+    // Not caught up while the head is unknown.
+    assert!(!table.is_caught_up());
+
+    table
+        .set_head_offsets([(1, Some(1)), (2, Some(0)), (3, None)])
+        .unwrap();
+    assert!(!table.is_caught_up());
+
+    table.apply(1, &message(1, "EUR/USD", "1.09")).unwrap();
+    assert!(!table.is_caught_up());
+    table.apply(2, &message(0, "GBP/USD", "1.27")).unwrap();
+
+    assert!(table.is_caught_up());
+    assert!(*table.caught_up().borrow());
+}
+
+#[test]
+fn test_empty_topic_is_caught_up() {
+    let table = table(InMemoryStateStore::new());
+
+    table.set_head_offsets([(1, None)]).unwrap();
+
+    assert!(table.is_caught_up());
+}
+
+#[test]
+fn test_on_disk_table_resumes_from_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = StateStoreConfig::from_path(dir.path().join("prices.db"));
+    {
+        let table = table(SqliteStateStore::open(&config).unwrap());
+        table.apply(0, &message(0, "EUR/USD", "1.08")).unwrap();
+        table.apply(0, &message(1, "EUR/USD", "1.09")).unwrap();
+    }
+
+    let table = table(SqliteStateStore::open(&config).unwrap());
+    table.set_head_offsets([(0, Some(1))]).unwrap();
+
+    assert!(table.is_caught_up());
+    assert_eq!(table.get("EUR/USD").unwrap().unwrap(), "1.09");
+    // Redelivered messages are not applied again.
+    assert!(table
+        .apply(0, &message(0, "EUR/USD", "1.08"))
+        .unwrap()
+        .is_none());
+    assert_eq!(table.get("EUR/USD").unwrap().unwrap(), "1.09");
+}
+
+fn table<S: StateStore>(store: S) -> MaterializedTable<S> {
+    MaterializedTable::new(MaterializedTableConfig::default(), store, HeaderWindowKey)
+}
+
+fn message(offset: u64, key: &str, payload: &str) -> PolledMessage {
+    let headers = MessageHeaders::new().partition_key(key).build().unwrap();
+    PolledMessage {
+        headers: Some(headers),
+        ..polled_message(offset, payload.to_string())
+    }
+}
@@ -0,0 +1 @@
+mod materialized_table_tests;
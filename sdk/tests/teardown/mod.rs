@@ -0,0 +1 @@
+mod teardown_guard_tests;
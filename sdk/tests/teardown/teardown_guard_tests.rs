@@ -0,0 +1,69 @@
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use sdk::builder::{is_not_found_error, IggyProducerConfig, IggyStreamProducer, TeardownGuard};
+use std::sync::Arc;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_teardown_reports_error() {
+    // The client is not connected, so the stream cannot be deleted.
+    let client = Arc::new(IggyClient::default());
+    let guard = IggyStreamProducer::teardown_guard(client, &IggyProducerConfig::default()).unwrap();
+
+    let res = guard.teardown().await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_disarmed_guard_keeps_stream() {
+    let client = Arc::new(IggyClient::default());
+    let stream_id = Identifier::named("test_stream").unwrap();
+    let guard = TeardownGuard::new(client, stream_id.clone()).unwrap();
+    assert_eq!(guard.stream_id(), Some(&stream_id));
+
+    // The disarmed guard hands the stream back instead of deleting it.
+    assert_eq!(guard.disarm(), Some(stream_id));
+}
+
+#[tokio::test]
+async fn test_guard_requires_multi_thread_runtime() {
+    let client = Arc::new(IggyClient::default());
+
+    let res = TeardownGuard::new(client, Identifier::named("test_stream").unwrap());
+
+    assert!(matches!(res, Err(IggyError::InvalidConfiguration)));
+}
+
+#[test]
+fn test_guard_outside_runtime() {
+    let client = Arc::new(IggyClient::default());
+
+    let res = TeardownGuard::new(client, Identifier::named("test_stream").unwrap());
+
+    assert!(matches!(res, Err(IggyError::InvalidConfiguration)));
+}
+
+#[test]
+fn test_not_found_errors_are_ignored_by_teardown() {
+    let not_found = [
+        IggyError::ResourceNotFound("test".to_string()),
+        IggyError::StreamIdNotFound(1),
+        IggyError::StreamNameNotFound("test_stream".to_string()),
+        IggyError::TopicIdNotFound(1, 1),
+        IggyError::TopicNameNotFound("test_topic".to_string(), "test_stream".to_string()),
+        IggyError::ConsumerGroupIdNotFound(1, 1),
+        IggyError::ConsumerGroupNameNotFound("test_group".to_string(), "test_topic".to_string()),
+    ];
+    for err in &not_found {
+        assert!(is_not_found_error(err), "{err}");
+    }
+
+    for err in [
+        IggyError::Disconnected,
+        IggyError::Unauthorized,
+        IggyError::InvalidConfiguration,
+    ] {
+        assert!(!is_not_found_error(&err), "{err}");
+    }
+}
@@ -0,0 +1,171 @@
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use sdk::builder::{
+    StreamSpec, StreamState, TopicSpec, TopicState, TopologyChange, TopologyPlan, TopologySpec,
+    TopologyState,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_plan_creates_everything_on_empty_server() {
+    let plan = TopologyPlan::diff(&spec(), &TopologyState::default());
+
+    let changes: Vec<String> = plan.changes().iter().map(ToString::to_string).collect();
+    assert_eq!(
+        changes,
+        vec![
+            "create stream orders",
+            "create topic orders/created with 3 partitions",
+            "create consumer group billing on orders/created",
+            "create consumer group shipping on orders/created",
+        ]
+    );
+    assert!(plan.drift().is_empty());
+}
+
+#[test]
+fn test_plan_in_sync() {
+    let plan = TopologyPlan::diff(&spec(), &state(topic_state()));
+
+    assert!(plan.is_in_sync());
+}
+
+#[test]
+fn test_plan_creates_missing_consumer_group() {
+    let mut topic = topic_state();
+    topic.consumer_groups.retain(|group| group != "shipping");
+
+    let plan = TopologyPlan::diff(&spec(), &state(topic));
+
+    assert_eq!(
+        plan.changes(),
+        &[TopologyChange::CreateConsumerGroup {
+            stream: "orders".to_string(),
+            topic: "created".to_string(),
+            consumer_group: "shipping".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_plan_updates_topic_settings() {
+    let mut topic = topic_state();
+    topic.message_expiry = IggyExpiry::NeverExpire;
+    topic.compression_algorithm = CompressionAlgorithm::None;
+    topic.max_topic_size = MaxTopicSize::Unlimited;
+
+    let plan = TopologyPlan::diff(&spec(), &state(topic));
+
+    assert!(plan.drift().is_empty());
+    assert_eq!(
+        plan.changes(),
+        &[TopologyChange::UpdateTopic {
+            stream: "orders".to_string(),
+            topic: "created".to_string(),
+            compression_algorithm: CompressionAlgorithm::Gzip,
+            replication_factor: Some(1),
+            message_expiry: expiry(),
+            // Left at the server default in the spec, so the server value is kept.
+            max_topic_size: MaxTopicSize::Unlimited,
+            settings: vec!["compression_algorithm", "message_expiry"],
+        }]
+    );
+    assert_eq!(
+        plan.changes()[0].to_string(),
+        "update compression_algorithm, message_expiry of topic orders/created"
+    );
+}
+
+#[test]
+fn test_plan_creates_missing_partitions() {
+    let mut topic = topic_state();
+    topic.partitions_count = 1;
+
+    let plan = TopologyPlan::diff(&spec(), &state(topic));
+
+    assert!(plan.drift().is_empty());
+    assert_eq!(
+        plan.changes(),
+        &[TopologyChange::CreatePartitions {
+            stream: "orders".to_string(),
+            topic: "created".to_string(),
+            partitions_count: 2,
+        }]
+    );
+    assert_eq!(
+        plan.changes()[0].to_string(),
+        "add 2 partitions to topic orders/created"
+    );
+}
+
+#[test]
+fn test_plan_reports_partition_decrease_as_drift() {
+    let mut topic = topic_state();
+    topic.partitions_count = 5;
+
+    let plan = TopologyPlan::diff(&spec(), &state(topic));
+
+    assert!(plan.changes().is_empty());
+    let settings: Vec<&str> = plan.drift().iter().map(|drift| drift.setting).collect();
+    assert_eq!(settings, vec!["partitions_count"]);
+    assert_eq!(plan.drift()[0].expected, "3");
+    assert_eq!(plan.drift()[0].actual, "5");
+}
+
+#[test]
+fn test_plan_ignores_server_defaults() {
+    let spec = TopologySpec::new(vec![StreamSpec::new(
+        "orders",
+        vec![TopicSpec::new("created", 3)],
+    )]);
+    // The compression of the server differs from the default, but is not set in the spec.
+    let mut topic = topic_state();
+    topic.replication_factor = 3;
+    topic.max_topic_size = MaxTopicSize::Unlimited;
+
+    let plan = TopologyPlan::diff(&spec, &state(topic));
+
+    assert!(plan.is_in_sync());
+}
+
+fn spec() -> TopologySpec {
+    TopologySpec::builder()
+        .streams(vec![StreamSpec::builder()
+            .name("orders")
+            .topics(vec![TopicSpec::builder()
+                .name("created")
+                .partitions_count(3)
+                .message_expiry(expiry())
+                .compression_algorithm(CompressionAlgorithm::Gzip)
+                .consumer_groups(vec!["billing".to_string(), "shipping".to_string()])
+                .build()])
+            .build()])
+        .build()
+}
+
+fn state(topic: TopicState) -> TopologyState {
+    TopologyState {
+        streams: vec![StreamState {
+            name: "orders".to_string(),
+            topics: vec![topic],
+        }],
+    }
+}
+
+fn topic_state() -> TopicState {
+    TopicState {
+        name: "created".to_string(),
+        partitions_count: 3,
+        replication_factor: 1,
+        message_expiry: expiry(),
+        compression_algorithm: CompressionAlgorithm::Gzip,
+        max_topic_size: MaxTopicSize::ServerDefault,
+        consumer_groups: vec!["billing".to_string(), "shipping".to_string()],
+    }
+}
+
+fn expiry() -> IggyExpiry {
+    IggyExpiry::ExpireDuration(IggyDuration::from_str("7days").unwrap())
+}
@@ -0,0 +1,2 @@
+mod windowed_aggregation_tests;
+mod windowed_event_consumer_tests;
@@ -0,0 +1,148 @@
+use crate::common::polled_message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    CountAggregate, MessageTimestamp, WindowResult, WindowedAggregation, WindowedAggregationConfig,
+};
+use std::str::FromStr;
+
+const SECOND: u64 = 1_000_000;
+
+#[test]
+fn test_tumbling_windows() {
+    let config = WindowedAggregationConfig::tumbling(duration("10s"), duration("0s"));
+    let mut aggregation = aggregation(config);
+
+    assert!(aggregation.push(&message("a", 1)).is_empty());
+    assert!(aggregation.push(&message("a", 9)).is_empty());
+    assert!(aggregation.push(&message("b", 5)).is_empty());
+
+    // The watermark reaches the end of the first window.
+    let results = aggregation.push(&message("a", 10));
+
+    assert_eq!(counts(&results), vec![("a", 0, 10, 2), ("b", 0, 10, 1)]);
+    assert_eq!(aggregation.open_windows(), 1);
+}
+
+#[test]
+fn test_hopping_windows() {
+    let config =
+        WindowedAggregationConfig::hopping(duration("10s"), duration("5s"), duration("0s"));
+    let mut aggregation = aggregation(config);
+
+    aggregation.push(&message("a", 7));
+    let mut results = aggregation.push(&message("a", 12));
+    results.extend(aggregation.flush());
+
+    assert_eq!(
+        counts(&results),
+        vec![("a", 0, 10, 1), ("a", 5, 15, 2), ("a", 10, 20, 1)]
+    );
+}
+
+#[test]
+fn test_session_windows_merge() {
+    let config = WindowedAggregationConfig::session(duration("5s"), duration("10s"));
+    let mut aggregation = aggregation(config);
+
+    aggregation.push(&message("a", 0));
+    aggregation.push(&message("a", 10));
+    assert_eq!(aggregation.open_windows(), 2);
+
+    // Bridges both sessions.
+    aggregation.push(&message("a", 5));
+    assert_eq!(aggregation.open_windows(), 1);
+
+    let results = aggregation.push(&message("b", 30));
+
+    assert_eq!(counts(&results), vec![("a", 0, 15, 3)]);
+}
+
+#[test]
+fn test_allowed_lateness() {
+    let config = WindowedAggregationConfig::tumbling(duration("10s"), duration("5s"));
+    let mut aggregation = aggregation(config);
+
+    aggregation.push(&message("a", 1));
+    assert!(aggregation.push(&message("a", 12)).is_empty());
+
+    // Late, but within the allowed lateness.
+    assert!(aggregation.push(&message("a", 8)).is_empty());
+
+    let results = aggregation.push(&message("a", 15));
+    assert_eq!(counts(&results), vec![("a", 0, 10, 2)]);
+
+    // The first window is closed.
+    assert!(aggregation.push(&message("a", 3)).is_empty());
+    assert_eq!(aggregation.late_messages(), 1);
+}
+
+#[test]
+fn test_skips_messages_without_key() {
+    let config = WindowedAggregationConfig::tumbling(duration("10s"), duration("0s"));
+    let mut aggregation = aggregation(config);
+
+    aggregation.push(&message("", 1));
+
+    assert_eq!(aggregation.skipped_messages(), 1);
+    assert!(aggregation.watermark().is_none());
+}
+
+#[test]
+fn test_invalid_config() {
+    let config =
+        WindowedAggregationConfig::hopping(duration("5s"), duration("10s"), duration("0s"));
+
+    assert!(WindowedAggregation::new(config, key, MessageTimestamp, CountAggregate).is_err());
+}
+
+#[test]
+fn test_event_time_near_max_does_not_overflow() {
+    let config =
+        WindowedAggregationConfig::hopping(duration("10s"), duration("5s"), duration("0s"));
+    let mut aggregation = aggregation(config);
+
+    let mut message = message("a", 0);
+    message.timestamp = u64::MAX - 1;
+    assert!(aggregation.push(&message).is_empty());
+
+    let results = aggregation.flush();
+    assert_eq!(results.len(), 2);
+    assert!(results
+        .iter()
+        .all(|result| result.window.end.as_micros() == u64::MAX && result.value == 1));
+}
+
+fn aggregation(config: WindowedAggregationConfig) -> WindowedAggregation<CountAggregate> {
+    WindowedAggregation::new(config, key, MessageTimestamp, CountAggregate).unwrap()
+}
+
+fn key(message: &PolledMessage) -> Option<String> {
+    let key = String::from_utf8_lossy(&message.payload).to_string();
+    (!key.is_empty()).then_some(key)
+}
+
+fn message(key: &str, seconds: u64) -> PolledMessage {
+    PolledMessage {
+        timestamp: seconds * SECOND,
+        ..polled_message(0, key.to_string())
+    }
+}
+
+fn counts(results: &[WindowResult<u64>]) -> Vec<(&str, u64, u64, u64)> {
+    results
+        .iter()
+        .map(|result| {
+            (
+                result.key.as_str(),
+                result.window.start.as_micros() / SECOND,
+                result.window.end.as_micros() / SECOND,
+                result.value,
+            )
+        })
+        .collect()
+}
+
+fn duration(value: &str) -> IggyDuration {
+    IggyDuration::from_str(value).unwrap()
+}
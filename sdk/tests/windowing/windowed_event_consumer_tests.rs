@@ -0,0 +1,114 @@
+use crate::common::{polled_message, RecordingEventProducer};
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::PolledMessage;
+use iggy::utils::duration::IggyDuration;
+use sdk::builder::{
+    CountAggregate, EventConsumer, MessageTimestamp, ProducerWindowHandler, WindowResult,
+    WindowedAggregation, WindowedAggregationConfig, WindowedEventConsumer,
+};
+use std::str::FromStr;
+
+const SECOND: u64 = 1_000_000;
+
+#[tokio::test]
+async fn test_window_results_are_produced() {
+    let consumer = windowed_consumer();
+
+    consumer.consume(message("a", 1)).await.unwrap();
+    consumer.consume(message("a", 2)).await.unwrap();
+    assert!(consumer.handler().producer().sent().is_empty());
+
+    consumer.consume(message("a", 11)).await.unwrap();
+
+    let sent = consumer.handler().producer().sent();
+    assert_eq!(payloads(&sent), vec!["a:0:2"]);
+}
+
+#[tokio::test]
+async fn test_flush_produces_open_windows() {
+    let consumer = windowed_consumer();
+
+    consumer.consume(message("a", 1)).await.unwrap();
+    consumer.consume(message("b", 2)).await.unwrap();
+    consumer.flush().await.unwrap();
+
+    let sent = consumer.handler().producer().sent();
+    assert_eq!(payloads(&sent), vec!["a:0:1", "b:0:1"]);
+}
+
+#[tokio::test]
+async fn test_failed_results_stay_pending() {
+    let consumer = windowed_consumer();
+    consumer.consume(message("a", 1)).await.unwrap();
+    consumer.consume(message("a", 2)).await.unwrap();
+
+    consumer.handler().producer().fail_all();
+    assert!(consumer.consume(message("a", 11)).await.is_err());
+    // Retried like `run_with_retries` does.
+    assert!(consumer.consume(message("a", 11)).await.is_err());
+    consumer.handler().producer().clear_failures();
+    consumer.consume(message("a", 11)).await.unwrap();
+    consumer.flush().await.unwrap();
+
+    let sent = consumer.handler().producer().sent();
+    assert_eq!(payloads(&sent), vec!["a:0:2", "a:10:1"]);
+}
+
+#[tokio::test]
+async fn test_process_skips_aggregated_offsets() {
+    let consumer = windowed_consumer();
+
+    consumer.process(1, message("a", 1)).await.unwrap();
+    consumer.process(2, message("a", 1)).await.unwrap();
+    // Delivered again.
+    consumer.process(1, message("a", 1)).await.unwrap();
+    consumer.flush().await.unwrap();
+
+    let sent = consumer.handler().producer().sent();
+    assert_eq!(payloads(&sent), vec!["a:0:2"]);
+}
+
+type EncodeFn = fn(&WindowResult<u64>) -> Result<Message, IggyError>;
+
+fn windowed_consumer(
+) -> WindowedEventConsumer<CountAggregate, ProducerWindowHandler<RecordingEventProducer, EncodeFn>>
+{
+    let config = WindowedAggregationConfig::tumbling(
+        IggyDuration::from_str("10s").unwrap(),
+        IggyDuration::from_str("0s").unwrap(),
+    );
+    let aggregation = WindowedAggregation::new(
+        config,
+        |message: &PolledMessage| Some(String::from_utf8_lossy(&message.payload).to_string()),
+        MessageTimestamp,
+        CountAggregate,
+    )
+    .unwrap();
+
+    let handler = ProducerWindowHandler::new(RecordingEventProducer::default(), encode as EncodeFn);
+    WindowedEventConsumer::new(aggregation, handler)
+}
+
+fn encode(result: &WindowResult<u64>) -> Result<Message, IggyError> {
+    Message::from_str(&format!(
+        "{}:{}:{}",
+        result.key,
+        result.window.start.as_micros() / SECOND,
+        result.value
+    ))
+}
+
+fn message(key: &str, seconds: u64) -> PolledMessage {
+    PolledMessage {
+        timestamp: seconds * SECOND,
+        ..polled_message(seconds, key.to_string())
+    }
+}
+
+fn payloads(messages: &[Message]) -> Vec<String> {
+    messages
+        .iter()
+        .map(|message| String::from_utf8_lossy(&message.payload).to_string())
+        .collect()
+}